@@ -0,0 +1,663 @@
+//! Animation: interpolation of style values, easing curves and transitions
+//!
+//! Widgets keep an [`Animated`] value per animated property, and retarget it every frame with the
+//! value for their current state. [`StateStyle::animate`](crate::style::StateStyle::animate)
+//! does that with the transition declared on the style.
+
+use std::time::Duration;
+
+use crate::color::Color;
+use crate::math::{Point, Rect, Size};
+use crate::paint::Paint;
+use crate::style::{Border, Borders, BoxStyle, CornerRadius, Margin, Padding};
+
+/// Values which can be blended for animation
+///
+/// # Notes
+///
+/// - `t` outside of `(0.0..=1.0)` extrapolates, so springs can overshoot. Results stay valid:
+///   color channels are clamped, and lengths like padding and border widths stay non-negative.
+/// - Values which can't be blended, like [`BorderStyle`](crate::style::BorderStyle), switch
+///   from `self` to `other` at `t == 0.5`
+pub trait Interpolate {
+    /// Blend between `self` and `other`
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Value at `t == 1.0`
+    /// * `t` - Interpolation factor
+    ///
+    /// # Returns
+    ///
+    /// `self` at `t == 0.0`, `other` at `t == 1.0`, and the mix of both in between
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::animation::Interpolate;
+    /// use ho_gui::math::Point;
+    ///
+    /// let start = Point::new(0.0, 10.0);
+    /// let end = Point::new(10.0, 20.0);
+    ///
+    /// assert_eq!(start.interpolate(&end, 0.5), Point::new(5.0, 15.0));
+    /// assert_eq!(start.interpolate(&end, 1.5), Point::new(15.0, 25.0));
+    /// ```
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+/// Pick `from` before the halfway point and `to` from it on
+fn step<T: Clone>(from: &T, to: &T, t: f32) -> T {
+    if t < 0.5 { from.clone() } else { to.clone() }
+}
+
+impl Interpolate for f32 {
+    /// Linear blend. Infinite values, like an unbounded `max_size`, switch at `t == 0.5`.
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        if self == other {
+            return *self;
+        }
+        if !self.is_finite() || !other.is_finite() {
+            return step(self, other, t);
+        }
+
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for Color {
+    /// Component-wise blend in sRGB, unlike [`Color::lerp`] without clamping `t`
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Color::new(
+            self.r.interpolate(&other.r, t),
+            self.g.interpolate(&other.g, t),
+            self.b.interpolate(&other.b, t),
+            self.a.interpolate(&other.a, t),
+        )
+    }
+}
+
+impl Interpolate for Point {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Point::new(
+            self.x.interpolate(&other.x, t),
+            self.y.interpolate(&other.y, t),
+        )
+    }
+}
+
+impl Interpolate for Size {
+    /// Component-wise blend, clamped to non-negative sizes
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Size::new(
+            self.width.interpolate(&other.width, t).max(0.0),
+            self.height.interpolate(&other.height, t).max(0.0),
+        )
+    }
+}
+
+impl Interpolate for Rect {
+    /// Blends position and size separately, so the size stays non-negative
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Rect {
+            pos: self.pos.interpolate(&other.pos, t),
+            size: self.size.interpolate(&other.size, t),
+        }
+    }
+}
+
+impl Interpolate for Padding {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Padding::new(
+            self.left.interpolate(&other.left, t),
+            self.right.interpolate(&other.right, t),
+            self.top.interpolate(&other.top, t),
+            self.bottom.interpolate(&other.bottom, t),
+        )
+    }
+}
+
+impl Interpolate for Margin {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Margin::new(
+            self.left.interpolate(&other.left, t),
+            self.right.interpolate(&other.right, t),
+            self.top.interpolate(&other.top, t),
+            self.bottom.interpolate(&other.bottom, t),
+        )
+    }
+}
+
+impl Interpolate for Border {
+    /// Blends width and color. The line style switches at `t == 0.5`.
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Border::new(
+            self.width.interpolate(&other.width, t),
+            self.color.interpolate(&other.color, t),
+        )
+        .with_style(step(&self.style, &other.style, t))
+    }
+}
+
+impl Interpolate for Borders {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Borders::new(
+            self.top.interpolate(&other.top, t),
+            self.right.interpolate(&other.right, t),
+            self.bottom.interpolate(&other.bottom, t),
+            self.left.interpolate(&other.left, t),
+        )
+    }
+}
+
+impl Interpolate for CornerRadius {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        CornerRadius::new(
+            self.top_left.interpolate(&other.top_left, t),
+            self.top_right.interpolate(&other.top_right, t),
+            self.bottom_right.interpolate(&other.bottom_right, t),
+            self.bottom_left.interpolate(&other.bottom_left, t),
+        )
+    }
+}
+
+impl Interpolate for Paint {
+    /// Blends solid colors. Gradients switch at `t == 0.5`.
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (Paint::Solid(from), Paint::Solid(to)) => Paint::Solid(from.interpolate(to, t)),
+            _ => step(self, other, t),
+        }
+    }
+}
+
+impl Interpolate for BoxStyle {
+    /// Blends every property
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        BoxStyle {
+            margin: self.margin.interpolate(&other.margin, t),
+            borders: self.borders.interpolate(&other.borders, t),
+            padding: self.padding.interpolate(&other.padding, t),
+            background: self.background.interpolate(&other.background, t),
+            corner_radius: self.corner_radius.interpolate(&other.corner_radius, t),
+            min_size: self.min_size.interpolate(&other.min_size, t),
+            max_size: self.max_size.interpolate(&other.max_size, t),
+        }
+    }
+}
+
+/// Easing curve mapping the time progress of a transition to its value progress
+///
+/// # Examples
+/// ```
+/// use ho_gui::animation::Easing;
+///
+/// assert_eq!(Easing::Linear.apply(0.25), 0.25);
+/// assert_eq!(Easing::CubicIn.apply(0.5), 0.125);
+///
+/// // Springs overshoot before settling
+/// let peak = (1..100).map(|i| Easing::SPRING.apply(i as f32 / 100.0)).fold(0.0, f32::max);
+/// assert!(peak > 1.1);
+/// assert_eq!(Easing::SPRING.apply(1.0), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    /// Constant speed
+    #[default]
+    Linear,
+
+    /// Starts slow, `t³`
+    CubicIn,
+
+    /// Ends slow, the mirror of `CubicIn`
+    CubicOut,
+
+    /// Starts and ends slow
+    CubicInOut,
+
+    /// Damped spring released from the start value
+    ///
+    /// The spring snaps to the end value when the transition ends, so pick a `frequency` and
+    /// `damping_ratio` which settle within the duration, like [`Easing::SPRING`].
+    Spring {
+        /// Damping ratio. Values below 1.0 oscillate around the end value, and 1.0 or more
+        /// approach it without overshooting.
+        damping_ratio: f32,
+
+        /// Undamped angular frequency, in radians per transition duration
+        frequency: f32,
+    },
+}
+
+impl Easing {
+    /// Bouncy spring which overshoots by about 16% and settles within the transition
+    pub const SPRING: Self = Easing::Spring {
+        damping_ratio: 0.5,
+        frequency: 12.0,
+    };
+
+    /// Map the time progress `t` of a transition to the value progress
+    ///
+    /// # Notes
+    ///
+    /// - `t` will be clamped to the range `(0.0..=1.0)`
+    /// - Every curve gives 0.0 at `t == 0.0` and 1.0 at `t == 1.0`, and only `Spring` leaves the
+    ///   range in between
+    pub fn apply(self, t: f32) -> f32 {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+
+        match self {
+            Easing::Linear => t,
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+                }
+            }
+            Easing::Spring {
+                damping_ratio,
+                frequency,
+            } => {
+                if t >= 1.0 {
+                    return 1.0;
+                }
+                spring(damping_ratio.max(0.0), frequency.max(0.0), t)
+            }
+        }
+    }
+}
+
+/// Position of a unit step response of a damped harmonic oscillator at time `t`
+fn spring(damping_ratio: f32, frequency: f32, t: f32) -> f32 {
+    let decay = (-damping_ratio * frequency * t).exp();
+
+    if damping_ratio < 1.0 {
+        let damped = frequency * (1.0 - damping_ratio * damping_ratio).sqrt();
+        let phase = damped * t;
+        1.0 - decay * (phase.cos() + damping_ratio * frequency / damped * phase.sin())
+    } else {
+        // Critically damped, which also stands in for overdamped springs
+        1.0 - decay * (1.0 + frequency * t)
+    }
+}
+
+/// How a style value changes to a new target: duration and easing
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use ho_gui::animation::{Easing, Transition};
+///
+/// let hover = Transition::new(Duration::from_millis(150), Easing::CubicOut);
+///
+/// assert_eq!(hover.progress(Duration::ZERO), 0.0);
+/// assert_eq!(hover.progress(Duration::from_millis(150)), 1.0);
+/// assert_eq!(Transition::NONE.progress(Duration::ZERO), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Transition {
+    /// Time to reach the target. Zero changes the value instantly.
+    pub duration: Duration,
+
+    /// Easing curve
+    pub easing: Easing,
+}
+
+impl Transition {
+    /// Instant change without animation
+    pub const NONE: Self = Self::new(Duration::ZERO, Easing::Linear);
+
+    /// Create Transition with given duration and easing
+    pub const fn new(duration: Duration, easing: Easing) -> Self {
+        Self { duration, easing }
+    }
+
+    /// Eased value progress after `elapsed` time, 1.0 once the transition is done
+    pub fn progress(&self, elapsed: Duration) -> f32 {
+        if elapsed >= self.duration {
+            return 1.0;
+        }
+
+        self.easing
+            .apply(elapsed.div_duration_f64(self.duration) as f32)
+    }
+}
+
+/// Per-widget animation state of one style value
+///
+/// # Notes
+///
+/// - Retargeting while a transition runs starts the new transition from the current value, so
+///   quickly toggled states don't jump
+/// - Time is advanced by the caller, usually with the frame delta time
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use ho_gui::animation::{Animated, Easing, Transition};
+/// use ho_gui::color::Color;
+///
+/// let hover = Transition::new(Duration::from_millis(150), Easing::Linear);
+/// let mut background = Animated::new(Color::BLACK);
+///
+/// // Hover starts
+/// background.set_target(Color::WHITE, hover);
+/// background.advance(Duration::from_millis(75));
+/// assert_eq!(background.value(), Color::rgb(0.5, 0.5, 0.5));
+///
+/// background.advance(Duration::from_millis(75));
+/// assert_eq!(background.value(), Color::WHITE);
+/// assert!(!background.is_animating());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animated<T> {
+    from: T,
+    to: T,
+    elapsed: Duration,
+    transition: Transition,
+}
+
+impl<T: Interpolate + Clone + PartialEq> Animated<T> {
+    /// Create Animated resting at `value`
+    pub fn new(value: T) -> Self {
+        Self {
+            from: value.clone(),
+            to: value,
+            elapsed: Duration::ZERO,
+            transition: Transition::NONE,
+        }
+    }
+
+    /// Value the animation is heading to
+    pub fn target(&self) -> &T {
+        &self.to
+    }
+
+    /// Current value
+    pub fn value(&self) -> T {
+        if !self.is_animating() {
+            return self.to.clone();
+        }
+
+        self.from
+            .interpolate(&self.to, self.transition.progress(self.elapsed))
+    }
+
+    /// Check if a transition is still running
+    pub fn is_animating(&self) -> bool {
+        self.elapsed < self.transition.duration
+    }
+
+    /// Start a transition from the current value to `target`
+    ///
+    /// # Notes
+    ///
+    /// - Setting the current target again does nothing, so it can be called every frame
+    pub fn set_target(&mut self, target: T, transition: Transition) {
+        if target == self.to {
+            return;
+        }
+
+        self.from = self.value();
+        self.to = target;
+        self.elapsed = Duration::ZERO;
+        self.transition = transition;
+    }
+
+    /// Jump to `value` without a transition
+    pub fn set_value(&mut self, value: T) {
+        self.from = value.clone();
+        self.to = value;
+        self.transition = Transition::NONE;
+    }
+
+    /// Advance the running transition by `delta`
+    pub fn advance(&mut self, delta: Duration) {
+        self.elapsed = self.elapsed.saturating_add(delta);
+    }
+
+    /// Retarget, advance and read the value in one per-frame call
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Value for the current widget state
+    /// * `transition` - Transition used if `target` changed
+    /// * `delta` - Time since the last frame
+    ///
+    /// # Returns
+    ///
+    /// Value to draw this frame
+    pub fn update(&mut self, target: T, transition: Transition, delta: Duration) -> T {
+        self.set_target(target, transition);
+        self.advance(delta);
+        self.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{BorderStyle, StateStyle, WidgetState};
+    use approx::assert_relative_eq;
+
+    const HOVER: Transition = Transition::new(Duration::from_millis(150), Easing::Linear);
+    const FRAME: Duration = Duration::from_millis(10);
+
+    mod interpolate {
+        use super::*;
+
+        #[test]
+        fn test_geometry() {
+            let from = Rect::new(0.0, 0.0, 10.0, 20.0);
+            let to = Rect::new(10.0, -10.0, 30.0, 0.0);
+
+            assert_eq!(from.interpolate(&to, 0.0), from);
+            assert_eq!(from.interpolate(&to, 1.0), to);
+            assert_eq!(from.interpolate(&to, 0.5), Rect::new(5.0, -5.0, 20.0, 10.0));
+
+            // Overshooting a zero size stays valid
+            assert_eq!(from.interpolate(&to, 1.5).size, Size::new(40.0, 0.0));
+            assert_eq!(
+                Size::new(1.0, 2.0).interpolate(&Size::new(3.0, 2.0), -1.0),
+                Size::new(0.0, 2.0)
+            );
+        }
+
+        #[test]
+        fn test_color_clamps_overshoot() {
+            let gray = Color::rgb(0.5, 0.5, 0.5);
+
+            assert_eq!(
+                Color::BLACK.interpolate(&gray, 0.5),
+                Color::rgb(0.25, 0.25, 0.25)
+            );
+            assert_eq!(Color::BLACK.interpolate(&Color::WHITE, 1.2), Color::WHITE);
+            assert_eq!(gray.interpolate(&Color::WHITE, -2.0), Color::BLACK);
+        }
+
+        #[test]
+        fn test_box_values() {
+            let padding = Padding::all(4.0).interpolate(&Padding::symmetric(8.0, 0.0), 0.5);
+            assert_eq!(padding, Padding::new(6.0, 6.0, 2.0, 2.0));
+            assert_eq!(
+                Padding::all(4.0).interpolate(&Padding::zero(), 2.0),
+                Padding::zero()
+            );
+
+            let from = Border::new(1.0, Color::BLACK);
+            let to = Border::dashed(3.0, Color::WHITE);
+            let halfway = from.interpolate(&to, 0.5);
+            assert_eq!(halfway.width, 2.0);
+            assert_eq!(halfway.color, Color::rgb(0.5, 0.5, 0.5));
+            assert_eq!(halfway.style, BorderStyle::Dashed);
+            assert_eq!(from.interpolate(&to, 0.49).style, BorderStyle::Solid);
+        }
+
+        #[test]
+        fn test_box_style_keeps_unbounded_size() {
+            let normal = BoxStyle::new().with_background(Color::BLACK);
+            let hovered = normal
+                .clone()
+                .with_background(Color::WHITE)
+                .with_corner_radius(CornerRadius::all(8.0));
+
+            let halfway = normal.interpolate(&hovered, 0.5);
+            assert_eq!(halfway.background, Paint::Solid(Color::rgb(0.5, 0.5, 0.5)));
+            assert_eq!(halfway.corner_radius, CornerRadius::all(4.0));
+            assert_eq!(halfway.max_size, normal.max_size);
+            assert_eq!(halfway.borders, normal.borders);
+        }
+    }
+
+    mod easing {
+        use super::*;
+
+        const CURVES: [Easing; 6] = [
+            Easing::Linear,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+            Easing::SPRING,
+            Easing::Spring {
+                damping_ratio: 1.0,
+                frequency: 10.0,
+            },
+        ];
+
+        #[test]
+        fn test_endpoints() {
+            for easing in CURVES {
+                assert_eq!(easing.apply(0.0), 0.0, "{:?}", easing);
+                assert_eq!(easing.apply(1.0), 1.0, "{:?}", easing);
+                assert_eq!(easing.apply(-1.0), 0.0, "{:?}", easing);
+                assert_eq!(easing.apply(2.0), 1.0, "{:?}", easing);
+                assert_eq!(easing.apply(f32::NAN), 0.0, "{:?}", easing);
+            }
+        }
+
+        #[test]
+        fn test_cubic_shapes() {
+            assert_relative_eq!(Easing::CubicOut.apply(0.5), 0.875);
+            assert_relative_eq!(Easing::CubicInOut.apply(0.25), 0.0625);
+            assert_relative_eq!(Easing::CubicInOut.apply(0.5), 0.5);
+            assert_relative_eq!(Easing::CubicInOut.apply(0.75), 0.9375);
+
+            for easing in &CURVES[..4] {
+                let samples: Vec<f32> = (0..=100).map(|i| easing.apply(i as f32 / 100.0)).collect();
+                assert!(
+                    samples.windows(2).all(|pair| pair[0] <= pair[1]),
+                    "{:?}",
+                    easing
+                );
+            }
+        }
+
+        #[test]
+        fn test_spring() {
+            let samples: Vec<f32> = (0..100)
+                .map(|i| Easing::SPRING.apply(i as f32 / 100.0))
+                .collect();
+            let peak = samples.iter().copied().fold(0.0, f32::max);
+
+            assert_relative_eq!(peak, 1.163, epsilon = 0.005);
+            // Settled before snapping to the end
+            assert_relative_eq!(samples[99], 1.0, epsilon = 0.01);
+
+            // Critical damping never overshoots
+            let critical = CURVES[5];
+            assert!((0..=100).all(|i| critical.apply(i as f32 / 100.0) <= 1.0));
+        }
+    }
+
+    mod animated {
+        use super::*;
+
+        #[test]
+        fn test_hover_transition() {
+            let mut background = Animated::new(Color::BLACK);
+            assert!(!background.is_animating());
+
+            background.set_target(Color::WHITE, HOVER);
+            assert!(background.is_animating());
+            assert_eq!(background.value(), Color::BLACK);
+
+            for _ in 0..14 {
+                background.advance(FRAME);
+                assert!(background.is_animating());
+            }
+            background.advance(FRAME);
+            assert!(!background.is_animating());
+            assert_eq!(background.value(), Color::WHITE);
+        }
+
+        #[test]
+        fn test_same_target_keeps_running() {
+            let mut width = Animated::new(0.0);
+            width.update(10.0, HOVER, Duration::from_millis(75));
+            assert_eq!(width.update(10.0, HOVER, Duration::ZERO), 5.0);
+            // The running transition is kept, 85 of 150ms
+            assert_relative_eq!(width.update(10.0, Transition::NONE, FRAME), 85.0 / 15.0);
+        }
+
+        #[test]
+        fn test_retarget_starts_from_current_value() {
+            let mut width = Animated::new(0.0);
+            assert_eq!(width.update(10.0, HOVER, Duration::from_millis(75)), 5.0);
+
+            // Hover ends halfway, so it goes back from 5
+            assert_eq!(width.update(0.0, HOVER, Duration::ZERO), 5.0);
+            assert_eq!(width.update(0.0, HOVER, Duration::from_millis(75)), 2.5);
+            assert_eq!(width.target(), &0.0);
+
+            width.set_value(3.0);
+            assert!(!width.is_animating());
+            assert_eq!(width.value(), 3.0);
+        }
+
+        #[test]
+        fn test_instant_transition() {
+            let mut color = Animated::new(Color::BLACK);
+            assert_eq!(
+                color.update(Color::RED, Transition::NONE, Duration::ZERO),
+                Color::RED
+            );
+        }
+
+        #[test]
+        fn test_state_style_animate() {
+            let style = StateStyle::new(Padding::all(0.0))
+                .with_hovered(Padding::all(10.0))
+                .with_disabled(Padding::all(20.0))
+                .with_transition(HOVER);
+            let mut animation = Animated::new(style.normal);
+            let hovered = WidgetState::normal().with_hovered(true);
+            let half = Duration::from_millis(75);
+
+            assert_eq!(
+                style.animate(hovered, &mut animation, half),
+                Padding::all(5.0)
+            );
+            assert_eq!(
+                style.animate(hovered, &mut animation, half),
+                Padding::all(10.0)
+            );
+
+            let disabled = hovered.with_disabled(true);
+            assert_eq!(
+                style.animate(disabled, &mut animation, half),
+                Padding::all(15.0)
+            );
+
+            // Without a transition, states switch instantly
+            let instant = style.with_transition(Transition::NONE);
+            assert_eq!(
+                instant.animate(WidgetState::normal(), &mut animation, Duration::ZERO),
+                Padding::all(0.0)
+            );
+        }
+    }
+}
@@ -1,18 +1,113 @@
 //! Application context and main event loop
 
+use std::path::PathBuf;
+
+use crate::theme::Theme;
+use crate::theme::file::{ThemeFileError, ThemeWatcher};
+
 /// Main application context for Ho GUI applications
 pub struct App {
+    theme: Theme,
+    theme_watcher: Option<ThemeWatcher>,
     // TODO: Add application state fields
 }
 
 impl App {
-    /// Create a new application instance
+    /// Create a new application instance with the default light theme
     pub fn new() -> Self {
         Self {
+            theme: Theme::default(),
+            theme_watcher: None,
             // TODO: Initialize app state
         }
     }
 
+    /// Return the application with `theme`
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Current theme
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Switch the theme. Widgets pick it up from the next frame.
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::app::App;
+    /// use ho_gui::theme::Theme;
+    ///
+    /// let mut app = App::new();
+    /// app.set_theme(Theme::dark());
+    ///
+    /// assert!(app.theme().is_dark());
+    /// ```
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Load the theme from a [theme file](crate::theme::file) and keep it in sync with the file
+    ///
+    /// # Notes
+    ///
+    /// - Edits are only applied by [`App::reload_theme_file`], which callers poll every frame
+    /// - The file keeps being watched after an error, so fixing it applies the theme
+    /// - Replaces any previously watched file
+    ///
+    /// # Errors
+    ///
+    /// * Returns the `ThemeFileError` of loading the file. The current theme is kept.
+    pub fn watch_theme_file(&mut self, path: impl Into<PathBuf>) -> Result<(), ThemeFileError> {
+        self.theme_watcher = Some(ThemeWatcher::new(path));
+        self.reload_theme_file().map(|_| ())
+    }
+
+    /// Apply changes to the watched theme file
+    ///
+    /// # Notes
+    ///
+    /// - [`App::run`] doesn't poll the file yet, so call this once per frame, e.g. before building
+    ///   the UI. Checking an unchanged file costs one `stat` call.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the theme was reloaded, `false` if the file didn't change or none is watched
+    ///
+    /// # Errors
+    ///
+    /// * Returns the `ThemeFileError` of reloading a changed file, once per change. The current
+    ///   theme is kept, so a half-edited file doesn't break the running application.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ho_gui::app::App;
+    ///
+    /// let mut app = App::new();
+    /// app.watch_theme_file("theme.txt").unwrap();
+    ///
+    /// // Edit theme.txt, then on the next frame
+    /// match app.reload_theme_file() {
+    ///     Ok(reloaded) => assert!(reloaded),
+    ///     Err(error) => eprintln!("{}", error),
+    /// }
+    /// ```
+    pub fn reload_theme_file(&mut self) -> Result<bool, ThemeFileError> {
+        let Some(watcher) = &mut self.theme_watcher else {
+            return Ok(false);
+        };
+
+        match watcher.poll() {
+            Some(theme) => {
+                self.theme = theme?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
     /// Run the main application event loop
     pub fn run(self) -> Result<(), Box<dyn std::error::Error>> {
         // TODO: Implement event loop
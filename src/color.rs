@@ -346,6 +346,147 @@ impl Color {
         )
     }
 
+    /// Get the color as 4 bytes in the channel order of `format`
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - Channel order of the output bytes
+    ///
+    /// # Returns
+    ///
+    /// Bytes of the color in the order they are laid out in a pixel buffer of `format`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::{Color, PixelFormat};
+    ///
+    /// let color = Color::rgba(0x11, 0x22, 0x33, 0x44);
+    ///
+    /// assert_eq!(color.to_bytes(PixelFormat::Rgba8), [0x11, 0x22, 0x33, 0x44]);
+    /// assert_eq!(color.to_bytes(PixelFormat::Bgra8), [0x33, 0x22, 0x11, 0x44]);
+    /// assert_eq!(color.to_bytes(PixelFormat::Argb8), [0x44, 0x11, 0x22, 0x33]);
+    /// ```
+    pub const fn to_bytes(&self, format: PixelFormat) -> [u8; 4] {
+        let (r, g, b, a) = self.to_rgba_u8();
+        format.order_bytes(r, g, b, a)
+    }
+
+    /// Create Color from 4 bytes in the channel order of `format`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::{Color, PixelFormat};
+    ///
+    /// let color = Color::from_bytes([0x33, 0x22, 0x11, 0x44], PixelFormat::Bgra8);
+    ///
+    /// assert_eq!(color, Color::rgba(0x11, 0x22, 0x33, 0x44));
+    /// ```
+    pub const fn from_bytes(bytes: [u8; 4], format: PixelFormat) -> Self {
+        let (r, g, b, a) = format.read_bytes(bytes);
+        Self::rgba(r, g, b, a)
+    }
+
+    /// Get the color packed into u32 with channels in the order of `format`, starting from the
+    /// most significant byte (e.g. `0xRR_GG_BB_AA` for `PixelFormat::Rgba8`)
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::{Color, PixelFormat};
+    ///
+    /// let color = Color::rgba(0x11, 0x22, 0x33, 0x44);
+    ///
+    /// assert_eq!(color.to_packed(PixelFormat::Rgba8), 0x11_22_33_44);
+    /// assert_eq!(color.to_packed(PixelFormat::Bgra8), 0x33_22_11_44);
+    /// assert_eq!(color.to_packed(PixelFormat::Argb8), 0x44_11_22_33);
+    /// ```
+    pub const fn to_packed(&self, format: PixelFormat) -> u32 {
+        u32::from_be_bytes(self.to_bytes(format))
+    }
+
+    /// Create Color from u32 with channels in the order of `format`, starting from the most
+    /// significant byte
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::{Color, PixelFormat};
+    ///
+    /// let color = Color::from_packed(0x44_11_22_33, PixelFormat::Argb8);
+    ///
+    /// assert_eq!(color, Color::rgba(0x11, 0x22, 0x33, 0x44));
+    /// ```
+    pub const fn from_packed(packed: u32, format: PixelFormat) -> Self {
+        Self::from_bytes(packed.to_be_bytes(), format)
+    }
+
+    /// Get the color as RGB565 (5 bits red, 6 bits green, 5 bits blue). Alpha is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    ///
+    /// assert_eq!(Color::WHITE.to_rgb565(), 0xFFFF);
+    /// assert_eq!(Color::RED.to_rgb565(), 0b11111_000000_00000);
+    /// assert_eq!(Color::GREEN.to_rgb565(), 0b00000_111111_00000);
+    /// ```
+    pub const fn to_rgb565(&self) -> u16 {
+        let r = Self::round_to_u8(self.r * 31.0) as u16;
+        let g = Self::round_to_u8(self.g * 63.0) as u16;
+        let b = Self::round_to_u8(self.b * 31.0) as u16;
+
+        (r << 11) | (g << 5) | b
+    }
+
+    /// Create Color from RGB565 (5 bits red, 6 bits green, 5 bits blue). Alpha will be set to 1.0.
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    ///
+    /// assert_eq!(Color::from_rgb565(0xFFFF), Color::WHITE);
+    /// assert_eq!(Color::from_rgb565(0b00000_000000_11111), Color::BLUE);
+    /// ```
+    pub const fn from_rgb565(packed: u16) -> Self {
+        let r = ((packed >> 11) & 0x1F) as f32 / 31.0;
+        let g = ((packed >> 5) & 0x3F) as f32 / 63.0;
+        let b = (packed & 0x1F) as f32 / 31.0;
+
+        Self::rgb(r, g, b)
+    }
+
+    /// Get RGBA components as IEEE 754 half-precision float bits, e.g. for `Rgba16Float`
+    /// textures
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    ///
+    /// // 1.0 is 0x3C00 and 0.5 is 0x3800 in half-precision float
+    /// assert_eq!(Color::new(1.0, 0.5, 0.0, 1.0).to_rgba_f16(), [0x3C00, 0x3800, 0x0000, 0x3C00]);
+    /// ```
+    pub fn to_rgba_f16(&self) -> [u16; 4] {
+        [self.r, self.g, self.b, self.a].map(f32_to_f16_bits)
+    }
+
+    /// Create Color from RGBA components given as IEEE 754 half-precision float bits
+    ///
+    /// # Note
+    ///
+    /// Values out of range `(0.0..=1.0)` (including NaN) are clamped as in [`Color::new`].
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    ///
+    /// assert_eq!(
+    ///     Color::from_rgba_f16([0x3C00, 0x3800, 0x0000, 0x3C00]),
+    ///     Color::new(1.0, 0.5, 0.0, 1.0)
+    /// );
+    /// ```
+    pub fn from_rgba_f16(bits: [u16; 4]) -> Self {
+        let [r, g, b, a] = bits.map(f16_bits_to_f32);
+        Self::new(r, g, b, a)
+    }
+
     /// Manual rounding implementation for const fn compatibility.
     const fn round_to_u8(x: f32) -> u8 {
         if x <= 0.0 {
@@ -357,90 +498,966 @@ impl Color {
         }
     }
 
-    /// Create a new Color with the same RGB values but a different alpha value.
+    /// Create a new Color with the same RGB values but a different alpha value.
+    ///
+    /// # Note
+    ///
+    /// The alpha value will be clamped to the range `(0.0..=1.0)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - New alpha (transparency) value
+    ///
+    /// # Returns
+    ///
+    /// New Color object with the same RGB but new alpha value
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    ///
+    /// let opaque_red = Color::RED;
+    /// let semi_transparent_red = opaque_red.with_alpha(0.5);
+    ///
+    /// assert_eq!(semi_transparent_red.r, 1.0);
+    /// assert_eq!(semi_transparent_red.g, 0.0);
+    /// assert_eq!(semi_transparent_red.b, 0.0);
+    /// assert_eq!(semi_transparent_red.a, 0.5);
+    /// ```
+    pub const fn with_alpha(&self, a: f32) -> Self {
+        Self::new(self.r, self.g, self.b, Self::clamp01(a))
+    }
+
+    /// Linearly interpolate between this color and `other` component-wise.
+    ///
+    /// # Note
+    ///
+    /// `t` will be clamped to the range `(0.0..=1.0)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Color at `t == 1.0`
+    /// * `t` - Interpolation factor
+    ///
+    /// # Returns
+    ///
+    /// `self` at `t == 0.0`, `other` at `t == 1.0`, and the mix of both in between
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    ///
+    /// let gray = Color::BLACK.lerp(Color::WHITE, 0.5);
+    ///
+    /// assert_eq!(gray, Color::rgb(0.5, 0.5, 0.5));
+    /// ```
+    pub const fn lerp(&self, other: Self, t: f32) -> Self {
+        let t = Self::clamp01(t);
+
+        Self::new(
+            self.r + (other.r - self.r) * t,
+            self.g + (other.g - self.g) * t,
+            self.b + (other.b - self.b) * t,
+            self.a + (other.a - self.a) * t,
+        )
+    }
+
+    /// Relative luminance of the color as defined by WCAG 2.x.
+    ///
+    /// # Note
+    ///
+    /// RGB components are treated as sRGB encoded values, and alpha is ignored.
+    ///
+    /// # Returns
+    ///
+    /// Luminance in range `(0.0..=1.0)`, where black is 0.0 and white is 1.0
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    ///
+    /// assert_eq!(Color::BLACK.relative_luminance(), 0.0);
+    /// assert_eq!(Color::WHITE.relative_luminance(), 1.0);
+    /// ```
+    pub fn relative_luminance(&self) -> f32 {
+        0.2126 * Self::srgb_to_linear(self.r)
+            + 0.7152 * Self::srgb_to_linear(self.g)
+            + 0.0722 * Self::srgb_to_linear(self.b)
+    }
+
+    /// Check if this color, drawn on `background`, meets the given WCAG contrast level.
+    ///
+    /// # Note
+    ///
+    /// Translucent colors are composited over `background` before measuring contrast.
+    ///
+    /// # Arguments
+    ///
+    /// * `background` - Color the foreground is drawn on
+    /// * `level` - WCAG conformance level to check
+    ///
+    /// # Returns
+    ///
+    /// `true` if the contrast ratio is at least `level.min_ratio()`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::{Color, ContrastLevel};
+    ///
+    /// assert!(Color::BLACK.meets_contrast(Color::WHITE, ContrastLevel::Aaa));
+    /// assert!(!Color::from_hex(0x77_77_77).meets_contrast(Color::WHITE, ContrastLevel::Aa));
+    /// ```
+    pub fn meets_contrast(&self, background: Self, level: ContrastLevel) -> bool {
+        contrast_ratio(self.composite_over(background), background) >= level.min_ratio()
+    }
+
+    /// Adjust this color towards black or white until it meets the given WCAG contrast level
+    /// on `background`, changing it as little as possible.
+    ///
+    /// # Notes
+    ///
+    /// - Returns the color unchanged if it already meets the level.
+    /// - Alpha is preserved, and translucent colors are composited over `background` before
+    ///   measuring contrast.
+    /// - If the level can't be reached (e.g. a translucent color), returns the reachable color
+    ///   with the highest contrast.
+    ///
+    /// # Arguments
+    ///
+    /// * `background` - Color the foreground is drawn on
+    /// * `level` - WCAG conformance level to meet
+    ///
+    /// # Returns
+    ///
+    /// Adjusted color which meets `level` on `background` whenever possible
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::{Color, ContrastLevel};
+    ///
+    /// let light_gray = Color::from_hex(0xAA_AA_AA);
+    /// let readable = light_gray.adjust_for_contrast(Color::WHITE, ContrastLevel::Aa);
+    ///
+    /// assert!(readable.meets_contrast(Color::WHITE, ContrastLevel::Aa));
+    /// ```
+    pub fn adjust_for_contrast(&self, background: Self, level: ContrastLevel) -> Self {
+        if self.meets_contrast(background, level) {
+            return *self;
+        }
+
+        // Smallest step towards the extreme which meets the level, if reachable
+        let search = |extreme: Self| -> Option<(f32, Self)> {
+            let extreme = extreme.with_alpha(self.a);
+            if !extreme.meets_contrast(background, level) {
+                return None;
+            }
+
+            let (mut low, mut high) = (0.0f32, 1.0f32);
+            for _ in 0..CONTRAST_SEARCH_STEPS {
+                let mid = (low + high) / 2.0;
+                if self.lerp(extreme, mid).meets_contrast(background, level) {
+                    high = mid;
+                } else {
+                    low = mid;
+                }
+            }
+
+            Some((high, self.lerp(extreme, high)))
+        };
+
+        match (search(Self::BLACK), search(Self::WHITE)) {
+            (Some(dark), Some(light)) => {
+                if dark.0 <= light.0 {
+                    dark.1
+                } else {
+                    light.1
+                }
+            }
+            (Some(dark), None) => dark.1,
+            (None, Some(light)) => light.1,
+            (None, None) => {
+                let dark = Self::BLACK.with_alpha(self.a);
+                let light = Self::WHITE.with_alpha(self.a);
+
+                if contrast_ratio(dark.composite_over(background), background)
+                    >= contrast_ratio(light.composite_over(background), background)
+                {
+                    dark
+                } else {
+                    light
+                }
+            }
+        }
+    }
+
+    /// Simulate how this color is perceived with the given color vision.
+    ///
+    /// # Note
+    ///
+    /// The simulation matrix is applied in linear RGB, and alpha is preserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `vision` - Color vision to simulate
+    ///
+    /// # Returns
+    ///
+    /// Simulated color
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::{Color, ColorVision};
+    ///
+    /// let gray = Color::RED.simulate_vision(ColorVision::Grayscale);
+    ///
+    /// assert_eq!(gray.r, gray.g);
+    /// assert_eq!(gray.g, gray.b);
+    /// assert_eq!(Color::RED.simulate_vision(ColorVision::Normal), Color::RED);
+    /// ```
+    pub fn simulate_vision(&self, vision: ColorVision) -> Self {
+        if vision == ColorVision::Normal {
+            return *self;
+        }
+
+        let m = vision.matrix();
+        let (r, g, b) = (
+            Self::srgb_to_linear(self.r),
+            Self::srgb_to_linear(self.g),
+            Self::srgb_to_linear(self.b),
+        );
+        let apply = |row: [f32; 3]| -> f32 {
+            Self::linear_to_srgb((row[0] * r + row[1] * g + row[2] * b).clamp(0.0, 1.0))
+        };
+
+        Self::new(apply(m[0]), apply(m[1]), apply(m[2]), self.a)
+    }
+
+    /// Convert the color into the OKLCH color space.
+    ///
+    /// # Returns
+    ///
+    /// `Oklch` with lightness in `(0.0..=1.0)`, chroma `>= 0.0` and hue in degrees
+    /// `(0.0..360.0)`. Alpha is carried over unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    ///
+    /// let white = Color::WHITE.to_oklch();
+    ///
+    /// assert!((white.l - 1.0).abs() < 1e-4);
+    /// assert!(white.c < 1e-4);
+    /// ```
+    pub fn to_oklch(&self) -> Oklch {
+        let (l, a, b) = Self::linear_to_oklab(
+            Self::srgb_to_linear(self.r),
+            Self::srgb_to_linear(self.g),
+            Self::srgb_to_linear(self.b),
+        );
+
+        Oklch {
+            l,
+            c: (a * a + b * b).sqrt(),
+            h: b.atan2(a).to_degrees().rem_euclid(360.0),
+            alpha: self.a,
+        }
+    }
+
+    /// Create Color from an OKLCH color.
+    ///
+    /// # Note
+    ///
+    /// Colors outside of the sRGB gamut are mapped into it by reducing chroma while keeping
+    /// lightness and hue, so the result keeps the perceived lightness of the input. NaN or
+    /// infinite hue is treated as 0.0, like the hue of achromatic colors.
+    ///
+    /// # Arguments
+    ///
+    /// * `oklch` - Color in OKLCH color space
+    ///
+    /// # Returns
+    ///
+    /// Color object closest to the input within sRGB gamut
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::{Color, Oklch};
+    ///
+    /// let red = Color::from_oklch(Color::RED.to_oklch());
+    /// assert_eq!(red.to_rgba_u8(), (0xFF, 0x00, 0x00, 0xFF));
+    ///
+    /// // Out of gamut chroma is reduced
+    /// let vivid = Color::from_oklch(Oklch::new(0.7, 1.0, 150.0, 1.0));
+    /// assert!(vivid.is_valid());
+    /// ```
+    pub fn from_oklch(oklch: Oklch) -> Self {
+        let l = Self::clamp01(oklch.l);
+        // Undefined hue is 0.0, as for achromatic colors
+        let h = if oklch.h.is_finite() {
+            oklch.h.to_radians()
+        } else {
+            0.0
+        };
+        let to_linear = |c: f32| Self::oklab_to_linear(l, c * h.cos(), c * h.sin());
+        let in_gamut = |(r, g, b): (f32, f32, f32)| {
+            let range = -OKLCH_GAMUT_EPSILON..=1.0 + OKLCH_GAMUT_EPSILON;
+            range.contains(&r) && range.contains(&g) && range.contains(&b)
+        };
+
+        let chroma = if oklch.c.is_nan() {
+            0.0
+        } else {
+            oklch.c.max(0.0)
+        };
+        let mut rgb = to_linear(chroma);
+
+        if !in_gamut(rgb) {
+            let (mut low, mut high) = (0.0f32, chroma);
+            for _ in 0..OKLCH_GAMUT_SEARCH_STEPS {
+                let mid = (low + high) / 2.0;
+                if in_gamut(to_linear(mid)) {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            rgb = to_linear(low);
+        }
+
+        let encode = |x: f32| Self::linear_to_srgb(x.clamp(0.0, 1.0));
+        Self::new(encode(rgb.0), encode(rgb.1), encode(rgb.2), oklch.alpha)
+    }
+
+    /// Check if all color components are within valid range.
+    ///
+    /// # Note
+    ///
+    /// Since all `Color` constructors automatically clamp values to the valid range,
+    /// this method will always return `true` for properly constructed `Color` instances.
+    /// This method is primarily useful for validation after direct field manipulation
+    /// or when working with colors from external sources.
+    ///
+    /// # Returns
+    ///
+    /// `true` if all RGBA components are in the range `(0.0..=1.0)`, `false` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    ///
+    /// let valid_color = Color::new(0.5, 0.5, 0.5, 1.0);
+    /// assert!(valid_color.is_valid());
+    ///
+    /// // This would still be valid due to constructor clamping
+    /// let clamped_color = Color::new(2.0, -1.0, 0.5, 0.5);
+    /// assert!(clamped_color.is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        let check_validity = |x: f32| -> bool { !x.is_nan() && (0.0..=1.0).contains(&x) };
+
+        check_validity(self.r)
+            && check_validity(self.g)
+            && check_validity(self.b)
+            && check_validity(self.a)
+    }
+
+    /// Composite this color over the given backdrop color (Porter-Duff `source-over`).
+    ///
+    /// # Note
+    ///
+    /// It behaves just as same as `self.blend(backdrop, BlendMode::SourceOver)`
+    ///
+    /// # Arguments
+    ///
+    /// * `backdrop` - Destination color which is placed below this color
+    ///
+    /// # Returns
+    ///
+    /// Resulting color of drawing this color on top of `backdrop`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    ///
+    /// let half_red = Color::RED.with_alpha(0.5);
+    /// let result = half_red.composite_over(Color::BLUE);
+    ///
+    /// assert_eq!(result, Color::new(0.5, 0.0, 0.5, 1.0));
+    /// ```
+    pub fn composite_over(&self, backdrop: Self) -> Self {
+        self.blend(backdrop, BlendMode::SourceOver)
+    }
+
+    /// Blend this color (source) with the given backdrop color (destination) using `mode`.
+    ///
+    /// # Notes
+    ///
+    /// - Colors are treated as non-premultiplied (straight alpha) values.
+    /// - Porter-Duff operators composite source and destination by coverage.
+    /// - Separable blend modes (multiply, screen ...) mix the color channels first, and then
+    ///   composite the mixed color with `source-over`, as specified by W3C Compositing Level 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `backdrop` - Destination color which is placed below this color
+    /// * `mode` - Blend mode to apply
+    ///
+    /// # Returns
+    ///
+    /// Resulting color. Fully transparent results are returned as `Color::TRANSPARENT`.
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::{BlendMode, Color};
+    ///
+    /// let gray = Color::rgb(0.5, 0.5, 0.5);
+    ///
+    /// assert_eq!(gray.blend(Color::WHITE, BlendMode::Multiply), gray);
+    /// assert_eq!(Color::RED.blend(Color::BLUE, BlendMode::DestinationOver), Color::BLUE);
+    /// assert_eq!(Color::RED.blend(Color::BLUE, BlendMode::Clear), Color::TRANSPARENT);
+    /// ```
+    pub fn blend(&self, backdrop: Self, mode: BlendMode) -> Self {
+        let (sa, da) = (self.a, backdrop.a);
+
+        let (fa, fb) = match mode.porter_duff_factors(sa, da) {
+            Some(factors) => factors,
+            None => {
+                // Separable blend mode: mix channels, then composite with source-over
+                let out_a = sa + da * (1.0 - sa);
+                if out_a <= 0.0 {
+                    return Self::TRANSPARENT;
+                }
+
+                let mix = |cs: f32, cb: f32| -> f32 {
+                    let mixed = (1.0 - da) * cs + da * mode.blend_channel(cb, cs);
+                    (sa * mixed + da * cb * (1.0 - sa)) / out_a
+                };
+
+                return Self::new(
+                    mix(self.r, backdrop.r),
+                    mix(self.g, backdrop.g),
+                    mix(self.b, backdrop.b),
+                    out_a,
+                );
+            }
+        };
+
+        let out_a = (fa * sa + fb * da).min(1.0);
+        if out_a <= 0.0 {
+            return Self::TRANSPARENT;
+        }
+
+        let mix = |cs: f32, cb: f32| -> f32 { (fa * sa * cs + fb * da * cb).min(1.0) / out_a };
+
+        Self::new(
+            mix(self.r, backdrop.r),
+            mix(self.g, backdrop.g),
+            mix(self.b, backdrop.b),
+            out_a,
+        )
+    }
+
+    fn srgb_to_linear(x: f32) -> f32 {
+        if x <= 0.04045 {
+            x / 12.92
+        } else {
+            ((x + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    fn linear_to_srgb(x: f32) -> f32 {
+        if x <= 0.0031308 {
+            x * 12.92
+        } else {
+            1.055 * x.powf(1.0 / 2.4) - 0.055
+        }
+    }
+
+    #[allow(clippy::excessive_precision)]
+    fn linear_to_oklab(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        (
+            0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        )
+    }
+
+    #[allow(clippy::excessive_precision)]
+    fn oklab_to_linear(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+        let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+        (
+            4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+            -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+            -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+        )
+    }
+
+    const fn extract_byte(hex: u32, idx_to_extract: u32) -> u8 {
+        // u32 has only 4 bytes
+        if idx_to_extract > 3 {
+            return 0x00;
+        }
+
+        ((hex >> (idx_to_extract * u8::BITS)) & 0xFF) as u8
+    }
+
+    const fn clamp01(x: f32) -> f32 {
+        if x.is_nan() || x < 0.0 {
+            0.0
+        } else if x > 1.0 {
+            1.0
+        } else {
+            x
+        }
+    }
+
+    const fn scale01(x: u8) -> f32 {
+        (x as f32) * (1.0 / u8::MAX as f32)
+    }
+}
+
+/// Channel order of a 4-byte-per-pixel buffer, listed from the first byte in memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PixelFormat {
+    /// Red, green, blue, alpha
+    #[default]
+    Rgba8,
+
+    /// Blue, green, red, alpha (common for screen capture and Windows surfaces)
+    Bgra8,
+
+    /// Alpha, red, green, blue
+    Argb8,
+}
+
+impl PixelFormat {
+    /// Byte index of red, green, blue and alpha within a pixel
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::PixelFormat;
+    ///
+    /// assert_eq!(PixelFormat::Bgra8.channel_indices(), [2, 1, 0, 3]);
+    /// ```
+    pub const fn channel_indices(&self) -> [usize; 4] {
+        match self {
+            Self::Rgba8 => [0, 1, 2, 3],
+            Self::Bgra8 => [2, 1, 0, 3],
+            Self::Argb8 => [1, 2, 3, 0],
+        }
+    }
+
+    const fn order_bytes(&self, r: u8, g: u8, b: u8, a: u8) -> [u8; 4] {
+        let [ri, gi, bi, ai] = self.channel_indices();
+        let mut bytes = [0u8; 4];
+        bytes[ri] = r;
+        bytes[gi] = g;
+        bytes[bi] = b;
+        bytes[ai] = a;
+        bytes
+    }
+
+    const fn read_bytes(&self, bytes: [u8; 4]) -> (u8, u8, u8, u8) {
+        let [ri, gi, bi, ai] = self.channel_indices();
+        (bytes[ri], bytes[gi], bytes[bi], bytes[ai])
+    }
+}
+
+/// Convert f32 into IEEE 754 half-precision float bits, rounding to nearest even
+fn f32_to_f16_bits(x: f32) -> u16 {
+    let bits = x.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xFF) as i32;
+    let mantissa = bits & 0x7F_FFFF;
+
+    // NaN and infinity
+    if exp == 0xFF {
+        let nan_bit = if mantissa != 0 { 0x200 } else { 0 };
+        return sign | 0x7C00 | nan_bit;
+    }
+
+    let half_exp = exp - 127 + 15;
+
+    // Overflow into infinity
+    if half_exp >= 0x1F {
+        return sign | 0x7C00;
+    }
+
+    // Normal half
+    if half_exp > 0 {
+        let half = ((half_exp as u32) << 10) | (mantissa >> 13);
+        let round = mantissa & 0x1FFF;
+        let half = if round > 0x1000 || (round == 0x1000 && (half & 1) == 1) {
+            half + 1 // Carry into the exponent is the correct rounding
+        } else {
+            half
+        };
+        return sign | half as u16;
+    }
+
+    // Subnormal half, or underflow into zero
+    if half_exp < -10 {
+        return sign;
+    }
+
+    let mantissa = mantissa | 0x80_0000;
+    let shift = (14 - half_exp) as u32;
+    let half = mantissa >> shift;
+    let round_bit = 1 << (shift - 1);
+    let round = mantissa & ((round_bit << 1) - 1);
+    let half = if round > round_bit || (round == round_bit && (half & 1) == 1) {
+        half + 1
+    } else {
+        half
+    };
+
+    sign | half as u16
+}
+
+/// Convert IEEE 754 half-precision float bits into f32
+fn f16_bits_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exp = ((half >> 10) & 0x1F) as u32;
+    let mantissa = (half & 0x3FF) as u32;
+
+    let bits = match (exp, mantissa) {
+        (0, 0) => sign,
+        (0, _) => {
+            // Subnormal half is a normal f32: normalize the mantissa
+            let shift = mantissa.leading_zeros() - 21;
+            let mantissa = (mantissa << shift) & 0x3FF;
+            sign | ((127 - 15 + 1 - shift) << 23) | (mantissa << 13)
+        }
+        (0x1F, _) => sign | 0x7F80_0000 | (mantissa << 13),
+        _ => sign | ((exp + 127 - 15) << 23) | (mantissa << 13),
+    };
+
+    f32::from_bits(bits)
+}
+
+/// Number of bisection steps used by [`Color::adjust_for_contrast`]
+const CONTRAST_SEARCH_STEPS: usize = 24;
+
+/// WCAG contrast ratio between two colors.
+///
+/// # Note
+///
+/// Alpha is ignored. Composite translucent colors over their background first
+/// (e.g. with [`Color::composite_over`]).
+///
+/// # Arguments
+///
+/// * `a` - First color
+/// * `b` - Second color
+///
+/// # Returns
+///
+/// Contrast ratio in range `(1.0..=21.0)`. The order of `a` and `b` doesn't matter.
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::{contrast_ratio, Color};
+///
+/// assert!((contrast_ratio(Color::BLACK, Color::WHITE) - 21.0).abs() < 1e-4);
+/// assert_eq!(contrast_ratio(Color::RED, Color::RED), 1.0);
+/// ```
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (la, lb) = (a.relative_luminance(), b.relative_luminance());
+    let (lighter, darker) = if la >= lb { (la, lb) } else { (lb, la) };
+
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// WCAG 2.x contrast conformance level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContrastLevel {
+    /// Level AA for normal text (4.5:1)
+    Aa,
+
+    /// Level AA for large text, i.e. 18pt or 14pt bold (3:1)
+    AaLarge,
+
+    /// Level AAA for normal text (7:1)
+    Aaa,
+
+    /// Level AAA for large text, i.e. 18pt or 14pt bold (4.5:1)
+    AaaLarge,
+}
+
+impl ContrastLevel {
+    /// Minimum contrast ratio required by the level
     ///
-    /// # Note
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::ContrastLevel;
     ///
-    /// The alpha value will be clamped to the range `(0.0..=1.0)`.
+    /// assert_eq!(ContrastLevel::Aa.min_ratio(), 4.5);
+    /// assert_eq!(ContrastLevel::Aaa.min_ratio(), 7.0);
+    /// ```
+    pub const fn min_ratio(&self) -> f32 {
+        match self {
+            Self::Aa => 4.5,
+            Self::AaLarge => 3.0,
+            Self::Aaa => 7.0,
+            Self::AaaLarge => 4.5,
+        }
+    }
+}
+
+/// Tolerance of linear RGB components when checking if an OKLCH color is in sRGB gamut
+const OKLCH_GAMUT_EPSILON: f32 = 1e-4;
+
+/// Number of bisection steps used to reduce chroma into sRGB gamut
+const OKLCH_GAMUT_SEARCH_STEPS: usize = 24;
+
+/// Color in OKLCH, the polar form of the perceptually uniform OKLab color space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    /// Perceived lightness in range `(0.0..=1.0)`
+    pub l: f32,
+
+    /// Chroma (colorfulness), `0.0` for grays. sRGB colors stay below about `0.33`.
+    pub c: f32,
+
+    /// Hue angle in degrees
+    pub h: f32,
+
+    /// Alpha (transparency) in range `(0.0..=1.0)`
+    pub alpha: f32,
+}
+
+impl Oklch {
+    /// Create Oklch with given lightness, chroma, hue and alpha
+    pub const fn new(l: f32, c: f32, h: f32, alpha: f32) -> Self {
+        Self { l, c, h, alpha }
+    }
+
+    /// Create a new Oklch with the hue rotated by `degrees`, wrapped into `(0.0..360.0)`
     ///
-    /// # Arguments
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Oklch;
     ///
-    /// * `a` - New alpha (transparency) value
+    /// assert_eq!(Oklch::new(0.5, 0.1, 300.0, 1.0).rotate_hue(90.0).h, 30.0);
+    /// ```
+    pub fn rotate_hue(&self, degrees: f32) -> Self {
+        Self {
+            h: (self.h + degrees).rem_euclid(360.0),
+            ..*self
+        }
+    }
+}
+
+/// Color vision used for color-vision-deficiency simulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorVision {
+    /// Typical trichromatic vision (no simulation)
+    #[default]
+    Normal,
+
+    /// Missing long-wavelength (red) cones
+    Protanopia,
+
+    /// Missing medium-wavelength (green) cones
+    Deuteranopia,
+
+    /// Missing short-wavelength (blue) cones
+    Tritanopia,
+
+    /// No color perception; only luminance remains
+    Grayscale,
+}
+
+impl ColorVision {
+    /// Linear RGB simulation matrix of the color vision, in row-major order.
     ///
-    /// # Returns
+    /// # Note
     ///
-    /// New Color object with the same RGB but new alpha value
+    /// Dichromacy matrices are from Machado, Oliveira and Fernandes (2009) with severity 1.0.
+    /// Grayscale maps every channel to the WCAG relative luminance.
     ///
     /// # Examples
     /// ```
-    /// use ho_gui::color::Color;
-    ///
-    /// let opaque_red = Color::RED;
-    /// let semi_transparent_red = opaque_red.with_alpha(0.5);
+    /// use ho_gui::color::ColorVision;
     ///
-    /// assert_eq!(semi_transparent_red.r, 1.0);
-    /// assert_eq!(semi_transparent_red.g, 0.0);
-    /// assert_eq!(semi_transparent_red.b, 0.0);
-    /// assert_eq!(semi_transparent_red.a, 0.5);
+    /// assert_eq!(
+    ///     ColorVision::Normal.matrix(),
+    ///     [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+    /// );
     /// ```
-    pub const fn with_alpha(&self, a: f32) -> Self {
-        Self::new(self.r, self.g, self.b, Self::clamp01(a))
+    pub const fn matrix(&self) -> [[f32; 3]; 3] {
+        match self {
+            Self::Normal => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            Self::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Self::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Self::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+            Self::Grayscale => [
+                [0.2126, 0.7152, 0.0722],
+                [0.2126, 0.7152, 0.0722],
+                [0.2126, 0.7152, 0.0722],
+            ],
+        }
     }
+}
 
-    /// Check if all color components are within valid range.
-    ///
-    /// # Note
-    ///
-    /// Since all `Color` constructors automatically clamp values to the valid range,
-    /// this method will always return `true` for properly constructed `Color` instances.
-    /// This method is primarily useful for validation after direct field manipulation
-    /// or when working with colors from external sources.
+/// Blend mode describing how a source color is combined with a destination (backdrop) color.
+///
+/// # Notes
+///
+/// - Variants from `Clear` to `Plus` are Porter-Duff compositing operators.
+/// - Variants from `Multiply` to `Difference` are separable blend modes, which are composited
+///   with `source-over` after mixing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BlendMode {
+    /// Neither source nor destination is kept (fully transparent)
+    Clear,
+
+    /// Only source is kept
+    Source,
+
+    /// Only destination is kept
+    Destination,
+
+    /// Source is placed over destination
+    #[default]
+    SourceOver,
+
+    /// Destination is placed over source
+    DestinationOver,
+
+    /// Source is kept only where destination exists
+    SourceIn,
+
+    /// Destination is kept only where source exists
+    DestinationIn,
+
+    /// Source is kept only where destination doesn't exist
+    SourceOut,
+
+    /// Destination is kept only where source doesn't exist
+    DestinationOut,
+
+    /// Source inside destination is placed over destination
+    SourceAtop,
+
+    /// Destination inside source is placed over source
+    DestinationAtop,
+
+    /// Source and destination are kept only where they don't overlap
+    Xor,
+
+    /// Source and destination are added together (clamped to 1.0)
+    Plus,
+
+    /// Source and destination are multiplied; always darkens
+    Multiply,
+
+    /// Inverse of source and destination are multiplied and inverted; always lightens
+    Screen,
+
+    /// Multiply or screen, depending on destination
+    Overlay,
+
+    /// Darker of source and destination
+    Darken,
+
+    /// Lighter of source and destination
+    Lighten,
+
+    /// Absolute difference of source and destination
+    Difference,
+}
+
+impl BlendMode {
+    /// Check if the blend mode is a Porter-Duff compositing operator
     ///
     /// # Returns
     ///
-    /// `true` if all RGBA components are in the range `(0.0..=1.0)`, `false` otherwise.
+    /// `true` for Porter-Duff operators, `false` for separable blend modes
     ///
     /// # Examples
     /// ```
-    /// use ho_gui::color::Color;
-    ///
-    /// let valid_color = Color::new(0.5, 0.5, 0.5, 1.0);
-    /// assert!(valid_color.is_valid());
+    /// use ho_gui::color::BlendMode;
     ///
-    /// // This would still be valid due to constructor clamping
-    /// let clamped_color = Color::new(2.0, -1.0, 0.5, 0.5);
-    /// assert!(clamped_color.is_valid());
+    /// assert!(BlendMode::SourceOver.is_porter_duff());
+    /// assert!(!BlendMode::Multiply.is_porter_duff());
     /// ```
-    pub fn is_valid(&self) -> bool {
-        let check_validity = |x: f32| -> bool { !x.is_nan() && (0.0..=1.0).contains(&x) };
-
-        check_validity(self.r)
-            && check_validity(self.g)
-            && check_validity(self.b)
-            && check_validity(self.a)
+    pub const fn is_porter_duff(&self) -> bool {
+        !matches!(
+            self,
+            Self::Multiply
+                | Self::Screen
+                | Self::Overlay
+                | Self::Darken
+                | Self::Lighten
+                | Self::Difference
+        )
     }
 
-    const fn extract_byte(hex: u32, idx_to_extract: u32) -> u8 {
-        // u32 has only 4 bytes
-        if idx_to_extract > 3 {
-            return 0x00;
-        }
+    /// Porter-Duff factors `(Fa, Fb)` for source alpha `sa` and destination alpha `da`.
+    /// Returns `None` for separable blend modes.
+    fn porter_duff_factors(&self, sa: f32, da: f32) -> Option<(f32, f32)> {
+        let factors = match self {
+            Self::Clear => (0.0, 0.0),
+            Self::Source => (1.0, 0.0),
+            Self::Destination => (0.0, 1.0),
+            Self::SourceOver => (1.0, 1.0 - sa),
+            Self::DestinationOver => (1.0 - da, 1.0),
+            Self::SourceIn => (da, 0.0),
+            Self::DestinationIn => (0.0, sa),
+            Self::SourceOut => (1.0 - da, 0.0),
+            Self::DestinationOut => (0.0, 1.0 - sa),
+            Self::SourceAtop => (da, 1.0 - sa),
+            Self::DestinationAtop => (1.0 - da, sa),
+            Self::Xor => (1.0 - da, 1.0 - sa),
+            Self::Plus => (1.0, 1.0),
+            _ => return None,
+        };
 
-        ((hex >> (idx_to_extract * u8::BITS)) & 0xFF) as u8
+        Some(factors)
     }
 
-    const fn clamp01(x: f32) -> f32 {
-        if x.is_nan() || x < 0.0 {
-            0.0
-        } else if x > 1.0 {
-            1.0
-        } else {
-            x
-        }
-    }
+    /// Separable blend function `B(cb, cs)` for a single channel.
+    /// Porter-Duff operators return the source channel unchanged.
+    fn blend_channel(&self, cb: f32, cs: f32) -> f32 {
+        let multiply = |cb: f32, cs: f32| cb * cs;
+        let screen = |cb: f32, cs: f32| cb + cs - cb * cs;
 
-    const fn scale01(x: u8) -> f32 {
-        (x as f32) * (1.0 / u8::MAX as f32)
+        match self {
+            Self::Multiply => multiply(cb, cs),
+            Self::Screen => screen(cb, cs),
+            Self::Overlay => {
+                // Overlay is hard-light with source and backdrop swapped
+                if cb <= 0.5 {
+                    multiply(cs, 2.0 * cb)
+                } else {
+                    screen(cs, 2.0 * cb - 1.0)
+                }
+            }
+            Self::Darken => cb.min(cs),
+            Self::Lighten => cb.max(cs),
+            Self::Difference => (cb - cs).abs(),
+            _ => cs,
+        }
     }
 }
 
@@ -484,6 +1501,7 @@ impl std::fmt::Display for ColorParseError {
 impl std::error::Error for ColorParseError {}
 
 #[cfg(test)]
+#[allow(clippy::clone_on_copy)]
 mod color_tests {
     use super::*;
     use approx::assert_relative_eq;
@@ -673,6 +1691,21 @@ mod color_tests {
         assert_eq!(color_new.a, 1.0);
     }
 
+    #[test]
+    fn test_lerp() {
+        let from = Color::new(0.0, 0.5, 1.0, 0.0);
+        let to = Color::new(1.0, 0.5, 0.0, 1.0);
+
+        assert_eq!(from.lerp(to, 0.0), from);
+        assert_eq!(from.lerp(to, 1.0), to);
+        assert_eq!(from.lerp(to, 0.25), Color::new(0.25, 0.5, 0.75, 0.25));
+
+        // t out of range is clamped
+        assert_eq!(from.lerp(to, -1.0), from);
+        assert_eq!(from.lerp(to, f32::NAN), from);
+        assert_eq!(from.lerp(to, 2.0), to);
+    }
+
     #[test]
     fn test_is_valid() {
         let color = Color::new(CLAMPED_VALID, 0.0, 1.0, CLAMPED_VALID);
@@ -738,6 +1771,407 @@ mod color_tests {
         assert_eq!(std::mem::align_of::<Color>(), 16);
     }
 
+    fn assert_color_eq(actual: Color, expected: Color) {
+        assert_relative_eq!(actual.r, expected.r, epsilon = TEST_EPSILON);
+        assert_relative_eq!(actual.g, expected.g, epsilon = TEST_EPSILON);
+        assert_relative_eq!(actual.b, expected.b, epsilon = TEST_EPSILON);
+        assert_relative_eq!(actual.a, expected.a, epsilon = TEST_EPSILON);
+    }
+
+    #[test]
+    fn test_composite_over() {
+        let opaque = Color::RED.composite_over(Color::BLUE);
+        let translucent = Color::RED.with_alpha(0.5).composite_over(Color::BLUE);
+        let over_transparent = Color::RED
+            .with_alpha(0.5)
+            .composite_over(Color::TRANSPARENT);
+        let both_transparent = Color::TRANSPARENT.composite_over(Color::TRANSPARENT);
+
+        assert_color_eq(opaque, Color::RED);
+        assert_color_eq(translucent, Color::new(0.5, 0.0, 0.5, 1.0));
+        assert_color_eq(over_transparent, Color::RED.with_alpha(0.5));
+        assert_color_eq(both_transparent, Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn test_porter_duff_operators() {
+        let src = Color::RED.with_alpha(0.5);
+        let dst = Color::BLUE.with_alpha(0.5);
+
+        let cases = [
+            (BlendMode::Clear, Color::TRANSPARENT),
+            (BlendMode::Source, src),
+            (BlendMode::Destination, dst),
+            (
+                BlendMode::SourceOver,
+                Color::new(2.0 / 3.0, 0.0, 1.0 / 3.0, 0.75),
+            ),
+            (
+                BlendMode::DestinationOver,
+                Color::new(1.0 / 3.0, 0.0, 2.0 / 3.0, 0.75),
+            ),
+            (BlendMode::SourceIn, Color::RED.with_alpha(0.25)),
+            (BlendMode::DestinationIn, Color::BLUE.with_alpha(0.25)),
+            (BlendMode::SourceOut, Color::RED.with_alpha(0.25)),
+            (BlendMode::DestinationOut, Color::BLUE.with_alpha(0.25)),
+            (BlendMode::SourceAtop, Color::new(0.5, 0.0, 0.5, 0.5)),
+            (BlendMode::DestinationAtop, Color::new(0.5, 0.0, 0.5, 0.5)),
+            (BlendMode::Xor, Color::new(0.5, 0.0, 0.5, 0.5)),
+            (BlendMode::Plus, Color::new(0.5, 0.0, 0.5, 1.0)),
+        ];
+
+        for (mode, expected) in cases {
+            assert!(mode.is_porter_duff(), "{:?} should be Porter-Duff", mode);
+            assert_color_eq(src.blend(dst, mode), expected);
+        }
+    }
+
+    #[test]
+    fn test_separable_blend_modes_opaque() {
+        let src = Color::rgb(0.25, 0.5, 0.75);
+        let dst = Color::rgb(0.5, 0.25, 1.0);
+
+        let cases = [
+            (BlendMode::Multiply, Color::rgb(0.125, 0.125, 0.75)),
+            (BlendMode::Screen, Color::rgb(0.625, 0.625, 1.0)),
+            (BlendMode::Overlay, Color::rgb(0.25, 0.25, 1.0)),
+            (BlendMode::Darken, Color::rgb(0.25, 0.25, 0.75)),
+            (BlendMode::Lighten, Color::rgb(0.5, 0.5, 1.0)),
+            (BlendMode::Difference, Color::rgb(0.25, 0.25, 0.25)),
+        ];
+
+        for (mode, expected) in cases {
+            assert!(!mode.is_porter_duff(), "{:?} should be separable", mode);
+            assert_color_eq(src.blend(dst, mode), expected);
+        }
+    }
+
+    #[test]
+    fn test_separable_blend_modes_translucent() {
+        // Separable modes over a transparent backdrop behave like source-over
+        let src = Color::rgb(0.25, 0.5, 0.75).with_alpha(0.5);
+
+        assert_color_eq(src.blend(Color::TRANSPARENT, BlendMode::Multiply), src);
+
+        // Translucent source mixes blended color with backdrop by source alpha
+        let result = src.blend(Color::WHITE, BlendMode::Multiply);
+        assert_color_eq(result, Color::rgb(0.625, 0.75, 0.875));
+    }
+
+    #[test]
+    fn test_relative_luminance() {
+        assert_relative_eq!(Color::BLACK.relative_luminance(), 0.0);
+        assert_relative_eq!(
+            Color::WHITE.relative_luminance(),
+            1.0,
+            epsilon = TEST_EPSILON
+        );
+        assert_relative_eq!(
+            Color::RED.relative_luminance(),
+            0.2126,
+            epsilon = TEST_EPSILON
+        );
+        assert_relative_eq!(
+            Color::GREEN.relative_luminance(),
+            0.7152,
+            epsilon = TEST_EPSILON
+        );
+        assert_relative_eq!(
+            Color::BLUE.relative_luminance(),
+            0.0722,
+            epsilon = TEST_EPSILON
+        );
+
+        // Values below the sRGB linear segment threshold are scaled linearly
+        let dark = Color::rgb(0.04, 0.04, 0.04);
+        assert_relative_eq!(
+            dark.relative_luminance(),
+            0.04 / 12.92,
+            epsilon = TEST_EPSILON
+        );
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert_relative_eq!(
+            contrast_ratio(Color::BLACK, Color::WHITE),
+            21.0,
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            contrast_ratio(Color::WHITE, Color::BLACK),
+            21.0,
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(contrast_ratio(Color::BLUE, Color::BLUE), 1.0);
+
+        // #767676 is the lightest gray passing AA on white (4.54:1)
+        let gray = Color::from_hex(0x76_76_76);
+        assert_relative_eq!(contrast_ratio(gray, Color::WHITE), 4.54, epsilon = 0.01);
+    }
+
+    #[test]
+    fn test_meets_contrast() {
+        let gray = Color::from_hex(0x76_76_76);
+
+        assert!(gray.meets_contrast(Color::WHITE, ContrastLevel::Aa));
+        assert!(gray.meets_contrast(Color::WHITE, ContrastLevel::AaLarge));
+        assert!(gray.meets_contrast(Color::WHITE, ContrastLevel::AaaLarge));
+        assert!(!gray.meets_contrast(Color::WHITE, ContrastLevel::Aaa));
+
+        // Translucent black is composited over white, which lowers contrast
+        assert!(
+            !Color::BLACK
+                .with_alpha(0.3)
+                .meets_contrast(Color::WHITE, ContrastLevel::Aa)
+        );
+    }
+
+    #[test]
+    fn test_adjust_for_contrast() {
+        let levels = [
+            ContrastLevel::Aa,
+            ContrastLevel::AaLarge,
+            ContrastLevel::Aaa,
+            ContrastLevel::AaaLarge,
+        ];
+        let backgrounds = [
+            Color::WHITE,
+            Color::BLACK,
+            Color::from_hex(0x1E_1E_2E),
+            Color::from_hex(0xF5_F0_E1),
+            Color::from_hex(0x22_55_AA),
+        ];
+        let foreground = Color::from_hex(0x88_99_AA);
+
+        for background in backgrounds {
+            for level in levels {
+                let adjusted = foreground.adjust_for_contrast(background, level);
+                assert!(
+                    adjusted.meets_contrast(background, level),
+                    "{:?} on {:?} should meet {:?}",
+                    adjusted,
+                    background,
+                    level
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_adjust_for_contrast_keeps_readable_color() {
+        assert_eq!(
+            Color::BLACK.adjust_for_contrast(Color::WHITE, ContrastLevel::Aaa),
+            Color::BLACK
+        );
+    }
+
+    #[test]
+    fn test_adjust_for_contrast_unreachable() {
+        // Mid gray can't reach 7:1 with either black or white
+        let background = Color::from_hex(0x80_80_80);
+        let adjusted = Color::RED.adjust_for_contrast(background, ContrastLevel::Aaa);
+
+        assert_eq!(adjusted, Color::BLACK);
+    }
+
+    #[test]
+    fn test_simulate_vision_normal_is_identity() {
+        let color = Color::new(0.2, 0.4, 0.6, 0.8);
+
+        assert_eq!(color.simulate_vision(ColorVision::Normal), color);
+    }
+
+    #[test]
+    fn test_simulate_vision_preserves_neutrals() {
+        // Every simulation matrix keeps white, black and grays unchanged
+        let visions = [
+            ColorVision::Protanopia,
+            ColorVision::Deuteranopia,
+            ColorVision::Tritanopia,
+            ColorVision::Grayscale,
+        ];
+
+        for vision in visions {
+            for color in [Color::WHITE, Color::BLACK, Color::rgb(0.5, 0.5, 0.5)] {
+                let simulated = color.simulate_vision(vision);
+
+                assert_relative_eq!(simulated.r, color.r, epsilon = 1e-3);
+                assert_relative_eq!(simulated.g, color.g, epsilon = 1e-3);
+                assert_relative_eq!(simulated.b, color.b, epsilon = 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_simulate_vision_grayscale() {
+        let color = Color::new(0.9, 0.3, 0.1, 0.5);
+        let gray = color.simulate_vision(ColorVision::Grayscale);
+
+        assert_relative_eq!(gray.r, gray.g);
+        assert_relative_eq!(gray.g, gray.b);
+        assert_relative_eq!(gray.a, 0.5);
+        assert_relative_eq!(
+            gray.relative_luminance(),
+            color.relative_luminance(),
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn test_simulate_vision_red_green_confusion() {
+        // Red and green are far apart for normal vision, but close for protanopia/deuteranopia
+        let red = Color::from_hex(0xCC_33_33);
+        let green = Color::from_hex(0x33_99_33);
+        let distance = |a: Color, b: Color| {
+            ((a.r - b.r).powi(2) + (a.g - b.g).powi(2) + (a.b - b.b).powi(2)).sqrt()
+        };
+
+        let normal = distance(red, green);
+        for vision in [ColorVision::Protanopia, ColorVision::Deuteranopia] {
+            let simulated = distance(red.simulate_vision(vision), green.simulate_vision(vision));
+            assert!(
+                simulated < normal / 2.0,
+                "{:?} should confuse red and green. normal: {}, simulated: {}",
+                vision,
+                normal,
+                simulated
+            );
+        }
+    }
+
+    #[test]
+    fn test_oklch_reference_values() {
+        // Reference values from the OKLab specification
+        let red = Color::RED.to_oklch();
+        assert_relative_eq!(red.l, 0.62796, epsilon = 1e-3);
+        assert_relative_eq!(red.c, 0.25768, epsilon = 1e-3);
+        assert_relative_eq!(red.h, 29.2339, epsilon = 1e-1);
+
+        let black = Color::BLACK.to_oklch();
+        assert_relative_eq!(black.l, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(black.c, 0.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_oklch_roundtrip() {
+        let colors = [
+            Color::from_hex(0x33_66_CC),
+            Color::from_hex(0xF5_A6_23),
+            Color::from_hex(0x12_34_56).with_alpha(0.5),
+            Color::CYAN,
+            Color::MAGENTA,
+        ];
+
+        for color in colors {
+            let roundtrip = Color::from_oklch(color.to_oklch());
+            assert_eq!(roundtrip.to_rgba_u8(), color.to_rgba_u8());
+        }
+    }
+
+    #[test]
+    fn test_from_oklch_gamut_mapping() {
+        let out_of_gamut = Oklch::new(0.6, 0.5, 264.0, 1.0);
+        let mapped = Color::from_oklch(out_of_gamut);
+        let mapped_oklch = mapped.to_oklch();
+
+        // Lightness and hue are kept while chroma is reduced
+        assert_relative_eq!(mapped_oklch.l, 0.6, epsilon = 1e-2);
+        assert_relative_eq!(mapped_oklch.h, 264.0, epsilon = 1.0);
+        assert!(mapped_oklch.c < 0.5);
+    }
+
+    #[test]
+    fn test_from_oklch_invalid_values() {
+        assert!(Color::from_oklch(Oklch::new(f32::NAN, f32::NAN, 0.0, 1.0)).is_valid());
+        assert!(Color::from_oklch(Oklch::new(2.0, -1.0, -30.0, 2.0)).is_valid());
+    }
+
+    #[test]
+    fn test_from_oklch_nan_hue() {
+        let expected = Color::from_oklch(Oklch::new(0.6, 0.1, 0.0, 1.0));
+
+        for hue in [f32::NAN, f32::INFINITY] {
+            let color = Color::from_oklch(Oklch::new(0.6, 0.1, hue, 1.0));
+            assert_eq!(color, expected);
+            assert_ne!(color, Color::BLACK);
+        }
+    }
+
+    #[test]
+    fn test_packed_formats_roundtrip() {
+        let color = Color::rgba(0x12, 0x34, 0x56, 0x78);
+
+        for format in [PixelFormat::Rgba8, PixelFormat::Bgra8, PixelFormat::Argb8] {
+            assert_eq!(Color::from_packed(color.to_packed(format), format), color);
+            assert_eq!(Color::from_bytes(color.to_bytes(format), format), color);
+        }
+    }
+
+    #[test]
+    fn test_packed_rgba8_matches_hex_alpha() {
+        let hex = 0x3F_FF_00_3F;
+
+        assert_eq!(
+            Color::from_packed(hex, PixelFormat::Rgba8),
+            Color::from_hex_alpha(hex)
+        );
+    }
+
+    #[test]
+    fn test_rgb565() {
+        let color = Color::rgb(CLAMPED_VALID, 0.5, 1.0);
+        let packed = color.to_rgb565();
+
+        // 0.247 * 31 = 7.66 -> 8, 0.5 * 63 = 31.5 -> 32, 1.0 * 31 = 31
+        assert_eq!(packed, (8 << 11) | (32 << 5) | 31);
+
+        let roundtrip = Color::from_rgb565(packed);
+        assert_relative_eq!(roundtrip.r, color.r, epsilon = 1.0 / 31.0);
+        assert_relative_eq!(roundtrip.g, color.g, epsilon = 1.0 / 63.0);
+        assert_relative_eq!(roundtrip.b, color.b, epsilon = 1.0 / 31.0);
+        assert_eq!(roundtrip.a, 1.0);
+    }
+
+    #[test]
+    fn test_f16_known_values() {
+        let cases: [(f32, u16); 8] = [
+            (0.0, 0x0000),
+            (-0.0, 0x8000),
+            (1.0, 0x3C00),
+            (0.5, 0x3800),
+            (0.333_333_34, 0x3555),
+            (65504.0, 0x7BFF),
+            (6.103_515_6e-5, 0x0400), // smallest normal
+            (5.960_464_5e-8, 0x0001), // smallest subnormal
+        ];
+
+        for (value, bits) in cases {
+            assert_eq!(
+                f32_to_f16_bits(value),
+                bits,
+                "f32 -> f16 failed for {}",
+                value
+            );
+            assert_relative_eq!(f16_bits_to_f32(bits), value, epsilon = 1e-3);
+        }
+
+        assert_eq!(f32_to_f16_bits(1e6), 0x7C00);
+        assert_eq!(f32_to_f16_bits(1e-10), 0x0000);
+        assert!(f16_bits_to_f32(f32_to_f16_bits(f32::NAN)).is_nan());
+    }
+
+    #[test]
+    fn test_f16_roundtrip_all_u8_levels() {
+        // Half-precision keeps every 8-bit level distinguishable
+        for level in 0..=u8::MAX {
+            let color = Color::rgba(level, level, level, level);
+            let roundtrip = Color::from_rgba_f16(color.to_rgba_f16());
+
+            assert_eq!(roundtrip.to_rgba_u8(), color.to_rgba_u8());
+        }
+    }
+
     // Property-based tests for round-trip conversions
     #[test]
     fn test_roundtrip_conversion_accuracy() {
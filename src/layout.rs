@@ -0,0 +1,543 @@
+//! Layouts placing widgets inside of a parent rectangle
+//!
+//! Immediate-mode widgets don't know their neighbours. Each widget asks the current layout for a
+//! rectangle of the size it wants, and the layout moves its cursor past it.
+
+pub mod constraint;
+pub mod flex;
+pub mod grid;
+pub mod layer;
+pub mod responsive;
+
+use crate::math::{Point, Rect, Size};
+use crate::style::Padding;
+
+/// Direction along which a layout places its items
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// Left to right
+    Horizontal,
+
+    /// Top to bottom
+    Vertical,
+}
+
+impl Axis {
+    /// Perpendicular axis
+    pub const fn cross(self) -> Self {
+        match self {
+            Axis::Horizontal => Axis::Vertical,
+            Axis::Vertical => Axis::Horizontal,
+        }
+    }
+
+    /// Extent of `size` along the axis
+    pub const fn extent(self, size: Size) -> f32 {
+        match self {
+            Axis::Horizontal => size.width,
+            Axis::Vertical => size.height,
+        }
+    }
+
+    /// Coordinate of `point` along the axis
+    pub const fn coord(self, point: Point) -> f32 {
+        match self {
+            Axis::Horizontal => point.x,
+            Axis::Vertical => point.y,
+        }
+    }
+
+    /// Size with extent `main` along the axis and `cross` along the perpendicular axis
+    pub const fn size(self, main: f32, cross: f32) -> Size {
+        match self {
+            Axis::Horizontal => Size::new(main, cross),
+            Axis::Vertical => Size::new(cross, main),
+        }
+    }
+
+    /// Point with coordinate `main` along the axis and `cross` along the perpendicular axis
+    pub const fn point(self, main: f32, cross: f32) -> Point {
+        match self {
+            Axis::Horizontal => Point::new(main, cross),
+            Axis::Vertical => Point::new(cross, main),
+        }
+    }
+}
+
+/// Placement of an item across the axis of its layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Align {
+    /// Top in a horizontal layout, left in a vertical one
+    #[default]
+    Start,
+
+    /// Centered
+    Center,
+
+    /// Bottom in a horizontal layout, right in a vertical one
+    End,
+
+    /// Fill the whole cross extent of the layout, ignoring the requested cross size
+    Stretch,
+}
+
+impl Align {
+    /// Place an item across the layout
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Cross coordinate where the available space starts
+    /// * `available` - Available cross extent
+    /// * `extent` - Requested cross extent of the item
+    ///
+    /// # Returns
+    ///
+    /// `(start, extent)` of the item. Items larger than the available space overflow it.
+    pub fn place(self, start: f32, available: f32, extent: f32) -> (f32, f32) {
+        match self {
+            Align::Start => (start, extent),
+            Align::Center => (start + (available - extent) / 2.0, extent),
+            Align::End => (start + available - extent, extent),
+            Align::Stretch => (start, available),
+        }
+    }
+}
+
+/// Layout handing out rectangles to widgets one after another
+pub trait Layout {
+    /// Reserve a rectangle of `size` for the next item, aligned by the layout alignment
+    fn allocate(&mut self, size: Size) -> Rect;
+
+    /// Reserve a rectangle of `size` for the next item, aligned by `align`
+    fn allocate_aligned(&mut self, size: Size, align: Align) -> Rect;
+
+    /// Leave `amount` of empty space before the next item
+    fn add_space(&mut self, amount: f32);
+
+    /// Space left for the next item, after its leading spacing
+    fn remaining(&self) -> Rect;
+
+    /// Outer size needed to fit every allocated item, including the padding
+    ///
+    /// # Notes
+    ///
+    /// - Use it to size the layout's own rectangle in its parent on the next frame
+    fn used_size(&self) -> Size;
+}
+
+/// Vertical box layout placing items top to bottom
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::{Align, Layout, VBox};
+/// use ho_gui::math::{Rect, Size};
+/// use ho_gui::style::Padding;
+///
+/// let mut column = VBox::new(Rect::new(0.0, 0.0, 200.0, 300.0))
+///     .with_padding(Padding::all(10.0))
+///     .with_spacing(4.0)
+///     .with_align(Align::Center);
+///
+/// let title = column.allocate(Size::new(100.0, 30.0));
+/// let button = column.allocate(Size::new(80.0, 20.0));
+/// let separator = column.allocate_aligned(Size::new(0.0, 1.0), Align::Stretch);
+///
+/// assert_eq!(title, Rect::new(50.0, 10.0, 100.0, 30.0));
+/// assert_eq!(button, Rect::new(60.0, 44.0, 80.0, 20.0));
+/// assert_eq!(separator, Rect::new(10.0, 68.0, 180.0, 1.0));
+/// assert_eq!(column.used_size(), Size::new(200.0, 79.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct VBox {
+    stack: Stack,
+}
+
+impl VBox {
+    /// Create VBox placing items inside of `rect`, starting at its top
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            stack: Stack::new(Axis::Vertical, rect),
+        }
+    }
+
+    /// Return the VBox with `spacing` between items
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.stack.spacing = to_valid(spacing);
+        self
+    }
+
+    /// Return the VBox placing items horizontally by `align`
+    pub fn with_align(mut self, align: Align) -> Self {
+        self.stack.align = align;
+        self
+    }
+
+    /// Return the VBox keeping `padding` free inside of its rectangle
+    pub fn with_padding(mut self, padding: Padding) -> Self {
+        self.stack.set_padding(padding);
+        self
+    }
+}
+
+impl Layout for VBox {
+    fn allocate(&mut self, size: Size) -> Rect {
+        self.stack.allocate(size, self.stack.align)
+    }
+
+    fn allocate_aligned(&mut self, size: Size, align: Align) -> Rect {
+        self.stack.allocate(size, align)
+    }
+
+    fn add_space(&mut self, amount: f32) {
+        self.stack.add_space(amount);
+    }
+
+    fn remaining(&self) -> Rect {
+        self.stack.remaining()
+    }
+
+    fn used_size(&self) -> Size {
+        self.stack.used_size()
+    }
+}
+
+/// Horizontal box layout placing items left to right
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::{Align, HBox, Layout};
+/// use ho_gui::math::{Rect, Size};
+///
+/// let mut toolbar = HBox::new(Rect::new(0.0, 0.0, 300.0, 40.0))
+///     .with_spacing(8.0)
+///     .with_align(Align::End);
+///
+/// let icon = toolbar.allocate(Size::new(16.0, 16.0));
+/// let button = toolbar.allocate(Size::new(60.0, 30.0));
+///
+/// assert_eq!(icon, Rect::new(0.0, 24.0, 16.0, 16.0));
+/// assert_eq!(button, Rect::new(24.0, 10.0, 60.0, 30.0));
+///
+/// // The rest of the row, e.g. for a text field
+/// assert_eq!(toolbar.remaining(), Rect::new(92.0, 0.0, 208.0, 40.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HBox {
+    stack: Stack,
+}
+
+impl HBox {
+    /// Create HBox placing items inside of `rect`, starting at its left
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            stack: Stack::new(Axis::Horizontal, rect),
+        }
+    }
+
+    /// Return the HBox with `spacing` between items
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.stack.spacing = to_valid(spacing);
+        self
+    }
+
+    /// Return the HBox placing items vertically by `align`
+    pub fn with_align(mut self, align: Align) -> Self {
+        self.stack.align = align;
+        self
+    }
+
+    /// Return the HBox keeping `padding` free inside of its rectangle
+    pub fn with_padding(mut self, padding: Padding) -> Self {
+        self.stack.set_padding(padding);
+        self
+    }
+}
+
+impl Layout for HBox {
+    fn allocate(&mut self, size: Size) -> Rect {
+        self.stack.allocate(size, self.stack.align)
+    }
+
+    fn allocate_aligned(&mut self, size: Size, align: Align) -> Rect {
+        self.stack.allocate(size, align)
+    }
+
+    fn add_space(&mut self, amount: f32) {
+        self.stack.add_space(amount);
+    }
+
+    fn remaining(&self) -> Rect {
+        self.stack.remaining()
+    }
+
+    fn used_size(&self) -> Size {
+        self.stack.used_size()
+    }
+}
+
+/// Cursor based placement shared by [`VBox`] and [`HBox`]
+#[derive(Debug, Clone, PartialEq)]
+struct Stack {
+    axis: Axis,
+    // Parent rectangle and the content rectangle inside of the padding
+    outer: Rect,
+    content: Rect,
+    padding: Padding,
+    spacing: f32,
+    align: Align,
+    // Main axis offset from the content start, past the last item
+    cursor: f32,
+    // Largest cross extent of an item
+    cross_used: f32,
+    has_items: bool,
+}
+
+impl Stack {
+    fn new(axis: Axis, rect: Rect) -> Self {
+        Self {
+            axis,
+            outer: rect,
+            content: rect,
+            padding: Padding::zero(),
+            spacing: 0.0,
+            align: Align::Start,
+            cursor: 0.0,
+            cross_used: 0.0,
+            has_items: false,
+        }
+    }
+
+    fn set_padding(&mut self, padding: Padding) {
+        self.padding = padding;
+        self.content = deflate(self.outer, padding);
+    }
+
+    /// Main axis offset of the next item
+    fn next_offset(&self) -> f32 {
+        if self.has_items {
+            self.cursor + self.spacing
+        } else {
+            self.cursor
+        }
+    }
+
+    fn allocate(&mut self, size: Size, align: Align) -> Rect {
+        let axis = self.axis;
+        let main_extent = to_valid(axis.extent(size));
+        let cross_extent = to_valid(axis.cross().extent(size));
+
+        let main = axis.coord(self.content.pos) + self.next_offset();
+        let (cross, cross_extent) = align.place(
+            axis.cross().coord(self.content.pos),
+            axis.cross().extent(self.content.size),
+            cross_extent,
+        );
+
+        self.cursor = self.next_offset() + main_extent;
+        self.cross_used = self.cross_used.max(cross_extent);
+        self.has_items = true;
+
+        Rect {
+            pos: axis.point(main, cross),
+            size: axis.size(main_extent, cross_extent),
+        }
+    }
+
+    fn add_space(&mut self, amount: f32) {
+        self.cursor += to_valid(amount);
+    }
+
+    fn remaining(&self) -> Rect {
+        let axis = self.axis;
+        let offset = self.next_offset();
+        let main_extent = (axis.extent(self.content.size) - offset).max(0.0);
+
+        Rect {
+            pos: axis.point(
+                axis.coord(self.content.pos) + offset,
+                axis.cross().coord(self.content.pos),
+            ),
+            size: axis.size(main_extent, axis.cross().extent(self.content.size)),
+        }
+    }
+
+    fn used_size(&self) -> Size {
+        let padding = self.padding;
+        let content = self.axis.size(self.cursor, self.cross_used);
+
+        Size::new(
+            content.width + padding.left + padding.right,
+            content.height + padding.top + padding.bottom,
+        )
+    }
+}
+
+/// `rect` without `padding`, never smaller than zero
+fn deflate(rect: Rect, padding: Padding) -> Rect {
+    Rect::new(
+        rect.left() + padding.left,
+        rect.top() + padding.top,
+        (rect.size.width - padding.left - padding.right).max(0.0),
+        (rect.size.height - padding.top - padding.bottom).max(0.0),
+    )
+}
+
+/// Sanitize lengths: `NaN` and negative values become 0.0
+pub(crate) const fn to_valid(x: f32) -> f32 {
+    if x.is_nan() || x < 0.0 { 0.0 } else { x }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARENT: Rect = Rect::new(10.0, 20.0, 100.0, 50.0);
+
+    mod axis {
+        use super::*;
+
+        #[test]
+        fn test_main_and_cross() {
+            let size = Size::new(3.0, 4.0);
+
+            assert_eq!(Axis::Horizontal.extent(size), 3.0);
+            assert_eq!(Axis::Vertical.extent(size), 4.0);
+            assert_eq!(Axis::Vertical.cross(), Axis::Horizontal);
+            assert_eq!(Axis::Vertical.size(4.0, 3.0), size);
+            assert_eq!(Axis::Vertical.point(2.0, 1.0), Point::new(1.0, 2.0));
+            assert_eq!(Axis::Horizontal.coord(Point::new(1.0, 2.0)), 1.0);
+        }
+
+        #[test]
+        fn test_align_place() {
+            assert_eq!(Align::Start.place(10.0, 100.0, 20.0), (10.0, 20.0));
+            assert_eq!(Align::Center.place(10.0, 100.0, 20.0), (50.0, 20.0));
+            assert_eq!(Align::End.place(10.0, 100.0, 20.0), (90.0, 20.0));
+            assert_eq!(Align::Stretch.place(10.0, 100.0, 20.0), (10.0, 100.0));
+
+            // Overflowing items stay centered
+            assert_eq!(Align::Center.place(0.0, 10.0, 20.0), (-5.0, 20.0));
+        }
+    }
+
+    mod vbox {
+        use super::*;
+
+        #[test]
+        fn test_items_stack_down() {
+            let mut column = VBox::new(PARENT).with_spacing(5.0);
+
+            assert_eq!(
+                column.allocate(Size::new(30.0, 10.0)),
+                Rect::new(10.0, 20.0, 30.0, 10.0)
+            );
+            assert_eq!(
+                column.allocate(Size::new(40.0, 15.0)),
+                Rect::new(10.0, 35.0, 40.0, 15.0)
+            );
+            assert_eq!(column.used_size(), Size::new(40.0, 30.0));
+            assert_eq!(column.remaining(), Rect::new(10.0, 55.0, 100.0, 15.0));
+        }
+
+        #[test]
+        fn test_cross_alignment() {
+            let mut column = VBox::new(PARENT).with_align(Align::End);
+            let size = Size::new(30.0, 10.0);
+
+            assert_eq!(column.allocate(size).pos, Point::new(80.0, 20.0));
+            assert_eq!(
+                column.allocate_aligned(size, Align::Center).pos,
+                Point::new(45.0, 30.0)
+            );
+            assert_eq!(
+                column.allocate_aligned(size, Align::Stretch),
+                Rect::new(10.0, 40.0, 100.0, 10.0)
+            );
+        }
+
+        #[test]
+        fn test_padding() {
+            let padding = Padding::new(1.0, 2.0, 3.0, 4.0);
+            let mut column = VBox::new(PARENT)
+                .with_padding(padding)
+                .with_align(Align::Stretch);
+
+            assert_eq!(column.remaining(), Rect::new(11.0, 23.0, 97.0, 43.0));
+            assert_eq!(
+                column.allocate(Size::new(5.0, 10.0)),
+                Rect::new(11.0, 23.0, 97.0, 10.0)
+            );
+            assert_eq!(column.used_size(), Size::new(100.0, 17.0));
+
+            // Padding larger than the parent leaves an empty content rect
+            let cramped = VBox::new(Rect::new(0.0, 0.0, 4.0, 4.0)).with_padding(Padding::all(5.0));
+            assert_eq!(cramped.remaining(), Rect::new(5.0, 5.0, 0.0, 0.0));
+        }
+
+        #[test]
+        fn test_add_space() {
+            let mut column = VBox::new(PARENT).with_spacing(2.0);
+            column.add_space(8.0);
+            assert_eq!(column.allocate(Size::new(1.0, 1.0)).pos.y, 28.0);
+
+            column.add_space(8.0);
+            column.add_space(-8.0);
+            assert_eq!(column.allocate(Size::new(1.0, 1.0)).pos.y, 39.0);
+        }
+
+        #[test]
+        fn test_overflow_and_invalid_sizes() {
+            let mut column = VBox::new(PARENT).with_spacing(f32::NAN);
+
+            assert_eq!(
+                column.allocate(Size::new(200.0, 80.0)),
+                Rect::new(10.0, 20.0, 200.0, 80.0)
+            );
+            assert_eq!(column.remaining().size, Size::new(100.0, 0.0));
+            assert_eq!(
+                column.allocate(Size::new(-1.0, f32::NAN)),
+                Rect::new(10.0, 100.0, 0.0, 0.0)
+            );
+        }
+    }
+
+    mod hbox {
+        use super::*;
+
+        #[test]
+        fn test_items_stack_right() {
+            let mut row = HBox::new(PARENT)
+                .with_spacing(4.0)
+                .with_align(Align::Center);
+
+            assert_eq!(
+                row.allocate(Size::new(20.0, 10.0)),
+                Rect::new(10.0, 40.0, 20.0, 10.0)
+            );
+            assert_eq!(
+                row.allocate(Size::new(30.0, 50.0)),
+                Rect::new(34.0, 20.0, 30.0, 50.0)
+            );
+            assert_eq!(row.used_size(), Size::new(54.0, 50.0));
+            assert_eq!(row.remaining(), Rect::new(68.0, 20.0, 42.0, 50.0));
+        }
+
+        #[test]
+        fn test_nested_in_vbox() {
+            let mut column = VBox::new(PARENT).with_spacing(5.0);
+            let mut row = HBox::new(column.allocate_aligned(Size::new(0.0, 20.0), Align::Stretch))
+                .with_spacing(5.0)
+                .with_align(Align::Stretch);
+
+            assert_eq!(
+                row.allocate(Size::new(45.0, 0.0)),
+                Rect::new(10.0, 20.0, 45.0, 20.0)
+            );
+            assert_eq!(row.remaining(), Rect::new(60.0, 20.0, 50.0, 20.0));
+            assert_eq!(
+                column.allocate(Size::new(10.0, 10.0)).pos,
+                Point::new(10.0, 45.0)
+            );
+        }
+    }
+}
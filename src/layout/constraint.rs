@@ -0,0 +1,1307 @@
+//! Constraint layout: rectangle edges solved from linear equalities and inequalities
+//!
+//! Nested boxes can't line up widgets living in different panels. Here every edge is a
+//! [`Variable`], the user states relations between them as [`Constraint`]s with a [`Strength`],
+//! and the [`Solver`] finds the values breaking as few weak wishes as possible.
+//!
+//! The solver is an incremental Cassowary simplex: constraints stay in the solver between
+//! frames, and each frame only suggests new values for edit variables such as the window size,
+//! which re-solves from the previous solution in a few pivots.
+
+use std::collections::BTreeMap;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::math::Rect;
+
+/// Unknown value solved by a [`Solver`], created by [`Solver::new_variable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Variable(usize);
+
+/// Linear combination of variables plus a constant
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::constraint::Solver;
+///
+/// let mut solver = Solver::new();
+/// let left = solver.new_variable();
+/// let right = solver.new_variable();
+///
+/// // Twice the width, plus a margin
+/// let expression = (right - left) * 2.0 + 8.0;
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Expression {
+    terms: Vec<(Variable, f64)>,
+    constant: f64,
+}
+
+impl Expression {
+    /// Create Expression of a constant
+    pub fn constant(value: f32) -> Self {
+        Self {
+            terms: Vec::new(),
+            constant: value as f64,
+        }
+    }
+
+    /// Constraint requiring the expression to be equal to `other`
+    pub fn equal_to(self, other: impl Into<Expression>) -> Constraint {
+        Constraint::new(self - other, Relation::Equal)
+    }
+
+    /// Constraint requiring the expression to be less than or equal to `other`
+    pub fn less_or_equal(self, other: impl Into<Expression>) -> Constraint {
+        Constraint::new(self - other, Relation::LessOrEqual)
+    }
+
+    /// Constraint requiring the expression to be greater than or equal to `other`
+    pub fn greater_or_equal(self, other: impl Into<Expression>) -> Constraint {
+        Constraint::new(self - other, Relation::GreaterOrEqual)
+    }
+}
+
+impl Variable {
+    /// Constraint requiring the variable to be equal to `other`
+    pub fn equal_to(self, other: impl Into<Expression>) -> Constraint {
+        Expression::from(self).equal_to(other)
+    }
+
+    /// Constraint requiring the variable to be less than or equal to `other`
+    pub fn less_or_equal(self, other: impl Into<Expression>) -> Constraint {
+        Expression::from(self).less_or_equal(other)
+    }
+
+    /// Constraint requiring the variable to be greater than or equal to `other`
+    pub fn greater_or_equal(self, other: impl Into<Expression>) -> Constraint {
+        Expression::from(self).greater_or_equal(other)
+    }
+}
+
+impl From<Variable> for Expression {
+    fn from(variable: Variable) -> Self {
+        Self {
+            terms: vec![(variable, 1.0)],
+            constant: 0.0,
+        }
+    }
+}
+
+impl From<f32> for Expression {
+    fn from(value: f32) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl<T: Into<Expression>> Add<T> for Expression {
+    type Output = Self;
+
+    fn add(mut self, other: T) -> Self::Output {
+        let other = other.into();
+        self.terms.extend(other.terms);
+        self.constant += other.constant;
+        self
+    }
+}
+
+impl<T: Into<Expression>> Sub<T> for Expression {
+    type Output = Self;
+
+    fn sub(self, other: T) -> Self::Output {
+        self + -other.into()
+    }
+}
+
+impl Mul<f32> for Expression {
+    type Output = Self;
+
+    fn mul(mut self, factor: f32) -> Self::Output {
+        let factor = factor as f64;
+        for (_, coefficient) in &mut self.terms {
+            *coefficient *= factor;
+        }
+        self.constant *= factor;
+        self
+    }
+}
+
+impl Neg for Expression {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self * -1.0
+    }
+}
+
+impl<T: Into<Expression>> Add<T> for Variable {
+    type Output = Expression;
+
+    fn add(self, other: T) -> Self::Output {
+        Expression::from(self) + other
+    }
+}
+
+impl<T: Into<Expression>> Sub<T> for Variable {
+    type Output = Expression;
+
+    fn sub(self, other: T) -> Self::Output {
+        Expression::from(self) - other
+    }
+}
+
+impl Mul<f32> for Variable {
+    type Output = Expression;
+
+    fn mul(self, factor: f32) -> Self::Output {
+        Expression::from(self) * factor
+    }
+}
+
+impl Neg for Variable {
+    type Output = Expression;
+
+    fn neg(self) -> Self::Output {
+        -Expression::from(self)
+    }
+}
+
+/// How hard the solver tries to satisfy a constraint
+///
+/// # Notes
+///
+/// - One strong constraint outweighs any number of weak ones in practice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strength {
+    /// Must hold, adding a conflicting constraint fails
+    #[default]
+    Required,
+
+    /// Holds unless it conflicts with required constraints
+    Strong,
+
+    /// Preference, given up first
+    Weak,
+}
+
+impl Strength {
+    /// Weight of the constraint's error in the solver objective
+    const fn weight(self) -> f64 {
+        match self {
+            Strength::Required => 1_001_001_000.0,
+            Strength::Strong => 1_000_000.0,
+            Strength::Weak => 1.0,
+        }
+    }
+}
+
+/// Comparison of a constraint's expression with zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Relation {
+    Equal,
+    LessOrEqual,
+    GreaterOrEqual,
+}
+
+/// Linear relation between variables, created by comparing expressions
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::constraint::{Solver, Strength};
+///
+/// let mut solver = Solver::new();
+/// let sidebar = solver.new_rect();
+///
+/// let wide = (sidebar.right - sidebar.left).equal_to(240.0).with_strength(Strength::Weak);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint {
+    expression: Expression,
+    relation: Relation,
+    strength: Strength,
+}
+
+impl Constraint {
+    fn new(expression: Expression, relation: Relation) -> Self {
+        Self {
+            expression,
+            relation,
+            strength: Strength::Required,
+        }
+    }
+
+    /// Return the constraint with `strength` instead of `Strength::Required`
+    pub const fn with_strength(mut self, strength: Strength) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    /// Strength of the constraint
+    pub const fn strength(&self) -> Strength {
+        self.strength
+    }
+}
+
+/// Handle of a constraint added to a [`Solver`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ConstraintId(usize);
+
+/// Edges of a rectangle solved by a [`Solver`], created by [`Solver::new_rect`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RectVariables {
+    /// Left edge
+    pub left: Variable,
+
+    /// Top edge
+    pub top: Variable,
+
+    /// Right edge
+    pub right: Variable,
+
+    /// Bottom edge
+    pub bottom: Variable,
+}
+
+impl RectVariables {
+    /// Width, `right - left`
+    pub fn width(&self) -> Expression {
+        self.right - self.left
+    }
+
+    /// Height, `bottom - top`
+    pub fn height(&self) -> Expression {
+        self.bottom - self.top
+    }
+
+    /// Horizontal center
+    pub fn center_x(&self) -> Expression {
+        (self.left + self.right) * 0.5
+    }
+
+    /// Vertical center
+    pub fn center_y(&self) -> Expression {
+        (self.top + self.bottom) * 0.5
+    }
+}
+
+/// Error type for constraint solving
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintError {
+    /// A required constraint conflicts with the other required constraints
+    Unsatisfiable,
+
+    /// The constraint was never added, or already removed
+    UnknownConstraint,
+
+    /// The variable is already an edit variable
+    DuplicateEditVariable,
+
+    /// The variable is not an edit variable
+    UnknownEditVariable,
+
+    /// Edit variables can't be `Strength::Required`, suggestions would conflict
+    RequiredEditVariable,
+
+    /// The suggested value is `NaN` or infinite
+    InvalidValue,
+}
+
+impl std::fmt::Display for ConstraintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstraintError::Unsatisfiable => write!(f, "Required constraints conflict"),
+            ConstraintError::UnknownConstraint => write!(f, "Unknown constraint"),
+            ConstraintError::DuplicateEditVariable => write!(f, "Variable is already edited"),
+            ConstraintError::UnknownEditVariable => write!(f, "Variable is not edited"),
+            ConstraintError::RequiredEditVariable => {
+                write!(f, "Edit variables can't be required")
+            }
+            ConstraintError::InvalidValue => write!(f, "Suggested value is not finite"),
+        }
+    }
+}
+
+impl std::error::Error for ConstraintError {}
+
+/// Incremental linear constraint solver
+///
+/// # Notes
+///
+/// - Constraints are added once and kept; per-frame inputs such as the window size are edit
+///   variables given new values with [`Solver::suggest_value`]
+/// - Variables not tied down by any constraint are 0.0
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::constraint::{Solver, Strength};
+/// use ho_gui::math::Rect;
+///
+/// let mut solver = Solver::new();
+/// let width = solver.new_variable();
+/// solver.add_edit_variable(width, Strength::Strong).unwrap();
+///
+/// let sidebar = solver.new_rect();
+/// let content = solver.new_rect();
+/// for rect in [sidebar, content] {
+///     solver.add_constraint(rect.top.equal_to(0.0)).unwrap();
+///     solver.add_constraint(rect.bottom.equal_to(100.0)).unwrap();
+/// }
+/// solver.add_constraint(sidebar.left.equal_to(0.0)).unwrap();
+/// solver.add_constraint(content.left.equal_to(sidebar.right + 8.0)).unwrap();
+/// solver.add_constraint(content.right.equal_to(width)).unwrap();
+/// solver.add_constraint(sidebar.width().greater_or_equal(100.0)).unwrap();
+/// solver
+///     .add_constraint(sidebar.width().equal_to(width * 0.25).with_strength(Strength::Weak))
+///     .unwrap();
+///
+/// // Each frame suggests the window width
+/// solver.suggest_value(width, 800.0).unwrap();
+/// assert_eq!(solver.rect(sidebar), Rect::new(0.0, 0.0, 200.0, 100.0));
+/// assert_eq!(solver.rect(content), Rect::new(208.0, 0.0, 592.0, 100.0));
+///
+/// // The minimum width beats the weak quarter of the window
+/// solver.suggest_value(width, 300.0).unwrap();
+/// assert_eq!(solver.rect(sidebar).size.width, 100.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Solver {
+    next_id: usize,
+    constraints: BTreeMap<ConstraintId, (Tag, Strength)>,
+    edits: BTreeMap<Variable, Edit>,
+    rows: BTreeMap<Symbol, Row>,
+    infeasible: Vec<Symbol>,
+    objective: Row,
+    artificial: Option<Row>,
+}
+
+impl Solver {
+    /// Create Solver without variables or constraints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a variable
+    pub fn new_variable(&mut self) -> Variable {
+        Variable(self.next_symbol(SymbolKind::External).id)
+    }
+
+    /// Create the four edges of a rectangle, requiring it to have no negative size
+    pub fn new_rect(&mut self) -> RectVariables {
+        let rect = RectVariables {
+            left: self.new_variable(),
+            top: self.new_variable(),
+            right: self.new_variable(),
+            bottom: self.new_variable(),
+        };
+
+        // Fresh variables can't conflict with anything yet
+        let _ = self.add_constraint(rect.right.greater_or_equal(rect.left));
+        let _ = self.add_constraint(rect.bottom.greater_or_equal(rect.top));
+
+        rect
+    }
+
+    /// Add `constraint`, solving the variables again
+    ///
+    /// # Returns
+    ///
+    /// Handle for [`Solver::remove_constraint`]
+    ///
+    /// # Errors
+    ///
+    /// `ConstraintError::Unsatisfiable` if a required constraint conflicts with the required
+    /// constraints already added. The solver is left as it was.
+    pub fn add_constraint(
+        &mut self,
+        constraint: Constraint,
+    ) -> Result<ConstraintId, ConstraintError> {
+        let (mut row, tag) = self.create_row(&constraint);
+
+        let subject = self.choose_subject(&row, &tag);
+        let subject = match subject {
+            None if row
+                .cells
+                .keys()
+                .all(|symbol| symbol.kind == SymbolKind::Dummy) =>
+            {
+                if !near_zero(row.constant) {
+                    self.remove_objective_errors(&tag, constraint.strength);
+                    self.optimize_objective();
+                    return Err(ConstraintError::Unsatisfiable);
+                }
+                Some(tag.marker)
+            }
+            subject => subject,
+        };
+
+        match subject {
+            Some(subject) => {
+                row.solve_for(subject);
+                self.substitute(subject, &row);
+                self.rows.insert(subject, row);
+            }
+            None => {
+                if !self.add_with_artificial_variable(row) {
+                    self.remove_tagged_rows(&tag);
+                    self.remove_objective_errors(&tag, constraint.strength);
+                    // Finding a solution pivoted the rows away from the optimum
+                    self.optimize_objective();
+                    return Err(ConstraintError::Unsatisfiable);
+                }
+            }
+        }
+
+        self.next_id += 1;
+        let id = ConstraintId(self.next_id);
+        self.constraints.insert(id, (tag, constraint.strength));
+        self.optimize_objective();
+
+        Ok(id)
+    }
+
+    /// Remove the constraint added as `id`, solving the variables again
+    ///
+    /// # Errors
+    ///
+    /// `ConstraintError::UnknownConstraint` if `id` is not in the solver
+    pub fn remove_constraint(&mut self, id: ConstraintId) -> Result<(), ConstraintError> {
+        let (tag, strength) = self
+            .constraints
+            .remove(&id)
+            .ok_or(ConstraintError::UnknownConstraint)?;
+
+        self.remove_objective_errors(&tag, strength);
+
+        if self.rows.remove(&tag.marker).is_none()
+            && let Some(leaving) = self.marker_leaving_row(tag.marker)
+            && let Some(mut row) = self.rows.remove(&leaving)
+        {
+            row.solve_for_pair(leaving, tag.marker);
+            self.substitute(tag.marker, &row);
+        }
+
+        self.optimize_objective();
+        Ok(())
+    }
+
+    /// Return true if the constraint added as `id` is still in the solver
+    pub fn has_constraint(&self, id: ConstraintId) -> bool {
+        self.constraints.contains_key(&id)
+    }
+
+    /// Make `variable` an input given new values by [`Solver::suggest_value`]
+    ///
+    /// # Errors
+    ///
+    /// - `ConstraintError::RequiredEditVariable` for `Strength::Required`
+    /// - `ConstraintError::DuplicateEditVariable` if `variable` is already edited
+    pub fn add_edit_variable(
+        &mut self,
+        variable: Variable,
+        strength: Strength,
+    ) -> Result<(), ConstraintError> {
+        if strength == Strength::Required {
+            return Err(ConstraintError::RequiredEditVariable);
+        }
+        if self.edits.contains_key(&variable) {
+            return Err(ConstraintError::DuplicateEditVariable);
+        }
+
+        let id = self.add_constraint(variable.equal_to(0.0).with_strength(strength))?;
+        let tag = self.constraints[&id].0;
+        self.edits.insert(
+            variable,
+            Edit {
+                id,
+                tag,
+                value: 0.0,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Stop editing `variable`
+    ///
+    /// # Errors
+    ///
+    /// `ConstraintError::UnknownEditVariable` if `variable` is not edited
+    pub fn remove_edit_variable(&mut self, variable: Variable) -> Result<(), ConstraintError> {
+        let edit = self
+            .edits
+            .remove(&variable)
+            .ok_or(ConstraintError::UnknownEditVariable)?;
+
+        self.remove_constraint(edit.id)
+    }
+
+    /// Suggest `value` for the edit `variable`, solving the other variables again
+    ///
+    /// # Notes
+    ///
+    /// - Re-solving starts from the previous solution, so suggesting values each frame is cheap
+    ///
+    /// # Errors
+    ///
+    /// - `ConstraintError::UnknownEditVariable` if `variable` is not edited
+    /// - `ConstraintError::InvalidValue` if `value` is `NaN` or infinite, the previous suggestion
+    ///   is kept
+    pub fn suggest_value(&mut self, variable: Variable, value: f32) -> Result<(), ConstraintError> {
+        let edit = self
+            .edits
+            .get_mut(&variable)
+            .ok_or(ConstraintError::UnknownEditVariable)?;
+        if !value.is_finite() {
+            return Err(ConstraintError::InvalidValue);
+        }
+
+        let delta = value as f64 - edit.value;
+        edit.value = value as f64;
+        let Tag { marker, other } = edit.tag;
+
+        if let Some(row) = self.rows.get_mut(&marker) {
+            if row.add(-delta) < 0.0 {
+                self.infeasible.push(marker);
+            }
+        } else if let Some(other) = other
+            && let Some(row) = self.rows.get_mut(&other)
+        {
+            if row.add(delta) < 0.0 {
+                self.infeasible.push(other);
+            }
+        } else {
+            for (&symbol, row) in &mut self.rows {
+                let coefficient = row.coefficient_for(marker);
+                if coefficient != 0.0
+                    && row.add(delta * coefficient) < 0.0
+                    && symbol.kind != SymbolKind::External
+                {
+                    self.infeasible.push(symbol);
+                }
+            }
+        }
+
+        self.dual_optimize();
+        Ok(())
+    }
+
+    /// Solved value of `variable`
+    pub fn value(&self, variable: Variable) -> f32 {
+        let symbol = Symbol {
+            id: variable.0,
+            kind: SymbolKind::External,
+        };
+
+        self.rows
+            .get(&symbol)
+            .map_or(0.0, |row| row.constant as f32)
+    }
+
+    /// Solved rectangle of `rect`
+    pub fn rect(&self, rect: RectVariables) -> Rect {
+        let left = self.value(rect.left);
+        let top = self.value(rect.top);
+
+        Rect::new(
+            left,
+            top,
+            self.value(rect.right) - left,
+            self.value(rect.bottom) - top,
+        )
+    }
+
+    fn next_symbol(&mut self, kind: SymbolKind) -> Symbol {
+        self.next_id += 1;
+        Symbol {
+            id: self.next_id,
+            kind,
+        }
+    }
+
+    /// Row of `constraint` in terms of the current non-basic symbols, and its marker symbols
+    fn create_row(&mut self, constraint: &Constraint) -> (Row, Tag) {
+        let expression = &constraint.expression;
+        let mut row = Row::new(expression.constant);
+
+        for &(variable, coefficient) in &expression.terms {
+            if near_zero(coefficient) {
+                continue;
+            }
+            let symbol = Symbol {
+                id: variable.0,
+                kind: SymbolKind::External,
+            };
+            match self.rows.get(&symbol) {
+                Some(basic) => row.insert_row(basic, coefficient),
+                None => row.insert_symbol(symbol, coefficient),
+            }
+        }
+
+        let weight = constraint.strength.weight();
+        let required = constraint.strength == Strength::Required;
+        let tag = match constraint.relation {
+            Relation::LessOrEqual | Relation::GreaterOrEqual => {
+                let sign = if constraint.relation == Relation::LessOrEqual {
+                    1.0
+                } else {
+                    -1.0
+                };
+                let slack = self.next_symbol(SymbolKind::Slack);
+                row.insert_symbol(slack, sign);
+
+                let error = (!required).then(|| self.next_symbol(SymbolKind::Error));
+                if let Some(error) = error {
+                    row.insert_symbol(error, -sign);
+                    self.objective.insert_symbol(error, weight);
+                }
+                Tag {
+                    marker: slack,
+                    other: error,
+                }
+            }
+            Relation::Equal if required => {
+                let dummy = self.next_symbol(SymbolKind::Dummy);
+                row.insert_symbol(dummy, 1.0);
+                Tag {
+                    marker: dummy,
+                    other: None,
+                }
+            }
+            Relation::Equal => {
+                let plus = self.next_symbol(SymbolKind::Error);
+                let minus = self.next_symbol(SymbolKind::Error);
+                row.insert_symbol(plus, -1.0);
+                row.insert_symbol(minus, 1.0);
+                self.objective.insert_symbol(plus, weight);
+                self.objective.insert_symbol(minus, weight);
+                Tag {
+                    marker: plus,
+                    other: Some(minus),
+                }
+            }
+        };
+
+        if row.constant < 0.0 {
+            row.reverse_sign();
+        }
+
+        (row, tag)
+    }
+
+    /// Symbol to make basic for a new row, `None` if an artificial variable is needed
+    fn choose_subject(&self, row: &Row, tag: &Tag) -> Option<Symbol> {
+        if let Some(&symbol) = row
+            .cells
+            .keys()
+            .find(|symbol| symbol.kind == SymbolKind::External)
+        {
+            return Some(symbol);
+        }
+
+        [Some(tag.marker), tag.other]
+            .into_iter()
+            .flatten()
+            .find(|symbol| symbol.is_restricted() && row.coefficient_for(*symbol) < 0.0)
+    }
+
+    /// Add `row` through a two-phase simplex, returning false if it is unsatisfiable
+    fn add_with_artificial_variable(&mut self, row: Row) -> bool {
+        let artificial = self.next_symbol(SymbolKind::Slack);
+        self.rows.insert(artificial, row.clone());
+        self.artificial = Some(row);
+
+        self.optimize(true);
+        let mut success = self
+            .artificial
+            .take()
+            .is_some_and(|objective| near_zero(objective.constant));
+
+        // Still basic: pivot the artificial variable out, unless its row is just a constant
+        if let Some(mut row) = self.rows.remove(&artificial)
+            && !row.cells.is_empty()
+        {
+            match row
+                .cells
+                .keys()
+                .copied()
+                .find(|symbol| symbol.is_restricted())
+            {
+                Some(entering) => {
+                    row.solve_for_pair(artificial, entering);
+                    self.substitute(entering, &row);
+                    self.rows.insert(entering, row);
+                }
+                None => success = false,
+            }
+        }
+
+        for row in self.rows.values_mut() {
+            row.remove(artificial);
+        }
+        self.objective.remove(artificial);
+
+        success
+    }
+
+    /// Undo a failed addition, which may have left rows basic in the constraint's markers
+    fn remove_tagged_rows(&mut self, tag: &Tag) {
+        for marker in [Some(tag.marker), tag.other].into_iter().flatten() {
+            if self.rows.remove(&marker).is_none()
+                && let Some(leaving) = self.marker_leaving_row(marker)
+                && let Some(mut row) = self.rows.remove(&leaving)
+            {
+                row.solve_for_pair(leaving, marker);
+                self.substitute(marker, &row);
+            }
+        }
+    }
+
+    /// Take the errors of a removed constraint out of the objective
+    fn remove_objective_errors(&mut self, tag: &Tag, strength: Strength) {
+        let weight = strength.weight();
+        for marker in [Some(tag.marker), tag.other].into_iter().flatten() {
+            if marker.kind != SymbolKind::Error {
+                continue;
+            }
+            match self.rows.get(&marker) {
+                Some(row) => self.objective.insert_row(row, -weight),
+                None => self.objective.insert_symbol(marker, -weight),
+            }
+        }
+    }
+
+    /// Row to pivot out so `marker` becomes basic and can be removed
+    fn marker_leaving_row(&self, marker: Symbol) -> Option<Symbol> {
+        let mut negative = (f64::INFINITY, None);
+        let mut positive = (f64::INFINITY, None);
+        let mut external = None;
+
+        for (&symbol, row) in &self.rows {
+            let coefficient = row.coefficient_for(marker);
+            if coefficient == 0.0 {
+                continue;
+            }
+            if symbol.kind == SymbolKind::External {
+                external = Some(symbol);
+            } else if coefficient < 0.0 {
+                let ratio = -row.constant / coefficient;
+                if ratio < negative.0 {
+                    negative = (ratio, Some(symbol));
+                }
+            } else {
+                let ratio = row.constant / coefficient;
+                if ratio < positive.0 {
+                    positive = (ratio, Some(symbol));
+                }
+            }
+        }
+
+        negative.1.or(positive.1).or(external)
+    }
+
+    /// Replace `symbol` by `row` everywhere, noting rows that became infeasible
+    fn substitute(&mut self, symbol: Symbol, row: &Row) {
+        for (&basic, other) in &mut self.rows {
+            other.substitute(symbol, row);
+            if basic.kind != SymbolKind::External && other.constant < 0.0 {
+                self.infeasible.push(basic);
+            }
+        }
+        self.objective.substitute(symbol, row);
+        if let Some(artificial) = &mut self.artificial {
+            artificial.substitute(symbol, row);
+        }
+    }
+
+    fn optimize_objective(&mut self) {
+        self.optimize(false);
+    }
+
+    /// Primal simplex on the objective, or on the artificial row of a two-phase addition
+    fn optimize(&mut self, artificial: bool) {
+        loop {
+            let objective = match &self.artificial {
+                Some(row) if artificial => row,
+                _ => &self.objective,
+            };
+            let Some(entering) = objective
+                .cells
+                .iter()
+                .find(|(symbol, coefficient)| {
+                    symbol.kind != SymbolKind::Dummy && **coefficient < 0.0
+                })
+                .map(|(symbol, _)| *symbol)
+            else {
+                return;
+            };
+
+            let mut leaving = (f64::INFINITY, None);
+            for (&symbol, row) in &self.rows {
+                let coefficient = row.coefficient_for(entering);
+                if symbol.kind != SymbolKind::External && coefficient < 0.0 {
+                    let ratio = -row.constant / coefficient;
+                    if ratio < leaving.0 {
+                        leaving = (ratio, Some(symbol));
+                    }
+                }
+            }
+
+            // Only possible for an unbounded objective, which errors never are
+            let Some(leaving) = leaving.1 else {
+                return;
+            };
+            let mut row = self.rows.remove(&leaving).unwrap_or_default();
+            row.solve_for_pair(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+    }
+
+    /// Dual simplex restoring feasibility after edit values changed
+    fn dual_optimize(&mut self) {
+        while let Some(leaving) = self.infeasible.pop() {
+            let Some(row) = self.rows.get(&leaving) else {
+                continue;
+            };
+            if row.constant >= 0.0 {
+                continue;
+            }
+
+            let mut entering = (f64::INFINITY, None);
+            for (&symbol, &coefficient) in &row.cells {
+                if coefficient > 0.0 && symbol.kind != SymbolKind::Dummy {
+                    let ratio = self.objective.coefficient_for(symbol) / coefficient;
+                    if ratio < entering.0 {
+                        entering = (ratio, Some(symbol));
+                    }
+                }
+            }
+
+            let Some(entering) = entering.1 else {
+                continue;
+            };
+            let mut row = self.rows.remove(&leaving).unwrap_or_default();
+            row.solve_for_pair(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum SymbolKind {
+    External,
+    Slack,
+    Error,
+    Dummy,
+}
+
+/// Tableau column, ordered by creation so solving is deterministic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Symbol {
+    id: usize,
+    kind: SymbolKind,
+}
+
+impl Symbol {
+    /// Slack and error symbols must stay non-negative
+    fn is_restricted(&self) -> bool {
+        matches!(self.kind, SymbolKind::Slack | SymbolKind::Error)
+    }
+}
+
+/// Symbols added for a constraint, used to find and remove it again
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Tag {
+    marker: Symbol,
+    other: Option<Symbol>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Edit {
+    id: ConstraintId,
+    tag: Tag,
+    value: f64,
+}
+
+/// `basic = constant + Σ coefficient · symbol`
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Row {
+    cells: BTreeMap<Symbol, f64>,
+    constant: f64,
+}
+
+impl Row {
+    fn new(constant: f64) -> Self {
+        Self {
+            cells: BTreeMap::new(),
+            constant,
+        }
+    }
+
+    fn add(&mut self, value: f64) -> f64 {
+        self.constant += value;
+        self.constant
+    }
+
+    fn coefficient_for(&self, symbol: Symbol) -> f64 {
+        self.cells.get(&symbol).copied().unwrap_or(0.0)
+    }
+
+    fn insert_symbol(&mut self, symbol: Symbol, coefficient: f64) {
+        let cell = self.cells.entry(symbol).or_insert(0.0);
+        *cell += coefficient;
+        if near_zero(*cell) {
+            self.cells.remove(&symbol);
+        }
+    }
+
+    fn insert_row(&mut self, other: &Row, coefficient: f64) {
+        self.constant += other.constant * coefficient;
+        for (&symbol, &other_coefficient) in &other.cells {
+            self.insert_symbol(symbol, other_coefficient * coefficient);
+        }
+    }
+
+    fn remove(&mut self, symbol: Symbol) {
+        self.cells.remove(&symbol);
+    }
+
+    fn reverse_sign(&mut self) {
+        self.constant = -self.constant;
+        for coefficient in self.cells.values_mut() {
+            *coefficient = -*coefficient;
+        }
+    }
+
+    /// Rewrite `0 = row` as `symbol = ...`, dropping `symbol` from the cells
+    fn solve_for(&mut self, symbol: Symbol) {
+        let factor = -1.0 / self.cells.remove(&symbol).unwrap_or(1.0);
+        self.constant *= factor;
+        for coefficient in self.cells.values_mut() {
+            *coefficient *= factor;
+        }
+    }
+
+    /// Rewrite `lhs = row` as `rhs = ...`
+    fn solve_for_pair(&mut self, lhs: Symbol, rhs: Symbol) {
+        self.insert_symbol(lhs, -1.0);
+        self.solve_for(rhs);
+    }
+
+    fn substitute(&mut self, symbol: Symbol, row: &Row) {
+        if let Some(coefficient) = self.cells.remove(&symbol) {
+            self.insert_row(row, coefficient);
+        }
+    }
+}
+
+fn near_zero(value: f64) -> bool {
+    value.abs() < 1e-8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    mod expression {
+        use super::*;
+
+        #[test]
+        fn test_operators_collect_terms() {
+            let mut solver = Solver::new();
+            let a = solver.new_variable();
+            let b = solver.new_variable();
+
+            let expression = (a * 2.0 - b + 3.0) * 0.5;
+
+            assert_eq!(expression.terms, vec![(a, 1.0), (b, -0.5)]);
+            assert_eq!(expression.constant, 1.5);
+        }
+
+        #[test]
+        fn test_constraints_default_to_required() {
+            let mut solver = Solver::new();
+            let a = solver.new_variable();
+
+            assert_eq!(a.equal_to(1.0).strength(), Strength::Required);
+            assert_eq!(
+                a.equal_to(1.0).with_strength(Strength::Weak).strength(),
+                Strength::Weak
+            );
+        }
+    }
+
+    mod solve {
+        use super::*;
+
+        #[test]
+        fn test_equalities_chain() {
+            let mut solver = Solver::new();
+            let a = solver.new_variable();
+            let b = solver.new_variable();
+
+            solver.add_constraint(a.equal_to(10.0)).unwrap();
+            solver.add_constraint(b.equal_to(a * 2.0 + 5.0)).unwrap();
+
+            assert_eq!(solver.value(a), 10.0);
+            assert_eq!(solver.value(b), 25.0);
+        }
+
+        #[test]
+        fn test_unconstrained_variables_are_zero() {
+            let mut solver = Solver::new();
+            let a = solver.new_variable();
+
+            assert_eq!(solver.value(a), 0.0);
+        }
+
+        #[test]
+        fn test_inequalities_bound_weak_wishes() {
+            let mut solver = Solver::new();
+            let a = solver.new_variable();
+
+            solver.add_constraint(a.less_or_equal(50.0)).unwrap();
+            solver
+                .add_constraint(a.equal_to(80.0).with_strength(Strength::Weak))
+                .unwrap();
+            assert_eq!(solver.value(a), 50.0);
+
+            solver
+                .add_constraint(a.greater_or_equal(60.0).with_strength(Strength::Strong))
+                .unwrap();
+            assert_eq!(solver.value(a), 50.0);
+        }
+
+        #[test]
+        fn test_strong_beats_weak() {
+            let mut solver = Solver::new();
+            let a = solver.new_variable();
+
+            solver
+                .add_constraint(a.equal_to(10.0).with_strength(Strength::Weak))
+                .unwrap();
+            solver
+                .add_constraint(a.equal_to(20.0).with_strength(Strength::Strong))
+                .unwrap();
+            solver
+                .add_constraint(a.equal_to(30.0).with_strength(Strength::Weak))
+                .unwrap();
+
+            assert_eq!(solver.value(a), 20.0);
+        }
+
+        #[test]
+        fn test_conflicting_required_constraints_fail() {
+            let mut solver = Solver::new();
+            let a = solver.new_variable();
+            let b = solver.new_variable();
+
+            solver.add_constraint(a.equal_to(10.0)).unwrap();
+            solver.add_constraint(b.greater_or_equal(a + 5.0)).unwrap();
+
+            assert_eq!(
+                solver.add_constraint(a.equal_to(20.0)),
+                Err(ConstraintError::Unsatisfiable)
+            );
+            assert_eq!(
+                solver.add_constraint(b.less_or_equal(a)),
+                Err(ConstraintError::Unsatisfiable)
+            );
+
+            // The solver keeps its previous solution
+            assert_eq!(solver.value(a), 10.0);
+            assert_relative_eq!(solver.value(b), 15.0);
+            solver.add_constraint(b.equal_to(40.0)).unwrap();
+            assert_eq!(solver.value(b), 40.0);
+        }
+
+        #[test]
+        fn test_rejected_constraints_keep_the_optimum() {
+            let mut solver = Solver::new();
+            let x = solver.new_variable();
+            let y = solver.new_variable();
+
+            solver.add_constraint(x.greater_or_equal(10.0)).unwrap();
+            solver.add_constraint(y.greater_or_equal(x + 5.0)).unwrap();
+            solver
+                .add_constraint(y.equal_to(20.0).with_strength(Strength::Weak))
+                .unwrap();
+            solver
+                .add_constraint(x.equal_to(12.0).with_strength(Strength::Strong))
+                .unwrap();
+
+            for rejected in [y.less_or_equal(x), x.less_or_equal(5.0)] {
+                assert_eq!(
+                    solver.add_constraint(rejected),
+                    Err(ConstraintError::Unsatisfiable)
+                );
+                assert_relative_eq!(solver.value(x), 12.0);
+                assert_relative_eq!(solver.value(y), 20.0);
+            }
+        }
+
+        #[test]
+        fn test_removed_constraints_release_variables() {
+            let mut solver = Solver::new();
+            let a = solver.new_variable();
+
+            solver
+                .add_constraint(a.equal_to(10.0).with_strength(Strength::Weak))
+                .unwrap();
+            let strong = solver
+                .add_constraint(a.equal_to(20.0).with_strength(Strength::Strong))
+                .unwrap();
+            let bound = solver.add_constraint(a.less_or_equal(15.0)).unwrap();
+            assert_eq!(solver.value(a), 15.0);
+
+            solver.remove_constraint(bound).unwrap();
+            assert_eq!(solver.value(a), 20.0);
+
+            solver.remove_constraint(strong).unwrap();
+            assert_eq!(solver.value(a), 10.0);
+            assert!(!solver.has_constraint(strong));
+            assert_eq!(
+                solver.remove_constraint(strong),
+                Err(ConstraintError::UnknownConstraint)
+            );
+        }
+    }
+
+    mod edit {
+        use super::*;
+
+        #[test]
+        fn test_suggestions_resolve_incrementally() {
+            let mut solver = Solver::new();
+            let width = solver.new_variable();
+            let half = solver.new_variable();
+            solver.add_edit_variable(width, Strength::Strong).unwrap();
+            solver.add_constraint(half.equal_to(width * 0.5)).unwrap();
+            solver.add_constraint(half.less_or_equal(300.0)).unwrap();
+
+            for (suggested, expected) in
+                [(100.0, 50.0), (400.0, 200.0), (900.0, 300.0), (10.0, 5.0)]
+            {
+                solver.suggest_value(width, suggested).unwrap();
+                assert_relative_eq!(solver.value(half), expected);
+            }
+            // The bound wins over the strong suggestion
+            solver.suggest_value(width, 900.0).unwrap();
+            assert_relative_eq!(solver.value(width), 600.0);
+        }
+
+        #[test]
+        fn test_non_finite_suggestions_are_rejected() {
+            let mut solver = Solver::new();
+            let width = solver.new_variable();
+            let half = solver.new_variable();
+            solver.add_edit_variable(width, Strength::Strong).unwrap();
+            solver.add_constraint(half.equal_to(width * 0.5)).unwrap();
+            solver.suggest_value(width, 100.0).unwrap();
+
+            for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+                assert_eq!(
+                    solver.suggest_value(width, value),
+                    Err(ConstraintError::InvalidValue)
+                );
+                assert_relative_eq!(solver.value(half), 50.0);
+            }
+
+            solver.suggest_value(width, 300.0).unwrap();
+            assert_relative_eq!(solver.value(half), 150.0);
+        }
+
+        #[test]
+        fn test_edit_variable_errors() {
+            let mut solver = Solver::new();
+            let a = solver.new_variable();
+
+            assert_eq!(
+                solver.add_edit_variable(a, Strength::Required),
+                Err(ConstraintError::RequiredEditVariable)
+            );
+            assert_eq!(
+                solver.suggest_value(a, 1.0),
+                Err(ConstraintError::UnknownEditVariable)
+            );
+
+            solver.add_edit_variable(a, Strength::Strong).unwrap();
+            assert_eq!(
+                solver.add_edit_variable(a, Strength::Weak),
+                Err(ConstraintError::DuplicateEditVariable)
+            );
+
+            solver.suggest_value(a, 7.0).unwrap();
+            assert_eq!(solver.value(a), 7.0);
+            solver.remove_edit_variable(a).unwrap();
+            assert_eq!(solver.value(a), 0.0);
+            assert_eq!(
+                solver.remove_edit_variable(a),
+                Err(ConstraintError::UnknownEditVariable)
+            );
+        }
+    }
+
+    mod rects {
+        use super::*;
+
+        #[test]
+        fn test_rects_have_no_negative_size() {
+            let mut solver = Solver::new();
+            let rect = solver.new_rect();
+
+            solver.add_constraint(rect.left.equal_to(50.0)).unwrap();
+            solver
+                .add_constraint(rect.right.equal_to(20.0).with_strength(Strength::Strong))
+                .unwrap();
+
+            assert_eq!(solver.rect(rect), Rect::new(50.0, 0.0, 0.0, 0.0));
+            assert_eq!(
+                solver.add_constraint(rect.height().equal_to(-1.0)),
+                Err(ConstraintError::Unsatisfiable)
+            );
+        }
+
+        #[test]
+        fn test_aligns_across_panels() {
+            let mut solver = Solver::new();
+            let height = solver.new_variable();
+            solver.add_edit_variable(height, Strength::Strong).unwrap();
+
+            // Two panels side by side, each with a label, whose labels share a baseline
+            let left = solver.new_rect();
+            let right = solver.new_rect();
+            let left_label = solver.new_rect();
+            let right_label = solver.new_rect();
+            for constraint in [
+                left.left.equal_to(0.0),
+                left.top.equal_to(0.0),
+                left.bottom.equal_to(height),
+                right.left.equal_to(left.right),
+                right.top.equal_to(0.0),
+                right.bottom.equal_to(height),
+                left.width().equal_to(100.0),
+                right.width().equal_to(100.0),
+                left_label.left.equal_to(left.left + 4.0),
+                right_label.left.equal_to(right.left + 4.0),
+                left_label.width().equal_to(50.0),
+                right_label.width().equal_to(70.0),
+                left_label.height().equal_to(20.0),
+                right_label.height().equal_to(20.0),
+                left_label.center_y().equal_to(left.center_y()),
+                right_label.top.equal_to(left_label.top),
+            ] {
+                solver.add_constraint(constraint).unwrap();
+            }
+
+            solver.suggest_value(height, 100.0).unwrap();
+            assert_eq!(solver.rect(left_label), Rect::new(4.0, 40.0, 50.0, 20.0));
+            assert_eq!(solver.rect(right_label), Rect::new(104.0, 40.0, 70.0, 20.0));
+
+            solver.suggest_value(height, 60.0).unwrap();
+            assert_eq!(solver.rect(right_label), Rect::new(104.0, 20.0, 70.0, 20.0));
+            assert_eq!(solver.rect(right), Rect::new(100.0, 0.0, 100.0, 60.0));
+        }
+    }
+}
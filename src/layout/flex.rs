@@ -0,0 +1,842 @@
+//! Flexbox layout: items grow and shrink along the main axis, optionally wrapping into lines
+//!
+//! [`Flex::arrange`] places a known list of items in one go. Immediate-mode widgets are added one
+//! at a time though, so [`FlexState`] keeps the arrangement of the previous frame: each widget is
+//! placed by last frame's layout of the same items, and [`FlexFrame::end`] measures the new items
+//! and arranges them for the next frame.
+
+use std::ops::Range;
+
+use super::{Align, Axis, deflate, to_valid};
+use crate::math::{Rect, Size};
+use crate::style::Padding;
+
+/// Distribution of the free main axis space of a line, like CSS `justify-content`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Justify {
+    /// Items packed at the start
+    #[default]
+    Start,
+
+    /// Items packed at the end
+    End,
+
+    /// Items packed in the middle
+    Center,
+
+    /// First and last item at the edges, free space evenly between items
+    SpaceBetween,
+
+    /// Free space evenly around each item, so edges get half the space of the gaps
+    SpaceAround,
+
+    /// Free space evenly between items and the edges
+    SpaceEvenly,
+}
+
+impl Justify {
+    /// Main axis space before the first item and extra space between items
+    ///
+    /// # Notes
+    ///
+    /// - Overflowing lines (negative `free`) aren't spread, only shifted by `End` and `Center`
+    fn spacing(self, free: f32, count: usize) -> (f32, f32) {
+        let n = count as f32;
+
+        match self {
+            Justify::Start => (0.0, 0.0),
+            Justify::End => (free, 0.0),
+            Justify::Center => (free / 2.0, 0.0),
+            _ if free <= 0.0 => (0.0, 0.0),
+            Justify::SpaceBetween if count > 1 => (0.0, free / (n - 1.0)),
+            Justify::SpaceBetween => (0.0, 0.0),
+            Justify::SpaceAround => (free / n / 2.0, free / n),
+            Justify::SpaceEvenly => (free / (n + 1.0), free / (n + 1.0)),
+        }
+    }
+}
+
+/// Item of a [`Flex`] layout
+///
+/// # Notes
+///
+/// - `size` is the measured content size. Its main axis extent is the flex basis, unless
+///   `basis` is set.
+/// - `min_size` and `max_size` clamp the item on both axes, after growing, shrinking and
+///   stretching
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlexItem {
+    /// Measured content size
+    pub size: Size,
+
+    /// Main axis size before growing and shrinking, `None` to use `size`
+    pub basis: Option<f32>,
+
+    /// Share of the free space taken when the line is too short, 0.0 to keep the basis
+    pub grow: f32,
+
+    /// Share of the overflow given up when the line is too long, weighted by the basis
+    pub shrink: f32,
+
+    /// Smallest size
+    pub min_size: Size,
+
+    /// Largest size
+    pub max_size: Size,
+
+    /// Cross axis alignment overriding `Flex::align_items`
+    pub align_self: Option<Align>,
+}
+
+impl FlexItem {
+    /// Create FlexItem with measured `size`, which shrinks but doesn't grow
+    pub const fn new(size: Size) -> Self {
+        Self {
+            size,
+            basis: None,
+            grow: 0.0,
+            shrink: 1.0,
+            min_size: Size::zero(),
+            max_size: Size::new(f32::INFINITY, f32::INFINITY),
+            align_self: None,
+        }
+    }
+
+    /// Return the item with flex basis `basis`
+    pub const fn with_basis(mut self, basis: f32) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    /// Return the item with grow factor `grow`
+    pub const fn with_grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    /// Return the item with shrink factor `shrink`
+    pub const fn with_shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    /// Return the item with minimum size `min_size`
+    pub const fn with_min_size(mut self, min_size: Size) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Return the item with maximum size `max_size`
+    pub const fn with_max_size(mut self, max_size: Size) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Return the item aligned by `align` instead of the container's `align_items`
+    pub const fn with_align_self(mut self, align: Align) -> Self {
+        self.align_self = Some(align);
+        self
+    }
+
+    /// Flex basis, sanitized to a non-negative length
+    fn base(&self, axis: Axis) -> f32 {
+        to_valid(self.basis.unwrap_or(axis.extent(self.size)))
+    }
+
+    /// `value` clamped by the min and max size along `axis`
+    fn clamp(&self, axis: Axis, value: f32) -> f32 {
+        let min = to_valid(axis.extent(self.min_size));
+        value.min(axis.extent(self.max_size)).max(min)
+    }
+}
+
+/// Flexbox container settings, like the CSS `display: flex` properties
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::flex::{Flex, FlexItem, Justify};
+/// use ho_gui::math::{Rect, Size};
+///
+/// // Toolbar: fixed buttons around a search field taking the rest
+/// let toolbar = Flex::row().with_gap(4.0);
+/// let rects = toolbar.arrange(
+///     Rect::new(0.0, 0.0, 300.0, 32.0),
+///     &[
+///         FlexItem::new(Size::new(32.0, 32.0)),
+///         FlexItem::new(Size::new(100.0, 24.0)).with_grow(1.0),
+///         FlexItem::new(Size::new(32.0, 32.0)),
+///     ],
+/// );
+///
+/// assert_eq!(rects[1], Rect::new(36.0, 0.0, 228.0, 32.0));
+/// assert_eq!(rects[2].pos.x, 268.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Flex {
+    /// Main axis
+    pub direction: Axis,
+
+    /// Break items into several lines instead of shrinking them
+    pub wrap: bool,
+
+    /// Main axis distribution of free space
+    pub justify: Justify,
+
+    /// Cross axis alignment of items in their line
+    pub align_items: Align,
+
+    /// Space between items, and between lines
+    pub gap: f32,
+
+    /// Space kept free inside of the container
+    pub padding: Padding,
+}
+
+impl Flex {
+    /// Create Flex placing items left to right, stretched to the container height
+    pub const fn row() -> Self {
+        Self::new(Axis::Horizontal)
+    }
+
+    /// Create Flex placing items top to bottom, stretched to the container width
+    pub const fn column() -> Self {
+        Self::new(Axis::Vertical)
+    }
+
+    const fn new(direction: Axis) -> Self {
+        Self {
+            direction,
+            wrap: false,
+            justify: Justify::Start,
+            align_items: Align::Stretch,
+            gap: 0.0,
+            padding: Padding::zero(),
+        }
+    }
+
+    /// Return the Flex wrapping items into several lines if `wrap` is true
+    pub const fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Return the Flex distributing free space by `justify`
+    pub const fn with_justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Return the Flex aligning items across lines by `align`
+    pub const fn with_align_items(mut self, align: Align) -> Self {
+        self.align_items = align;
+        self
+    }
+
+    /// Return the Flex with `gap` between items and lines
+    pub const fn with_gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Return the Flex keeping `padding` free inside of the container
+    pub const fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Place `items` inside of `rect`
+    ///
+    /// # Notes
+    ///
+    /// - Follows the CSS flexbox algorithm: items are broken into lines, free space of each line
+    ///   is handed out by `grow` (or taken back by `shrink`), and items hitting their min or max
+    ///   size are frozen while the rest is redistributed
+    /// - A single line without wrapping spans the whole cross extent. Wrapped lines are as thick
+    ///   as their thickest item and are stacked from the cross start.
+    ///
+    /// # Returns
+    ///
+    /// Rectangle of each item, in the order of `items`
+    pub fn arrange(&self, rect: Rect, items: &[FlexItem]) -> Vec<Rect> {
+        if items.is_empty() {
+            return Vec::new();
+        }
+
+        let axis = self.direction;
+        let cross_axis = axis.cross();
+        let gap = to_valid(self.gap);
+
+        let content = deflate(rect, self.padding);
+        let available = axis.extent(content.size);
+        let hypothetical: Vec<f32> = items
+            .iter()
+            .map(|item| item.clamp(axis, item.base(axis)))
+            .collect();
+
+        let mut rects = vec![Rect::zero(); items.len()];
+        let mut line_start = cross_axis.coord(content.pos);
+
+        for line in self.lines(&hypothetical, available, gap) {
+            let line_items = &items[line.clone()];
+            let gaps = gap * (line.len() - 1) as f32;
+            let mains = resolve_flexible(
+                axis,
+                line_items,
+                &hypothetical[line.clone()],
+                available - gaps,
+            );
+
+            let thickness = if self.wrap {
+                line_items
+                    .iter()
+                    .map(|item| item.clamp(cross_axis, to_valid(cross_axis.extent(item.size))))
+                    .fold(0.0, f32::max)
+            } else {
+                cross_axis.extent(content.size)
+            };
+
+            let free = available - gaps - mains.iter().sum::<f32>();
+            let (lead, extra) = self.justify.spacing(free, line.len());
+            let mut main = axis.coord(content.pos) + lead;
+
+            for ((rect, item), &extent) in rects[line].iter_mut().zip(line_items).zip(&mains) {
+                let align = item.align_self.unwrap_or(self.align_items);
+                let requested = to_valid(cross_axis.extent(item.size));
+                let (cross, cross_extent) = match align {
+                    Align::Stretch => (line_start, item.clamp(cross_axis, thickness)),
+                    _ => align.place(line_start, thickness, item.clamp(cross_axis, requested)),
+                };
+
+                *rect = Rect {
+                    pos: axis.point(main, cross),
+                    size: axis.size(extent, cross_extent),
+                };
+                main += extent + gap + extra;
+            }
+
+            line_start += thickness + gap;
+        }
+
+        rects
+    }
+
+    /// Break items into lines by their hypothetical main sizes
+    fn lines(&self, hypothetical: &[f32], available: f32, gap: f32) -> Vec<Range<usize>> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        let mut used = 0.0;
+
+        for (index, &extent) in hypothetical.iter().enumerate() {
+            if self.wrap && index > start && used + gap + extent > available {
+                lines.push(start..index);
+                start = index;
+                used = extent;
+            } else if index > start {
+                used += gap + extent;
+            } else {
+                used = extent;
+            }
+        }
+        if start < hypothetical.len() {
+            lines.push(start..hypothetical.len());
+        }
+
+        lines
+    }
+}
+
+/// Main sizes of the items of a line sharing `space`, by the CSS "resolve flexible lengths" steps
+fn resolve_flexible(axis: Axis, items: &[FlexItem], hypothetical: &[f32], space: f32) -> Vec<f32> {
+    let mut target = hypothetical.to_vec();
+    let total: f32 = hypothetical.iter().sum();
+    if total == space {
+        return target;
+    }
+    let growing = total < space;
+
+    // Items without a factor, or already clamped the other way, keep their hypothetical size
+    let mut frozen: Vec<bool> = items
+        .iter()
+        .zip(hypothetical)
+        .map(|(item, &extent)| {
+            let base = item.base(axis);
+            if growing {
+                to_valid(item.grow) == 0.0 || base > extent
+            } else {
+                to_valid(item.shrink) == 0.0 || base < extent
+            }
+        })
+        .collect();
+
+    // Every pass freezes at least one item
+    for _ in 0..items.len() {
+        if !frozen.contains(&false) {
+            break;
+        }
+
+        let used: f32 = items
+            .iter()
+            .zip(&target)
+            .zip(&frozen)
+            .map(|((item, &extent), &frozen)| if frozen { extent } else { item.base(axis) })
+            .sum();
+        let free = space - used;
+
+        let weight = |item: &FlexItem| {
+            if growing {
+                to_valid(item.grow)
+            } else {
+                to_valid(item.shrink) * item.base(axis)
+            }
+        };
+        let total_weight: f32 = items
+            .iter()
+            .zip(&frozen)
+            .filter(|&(_, &frozen)| !frozen)
+            .map(|(item, _)| weight(item))
+            .sum();
+
+        let mut violation = 0.0;
+        let mut violations = vec![0.0; items.len()];
+        for (index, item) in items.iter().enumerate() {
+            if frozen[index] {
+                continue;
+            }
+
+            let base = item.base(axis);
+            let flexed = if total_weight > 0.0 {
+                base + free * weight(item) / total_weight
+            } else {
+                base
+            };
+            // Infinite sizes leave nothing to share, so such items keep their size
+            if !flexed.is_finite() {
+                frozen[index] = true;
+                continue;
+            }
+            let clamped = item.clamp(axis, flexed);
+
+            target[index] = clamped;
+            violations[index] = clamped - flexed;
+            violation += clamped - flexed;
+        }
+
+        for (index, &item_violation) in violations.iter().enumerate() {
+            let freeze = if violation == 0.0 {
+                true
+            } else if violation > 0.0 {
+                item_violation > 0.0
+            } else {
+                item_violation < 0.0
+            };
+            frozen[index] |= freeze;
+        }
+    }
+
+    target
+}
+
+/// Per-container layout cache, kept by the widget between frames
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::flex::{Flex, FlexItem, FlexState};
+/// use ho_gui::math::{Rect, Size};
+///
+/// let mut state = FlexState::new();
+/// let rect = Rect::new(0.0, 0.0, 200.0, 20.0);
+///
+/// let mut frame = |state: &mut FlexState| {
+///     let mut flex = state.begin(Flex::row().with_gap(10.0), rect);
+///     let label = flex.allocate(FlexItem::new(Size::new(50.0, 20.0)));
+///     let field = flex.allocate(FlexItem::new(Size::new(50.0, 20.0)).with_grow(1.0));
+///     (label, field, flex.end())
+/// };
+///
+/// // The first frame guesses, and asks for another frame
+/// let (_, _, changed) = frame(&mut state);
+/// assert!(changed);
+///
+/// // Following frames are placed exactly
+/// let (label, field, changed) = frame(&mut state);
+/// assert_eq!(label, Rect::new(0.0, 0.0, 50.0, 20.0));
+/// assert_eq!(field, Rect::new(60.0, 0.0, 140.0, 20.0));
+/// assert!(!changed);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlexState {
+    // Inputs and result of the last arrangement
+    flex: Option<Flex>,
+    rect: Rect,
+    items: Vec<FlexItem>,
+    rects: Vec<Rect>,
+}
+
+impl FlexState {
+    /// Create empty FlexState, which places the first frame approximately
+    pub fn new() -> Self {
+        Self {
+            flex: None,
+            rect: Rect::zero(),
+            items: Vec::new(),
+            rects: Vec::new(),
+        }
+    }
+
+    /// Start adding the items of this frame to a `flex` container filling `rect`
+    pub fn begin(&mut self, flex: Flex, rect: Rect) -> FlexFrame<'_> {
+        let capacity = self.items.len();
+
+        FlexFrame {
+            state: self,
+            flex,
+            rect,
+            items: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Item rectangles of the last arrangement
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+}
+
+impl Default for FlexState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Items of a flex container added during one frame, see [`FlexState::begin`]
+#[derive(Debug)]
+pub struct FlexFrame<'a> {
+    state: &'a mut FlexState,
+    flex: Flex,
+    rect: Rect,
+    items: Vec<FlexItem>,
+}
+
+impl FlexFrame<'_> {
+    /// Add the next item
+    ///
+    /// # Returns
+    ///
+    /// Rectangle of the item in the previous frame's arrangement. Items new in this frame are
+    /// placed at the content start with their measured size.
+    pub fn allocate(&mut self, item: FlexItem) -> Rect {
+        let index = self.items.len();
+        self.items.push(item);
+
+        self.state.rects.get(index).copied().unwrap_or_else(|| {
+            let content = deflate(self.rect, self.flex.padding);
+            Rect {
+                pos: content.pos,
+                size: item.size,
+            }
+        })
+    }
+
+    /// Arrange this frame's items for the next frame
+    ///
+    /// # Returns
+    ///
+    /// `true` if the arrangement changed, so this frame was placed with stale rectangles and
+    /// should be redrawn
+    pub fn end(self) -> bool {
+        let state = self.state;
+        if state.flex == Some(self.flex) && state.rect == self.rect && state.items == self.items {
+            return false;
+        }
+
+        let rects = self.flex.arrange(self.rect, &self.items);
+        let changed = rects != state.rects;
+
+        state.flex = Some(self.flex);
+        state.rect = self.rect;
+        state.items = self.items;
+        state.rects = rects;
+
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const ROW: Rect = Rect::new(0.0, 0.0, 100.0, 50.0);
+
+    fn item(width: f32, height: f32) -> FlexItem {
+        FlexItem::new(Size::new(width, height))
+    }
+
+    fn widths(rects: &[Rect]) -> Vec<f32> {
+        rects.iter().map(|rect| rect.size.width).collect()
+    }
+
+    fn xs(rects: &[Rect]) -> Vec<f32> {
+        rects.iter().map(|rect| rect.pos.x).collect()
+    }
+
+    mod grow_shrink {
+        use super::*;
+
+        #[test]
+        fn test_grow_by_factor() {
+            let rects = Flex::row().arrange(
+                Rect::new(0.0, 0.0, 300.0, 10.0),
+                &[
+                    item(50.0, 10.0).with_basis(0.0).with_grow(1.0),
+                    item(50.0, 10.0).with_basis(0.0).with_grow(2.0),
+                ],
+            );
+
+            assert_eq!(widths(&rects), [100.0, 200.0]);
+            assert_eq!(xs(&rects), [0.0, 100.0]);
+        }
+
+        #[test]
+        fn test_grow_freezes_at_max() {
+            let rects = Flex::row().with_gap(10.0).arrange(
+                ROW,
+                &[
+                    item(0.0, 10.0)
+                        .with_grow(1.0)
+                        .with_max_size(Size::new(20.0, 100.0)),
+                    item(0.0, 10.0).with_grow(1.0),
+                    item(10.0, 10.0),
+                ],
+            );
+
+            assert_eq!(widths(&rects), [20.0, 50.0, 10.0]);
+            assert_eq!(xs(&rects), [0.0, 30.0, 90.0]);
+        }
+
+        #[test]
+        fn test_shrink_weighted_by_basis() {
+            let rects = Flex::row().arrange(ROW, &[item(100.0, 10.0), item(50.0, 10.0)]);
+
+            assert_relative_eq!(rects[0].size.width, 200.0 / 3.0, epsilon = 1e-4);
+            assert_relative_eq!(rects[1].size.width, 100.0 / 3.0, epsilon = 1e-4);
+        }
+
+        #[test]
+        fn test_shrink_freezes_at_min() {
+            let rects = Flex::row().arrange(
+                ROW,
+                &[
+                    item(100.0, 10.0).with_min_size(Size::new(90.0, 0.0)),
+                    item(50.0, 10.0),
+                ],
+            );
+            assert_eq!(widths(&rects), [90.0, 10.0]);
+
+            // Items which can't shrink overflow
+            let rects = Flex::row().arrange(
+                ROW,
+                &[
+                    item(80.0, 10.0).with_shrink(0.0),
+                    item(80.0, 10.0).with_shrink(0.0),
+                ],
+            );
+            assert_eq!(widths(&rects), [80.0, 80.0]);
+            assert_eq!(xs(&rects), [0.0, 80.0]);
+        }
+
+        #[test]
+        fn test_min_beats_basis() {
+            let rects =
+                Flex::row().arrange(ROW, &[item(10.0, 10.0).with_min_size(Size::new(30.0, 0.0))]);
+            assert_eq!(widths(&rects), [30.0]);
+        }
+
+        #[test]
+        fn test_invalid_factors() {
+            let rects = Flex::row().arrange(
+                ROW,
+                &[
+                    item(f32::NAN, 10.0).with_grow(f32::NAN),
+                    item(10.0, 10.0).with_grow(-1.0),
+                ],
+            );
+            assert_eq!(widths(&rects), [0.0, 10.0]);
+            assert!(Flex::row().arrange(ROW, &[]).is_empty());
+        }
+
+        #[test]
+        fn test_infinite_basis_terminates() {
+            let rects = Flex::row().arrange(
+                Rect::new(0.0, 0.0, 100.0, 20.0),
+                &[item(f32::INFINITY, 10.0), item(10.0, 10.0)],
+            );
+
+            assert_eq!(rects.len(), 2);
+            assert_eq!(rects[1].size.width, 10.0);
+        }
+    }
+
+    mod placement {
+        use super::*;
+
+        #[test]
+        fn test_justify() {
+            let items = [item(20.0, 10.0), item(20.0, 10.0)];
+            let justified = |justify| xs(&Flex::row().with_justify(justify).arrange(ROW, &items));
+
+            assert_eq!(justified(Justify::Start), [0.0, 20.0]);
+            assert_eq!(justified(Justify::End), [60.0, 80.0]);
+            assert_eq!(justified(Justify::Center), [30.0, 50.0]);
+            assert_eq!(justified(Justify::SpaceBetween), [0.0, 80.0]);
+            assert_eq!(justified(Justify::SpaceAround), [15.0, 65.0]);
+            assert_eq!(justified(Justify::SpaceEvenly), [20.0, 60.0]);
+
+            // A single item can't be spread
+            let single = Flex::row().with_justify(Justify::SpaceBetween);
+            assert_eq!(xs(&single.arrange(ROW, &items[..1])), [0.0]);
+        }
+
+        #[test]
+        fn test_justify_overflow() {
+            let items = [item(80.0, 10.0).with_shrink(0.0); 2];
+            let justified = |justify| xs(&Flex::row().with_justify(justify).arrange(ROW, &items));
+
+            assert_eq!(justified(Justify::Center), [-30.0, 50.0]);
+            assert_eq!(justified(Justify::SpaceEvenly), [0.0, 80.0]);
+        }
+
+        #[test]
+        fn test_align_items() {
+            let items = [
+                item(10.0, 10.0),
+                item(10.0, 10.0).with_max_size(Size::new(100.0, 30.0)),
+                item(10.0, 10.0).with_align_self(Align::End),
+            ];
+            let aligned = |align| Flex::row().with_align_items(align).arrange(ROW, &items);
+
+            let stretched = aligned(Align::Stretch);
+            assert_eq!(stretched[0], Rect::new(0.0, 0.0, 10.0, 50.0));
+            assert_eq!(stretched[1], Rect::new(10.0, 0.0, 10.0, 30.0));
+            assert_eq!(stretched[2], Rect::new(20.0, 40.0, 10.0, 10.0));
+
+            let centered = aligned(Align::Center);
+            assert_eq!(centered[0].pos.y, 20.0);
+            assert_eq!(centered[0].size.height, 10.0);
+        }
+
+        #[test]
+        fn test_column_with_padding() {
+            let rects = Flex::column()
+                .with_padding(Padding::all(5.0))
+                .with_justify(Justify::End)
+                .arrange(
+                    Rect::new(10.0, 10.0, 40.0, 100.0),
+                    &[
+                        item(10.0, 20.0),
+                        item(10.0, 0.0).with_grow(1.0).with_basis(30.0),
+                    ],
+                );
+
+            assert_eq!(rects[0], Rect::new(15.0, 15.0, 30.0, 20.0));
+            assert_eq!(rects[1], Rect::new(15.0, 35.0, 30.0, 70.0));
+        }
+    }
+
+    mod wrap {
+        use super::*;
+
+        #[test]
+        fn test_lines() {
+            let flex = Flex::row().with_wrap(true).with_gap(10.0);
+            let rects = flex.arrange(ROW, &[item(40.0, 20.0), item(40.0, 30.0), item(40.0, 10.0)]);
+
+            // Lines are as thick as their thickest item
+            assert_eq!(rects[0], Rect::new(0.0, 0.0, 40.0, 30.0));
+            assert_eq!(rects[1], Rect::new(50.0, 0.0, 40.0, 30.0));
+            assert_eq!(rects[2], Rect::new(0.0, 40.0, 40.0, 10.0));
+        }
+
+        #[test]
+        fn test_lines_grow_separately() {
+            let flex = Flex::row()
+                .with_wrap(true)
+                .with_gap(10.0)
+                .with_align_items(Align::Start);
+            let rects = flex.arrange(ROW, &[item(40.0, 10.0).with_grow(1.0); 3]);
+
+            assert_eq!(widths(&rects), [45.0, 45.0, 100.0]);
+            assert_eq!(xs(&rects), [0.0, 55.0, 0.0]);
+            assert_eq!(rects[2].pos.y, 20.0);
+        }
+
+        #[test]
+        fn test_oversized_item_gets_own_line() {
+            let flex = Flex::row().with_wrap(true);
+            let rects = flex.arrange(
+                ROW,
+                &[item(30.0, 10.0), item(150.0, 10.0), item(30.0, 10.0)],
+            );
+
+            assert_eq!(
+                rects.iter().map(|rect| rect.pos.y).collect::<Vec<_>>(),
+                [0.0, 10.0, 20.0]
+            );
+            // Shrinks to fit its line
+            assert_eq!(rects[1].size.width, 100.0);
+        }
+    }
+
+    mod frame {
+        use super::*;
+
+        fn run(state: &mut FlexState, rect: Rect, items: &[FlexItem]) -> (Vec<Rect>, bool) {
+            let mut flex = state.begin(Flex::row().with_padding(Padding::all(1.0)), rect);
+            let rects = items.iter().map(|&item| flex.allocate(item)).collect();
+
+            (rects, flex.end())
+        }
+
+        #[test]
+        fn test_settles_after_one_frame() {
+            let mut state = FlexState::default();
+            let items = [item(10.0, 10.0), item(10.0, 10.0).with_grow(1.0)];
+
+            let (guess, changed) = run(&mut state, ROW, &items);
+            assert!(changed);
+            assert_eq!(guess[1], Rect::new(1.0, 1.0, 10.0, 10.0));
+
+            let (rects, changed) = run(&mut state, ROW, &items);
+            assert!(!changed);
+            assert_eq!(rects[1], Rect::new(11.0, 1.0, 88.0, 48.0));
+            assert_eq!(state.rects(), rects);
+        }
+
+        #[test]
+        fn test_changes_trigger_rearrange() {
+            let mut state = FlexState::new();
+            let items = [item(10.0, 10.0), item(10.0, 10.0).with_grow(1.0)];
+            run(&mut state, ROW, &items);
+
+            // A measured size changed
+            let (_, changed) = run(&mut state, ROW, &[item(20.0, 10.0), items[1]]);
+            assert!(changed);
+            assert_eq!(state.rects()[1].pos.x, 21.0);
+
+            // The container was resized
+            let (_, changed) = run(&mut state, Rect::new(0.0, 0.0, 200.0, 50.0), &items);
+            assert!(changed);
+
+            // Different inputs with the same result don't need a redraw
+            let (_, changed) = run(
+                &mut state,
+                Rect::new(0.0, 0.0, 200.0, 50.0),
+                &[items[0], items[1].with_grow(2.0)],
+            );
+            assert!(!changed);
+
+            // Removed items are dropped from the cache
+            run(&mut state, ROW, &items[..1]);
+            assert_eq!(state.rects().len(), 1);
+        }
+    }
+}
@@ -0,0 +1,748 @@
+//! Grid layout: cells placed on rows and columns of fixed, fractional or auto-sized tracks
+//!
+//! Auto tracks are as large as their largest content. [`Grid::arrange`] measures a known list of
+//! cells, while [`GridState`] remembers the largest content seen in each auto track across frames,
+//! so immediate-mode widgets keep their columns lined up.
+
+use super::{Align, deflate, to_valid};
+use crate::math::{Rect, Size};
+use crate::style::Padding;
+
+/// Number of rows or columns cells can cover
+const MAX_TRACKS: usize = 1 << 16;
+
+/// Size of a grid row or column
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Track {
+    /// Fixed length in pixels
+    Fixed(f32),
+
+    /// Share of the space left by the other tracks, like the CSS `fr` unit
+    Fraction(f32),
+
+    /// Size of the largest content in the track
+    Auto,
+}
+
+/// Grid container settings
+///
+/// # Notes
+///
+/// - Rows and columns beyond the declared tracks are `Track::Auto`, so rows can be added
+///   without declaring them
+/// - Cells are stretched over their area by default, like CSS grid items
+/// - Cells are clipped to the first 65536 rows and columns, so huge indices or spans can't
+///   overflow or allocate without bounds
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::Align;
+/// use ho_gui::layout::grid::{Grid, GridCell, Track};
+/// use ho_gui::math::{Rect, Size};
+///
+/// // Property inspector: labels sized to the longest one, editors take the rest
+/// let grid = Grid::new(vec![Track::Auto, Track::Fraction(1.0)])
+///     .with_gap(8.0, 4.0)
+///     .with_align(Align::Stretch, Align::Center);
+///
+/// let rects = grid.arrange(
+///     Rect::new(0.0, 0.0, 300.0, 200.0),
+///     &[
+///         GridCell::new(0, 0, Size::new(40.0, 14.0)),
+///         GridCell::new(1, 0, Size::new(100.0, 24.0)),
+///         GridCell::new(0, 1, Size::new(72.0, 14.0)),
+///         GridCell::new(1, 1, Size::new(100.0, 24.0)),
+///     ],
+/// );
+///
+/// assert_eq!(rects[0], Rect::new(0.0, 5.0, 72.0, 14.0));
+/// assert_eq!(rects[1], Rect::new(80.0, 0.0, 220.0, 24.0));
+/// assert_eq!(rects[3].pos.y, 28.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    /// Declared column tracks, left to right
+    pub columns: Vec<Track>,
+
+    /// Declared row tracks, top to bottom
+    pub rows: Vec<Track>,
+
+    /// Space between columns
+    pub column_gap: f32,
+
+    /// Space between rows
+    pub row_gap: f32,
+
+    /// Space kept free inside of the container
+    pub padding: Padding,
+
+    /// Default horizontal alignment of cells in their area
+    pub align_x: Align,
+
+    /// Default vertical alignment of cells in their area
+    pub align_y: Align,
+}
+
+impl Grid {
+    /// Create Grid with `columns` and auto-sized rows
+    pub fn new(columns: Vec<Track>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+            column_gap: 0.0,
+            row_gap: 0.0,
+            padding: Padding::zero(),
+            align_x: Align::Stretch,
+            align_y: Align::Stretch,
+        }
+    }
+
+    /// Return the Grid with declared `rows`
+    pub fn with_rows(mut self, rows: Vec<Track>) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Return the Grid with `column` gaps between columns and `row` gaps between rows
+    pub fn with_gap(mut self, column: f32, row: f32) -> Self {
+        self.column_gap = to_valid(column);
+        self.row_gap = to_valid(row);
+        self
+    }
+
+    /// Return the Grid keeping `padding` free inside of the container
+    pub fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Return the Grid aligning cells in their area by `x` horizontally and `y` vertically
+    pub fn with_align(mut self, x: Align, y: Align) -> Self {
+        self.align_x = x;
+        self.align_y = y;
+        self
+    }
+
+    /// Place `cells` inside of `rect`, sizing auto tracks to these cells only
+    ///
+    /// # Returns
+    ///
+    /// Rectangle of each cell, in the order of `cells`
+    pub fn arrange(&self, rect: Rect, cells: &[GridCell]) -> Vec<Rect> {
+        let mut auto = AutoSizes::default();
+        auto.measure(self, cells);
+
+        let tracks = TrackLayout::new(self, rect, &auto);
+        cells.iter().map(|cell| tracks.place(cell)).collect()
+    }
+
+    fn column(&self, index: usize) -> Track {
+        self.columns.get(index).copied().unwrap_or(Track::Auto)
+    }
+
+    fn row(&self, index: usize) -> Track {
+        self.rows.get(index).copied().unwrap_or(Track::Auto)
+    }
+}
+
+/// Cell of a [`Grid`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridCell {
+    /// First column, starting at 0
+    pub column: usize,
+
+    /// First row, starting at 0
+    pub row: usize,
+
+    /// Number of columns covered, at least 1
+    pub column_span: usize,
+
+    /// Number of rows covered, at least 1
+    pub row_span: usize,
+
+    /// Measured content size
+    pub size: Size,
+
+    /// Horizontal alignment overriding `Grid::align_x`
+    pub align_x: Option<Align>,
+
+    /// Vertical alignment overriding `Grid::align_y`
+    pub align_y: Option<Align>,
+}
+
+impl GridCell {
+    /// Create GridCell covering one track at `column` and `row`, with measured `size`
+    pub const fn new(column: usize, row: usize, size: Size) -> Self {
+        Self {
+            column,
+            row,
+            column_span: 1,
+            row_span: 1,
+            size,
+            align_x: None,
+            align_y: None,
+        }
+    }
+
+    /// Return the cell covering `columns` columns and `rows` rows
+    pub const fn with_span(mut self, columns: usize, rows: usize) -> Self {
+        self.column_span = columns;
+        self.row_span = rows;
+        self
+    }
+
+    /// Return the cell aligned by `x` horizontally and `y` vertically
+    pub const fn with_align(mut self, x: Align, y: Align) -> Self {
+        self.align_x = Some(x);
+        self.align_y = Some(y);
+        self
+    }
+
+    fn columns(&self) -> (usize, usize) {
+        clip_tracks(self.column, self.column_span)
+    }
+
+    fn rows(&self) -> (usize, usize) {
+        clip_tracks(self.row, self.row_span)
+    }
+}
+
+/// `(first, span)` of a cell clipped to the first [`MAX_TRACKS`] tracks, spanning at least one
+fn clip_tracks(first: usize, span: usize) -> (usize, usize) {
+    let first = first.min(MAX_TRACKS - 1);
+    (first, span.clamp(1, MAX_TRACKS - first))
+}
+
+/// Per-container cache of auto track sizes, kept by the widget between frames
+///
+/// # Notes
+///
+/// - Auto tracks only grow, so content that changes from frame to frame doesn't make columns
+///   jitter. Call [`GridState::forget_auto_sizes`] when the content is replaced.
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::grid::{Grid, GridCell, GridState, Track};
+/// use ho_gui::math::{Rect, Size};
+///
+/// let grid = Grid::new(vec![Track::Auto, Track::Fraction(1.0)]);
+/// let rect = Rect::new(0.0, 0.0, 200.0, 100.0);
+/// let mut state = GridState::new();
+///
+/// let mut frame = |state: &mut GridState, label_width: f32| {
+///     let mut cells = state.begin(&grid, rect);
+///     cells.allocate(GridCell::new(0, 0, Size::new(label_width, 20.0)));
+///     let editor = cells.allocate(GridCell::new(1, 0, Size::new(50.0, 20.0)));
+///     (editor, cells.end())
+/// };
+///
+/// // The first frame measures the label column, and asks for another frame
+/// assert!(frame(&mut state, 60.0).1);
+/// assert_eq!(frame(&mut state, 60.0), (Rect::new(60.0, 0.0, 140.0, 20.0), false));
+///
+/// // A shorter label keeps the widest one seen
+/// assert_eq!(frame(&mut state, 30.0).0.pos.x, 60.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GridState {
+    auto: AutoSizes,
+}
+
+impl GridState {
+    /// Create GridState without any measured content
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start adding the cells of this frame to `grid` filling `rect`
+    pub fn begin<'a>(&'a mut self, grid: &'a Grid, rect: Rect) -> GridFrame<'a> {
+        GridFrame {
+            tracks: TrackLayout::new(grid, rect, &self.auto),
+            state: self,
+            grid,
+            cells: Vec::new(),
+        }
+    }
+
+    /// Largest content width seen in each column, 0.0 for non-auto columns
+    pub fn auto_columns(&self) -> &[f32] {
+        &self.auto.columns
+    }
+
+    /// Largest content height seen in each row, 0.0 for non-auto rows
+    pub fn auto_rows(&self) -> &[f32] {
+        &self.auto.rows
+    }
+
+    /// Shrink auto tracks back to the content of the next frame
+    pub fn forget_auto_sizes(&mut self) {
+        self.auto = AutoSizes::default();
+    }
+}
+
+/// Cells of a grid added during one frame, see [`GridState::begin`]
+#[derive(Debug)]
+pub struct GridFrame<'a> {
+    state: &'a mut GridState,
+    grid: &'a Grid,
+    tracks: TrackLayout,
+    cells: Vec<GridCell>,
+}
+
+impl GridFrame<'_> {
+    /// Add the next cell
+    ///
+    /// # Returns
+    ///
+    /// Rectangle of the cell, with auto tracks sized by the content of previous frames
+    pub fn allocate(&mut self, cell: GridCell) -> Rect {
+        self.cells.push(cell);
+        self.tracks.place(&cell)
+    }
+
+    /// Remember the content sizes of this frame's cells
+    ///
+    /// # Returns
+    ///
+    /// `true` if an auto track grew, so this frame was placed with stale tracks and should be
+    /// redrawn
+    pub fn end(self) -> bool {
+        let mut auto = self.state.auto.clone();
+        auto.measure(self.grid, &self.cells);
+
+        let changed = auto != self.state.auto;
+        self.state.auto = auto;
+
+        changed
+    }
+}
+
+/// Content sizes of auto tracks, indexed by track
+#[derive(Debug, Clone, PartialEq, Default)]
+struct AutoSizes {
+    columns: Vec<f32>,
+    rows: Vec<f32>,
+}
+
+impl AutoSizes {
+    /// Grow auto tracks to fit `cells`
+    ///
+    /// Cells spanning several tracks are measured after single-track cells, and spread what
+    /// doesn't fit yet evenly over their auto tracks. Cells spanning a fractional track fit by
+    /// growing it instead, so they are skipped.
+    fn measure(&mut self, grid: &Grid, cells: &[GridCell]) {
+        let mut cells: Vec<&GridCell> = cells.iter().collect();
+        cells.sort_by_key(|cell| (cell.columns().1, cell.rows().1));
+
+        for cell in cells {
+            grow_span(
+                &mut self.columns,
+                cell.columns(),
+                to_valid(cell.size.width),
+                grid.column_gap,
+                |index| grid.column(index),
+            );
+            grow_span(
+                &mut self.rows,
+                cell.rows(),
+                to_valid(cell.size.height),
+                grid.row_gap,
+                |index| grid.row(index),
+            );
+        }
+    }
+}
+
+fn grow_span(
+    sizes: &mut Vec<f32>,
+    (first, span): (usize, usize),
+    needed: f32,
+    gap: f32,
+    track: impl Fn(usize) -> Track,
+) {
+    let range = first..first + span;
+    if sizes.len() < range.end {
+        sizes.resize(range.end, 0.0);
+    }
+
+    let mut current = gap * (span - 1) as f32;
+    let mut auto_tracks = 0;
+    for index in range.clone() {
+        match track(index) {
+            Track::Fixed(length) => current += to_valid(length),
+            Track::Fraction(_) => return,
+            Track::Auto => {
+                current += sizes[index];
+                auto_tracks += 1;
+            }
+        }
+    }
+
+    let extra = needed - current;
+    if auto_tracks == 0 || extra <= 0.0 {
+        return;
+    }
+
+    for index in range {
+        if track(index) == Track::Auto {
+            sizes[index] += extra / auto_tracks as f32;
+        }
+    }
+}
+
+/// Resolved track positions of a grid in its rectangle
+#[derive(Debug, Clone, PartialEq)]
+struct TrackLayout {
+    // (start, extent) of each track
+    columns: Vec<(f32, f32)>,
+    rows: Vec<(f32, f32)>,
+    column_gap: f32,
+    row_gap: f32,
+    align_x: Align,
+    align_y: Align,
+}
+
+impl TrackLayout {
+    fn new(grid: &Grid, rect: Rect, auto: &AutoSizes) -> Self {
+        let content = deflate(rect, grid.padding);
+
+        Self {
+            columns: resolve_tracks(
+                grid.columns.len().max(auto.columns.len()),
+                |index| grid.column(index),
+                &auto.columns,
+                content.left(),
+                content.size.width,
+                grid.column_gap,
+            ),
+            rows: resolve_tracks(
+                grid.rows.len().max(auto.rows.len()),
+                |index| grid.row(index),
+                &auto.rows,
+                content.top(),
+                content.size.height,
+                grid.row_gap,
+            ),
+            column_gap: grid.column_gap,
+            row_gap: grid.row_gap,
+            align_x: grid.align_x,
+            align_y: grid.align_y,
+        }
+    }
+
+    fn place(&self, cell: &GridCell) -> Rect {
+        let (x, width) = span(&self.columns, cell.columns(), self.column_gap);
+        let (y, height) = span(&self.rows, cell.rows(), self.row_gap);
+
+        let align_x = cell.align_x.unwrap_or(self.align_x);
+        let align_y = cell.align_y.unwrap_or(self.align_y);
+        let (x, width) = align_x.place(x, width, to_valid(cell.size.width));
+        let (y, height) = align_y.place(y, height, to_valid(cell.size.height));
+
+        Rect::new(x, y, width, height)
+    }
+}
+
+/// `(start, extent)` of `count` tracks, with fractions sharing the space left by the others
+fn resolve_tracks(
+    count: usize,
+    track: impl Fn(usize) -> Track,
+    auto: &[f32],
+    start: f32,
+    available: f32,
+    gap: f32,
+) -> Vec<(f32, f32)> {
+    let base = |index: usize| match track(index) {
+        Track::Fixed(length) => to_valid(length),
+        Track::Fraction(_) => 0.0,
+        Track::Auto => auto.get(index).copied().unwrap_or(0.0),
+    };
+    let weight = |index: usize| match track(index) {
+        Track::Fraction(weight) => to_valid(weight),
+        _ => 0.0,
+    };
+
+    let gaps = gap * count.saturating_sub(1) as f32;
+    let used: f32 = (0..count).map(base).sum();
+    let total_weight: f32 = (0..count).map(weight).sum();
+    let left = (available - gaps - used).max(0.0);
+
+    let mut position = start;
+    (0..count)
+        .map(|index| {
+            let extent = if total_weight > 0.0 {
+                base(index) + left * weight(index) / total_weight
+            } else {
+                base(index)
+            };
+            let track = (position, extent);
+            position += extent + gap;
+            track
+        })
+        .collect()
+}
+
+/// `(start, extent)` of the area covered by `span` tracks from `first`
+///
+/// Tracks unknown to the layout, e.g. rows first added this frame, are empty and placed after the
+/// last known track.
+fn span(tracks: &[(f32, f32)], (first, span): (usize, usize), gap: f32) -> (f32, f32) {
+    let track = |index: usize| {
+        tracks
+            .get(index)
+            .copied()
+            .unwrap_or_else(|| match tracks.last() {
+                Some(&(start, extent)) => (start + extent + gap, 0.0),
+                None => (0.0, 0.0),
+            })
+    };
+
+    let (start, _) = track(first);
+    let (last_start, last_extent) = track(first + span - 1);
+
+    (start, (last_start + last_extent - start).max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: Rect = Rect::new(0.0, 0.0, 200.0, 100.0);
+
+    fn cell(column: usize, row: usize, width: f32, height: f32) -> GridCell {
+        GridCell::new(column, row, Size::new(width, height))
+    }
+
+    mod tracks {
+        use super::*;
+
+        #[test]
+        fn test_fixed_and_fractions_share_the_width() {
+            let grid = Grid::new(vec![
+                Track::Fixed(50.0),
+                Track::Fraction(1.0),
+                Track::Fraction(2.0),
+            ])
+            .with_rows(vec![Track::Fraction(1.0)]);
+            let rects = grid.arrange(
+                AREA,
+                &[
+                    cell(0, 0, 0.0, 0.0),
+                    cell(1, 0, 0.0, 0.0),
+                    cell(2, 0, 0.0, 0.0),
+                ],
+            );
+
+            assert_eq!(rects[0], Rect::new(0.0, 0.0, 50.0, 100.0));
+            assert_eq!(rects[1], Rect::new(50.0, 0.0, 50.0, 100.0));
+            assert_eq!(rects[2], Rect::new(100.0, 0.0, 100.0, 100.0));
+        }
+
+        #[test]
+        fn test_auto_tracks_fit_the_largest_content() {
+            let grid = Grid::new(vec![Track::Auto, Track::Auto]);
+            let rects = grid.arrange(
+                AREA,
+                &[
+                    cell(0, 0, 30.0, 10.0),
+                    cell(1, 0, 20.0, 16.0),
+                    cell(0, 1, 45.0, 12.0),
+                ],
+            );
+
+            assert_eq!(rects[0], Rect::new(0.0, 0.0, 45.0, 16.0));
+            assert_eq!(rects[1], Rect::new(45.0, 0.0, 20.0, 16.0));
+            assert_eq!(rects[2], Rect::new(0.0, 16.0, 45.0, 12.0));
+        }
+
+        #[test]
+        fn test_gaps_and_padding() {
+            let grid = Grid::new(vec![Track::Fraction(1.0), Track::Fraction(1.0)])
+                .with_gap(10.0, 5.0)
+                .with_padding(Padding::new(10.0, 10.0, 10.0, 10.0));
+            let rects = grid.arrange(AREA, &[cell(1, 1, 0.0, 20.0)]);
+
+            // Row 0 is an empty auto row, followed by a gap
+            assert_eq!(rects[0], Rect::new(105.0, 15.0, 85.0, 20.0));
+        }
+
+        #[test]
+        fn test_fractions_get_nothing_without_space() {
+            let grid = Grid::new(vec![
+                Track::Fixed(150.0),
+                Track::Fixed(80.0),
+                Track::Fraction(1.0),
+            ]);
+            let rects = grid.arrange(AREA, &[cell(2, 0, 10.0, 10.0)]);
+
+            assert_eq!(rects[0], Rect::new(230.0, 0.0, 0.0, 10.0));
+        }
+
+        #[test]
+        fn test_invalid_values_are_zero() {
+            let grid = Grid::new(vec![
+                Track::Fixed(f32::NAN),
+                Track::Fraction(-1.0),
+                Track::Auto,
+            ])
+            .with_gap(-5.0, f32::NAN);
+            let rects = grid.arrange(AREA, &[cell(2, 0, 10.0, f32::NAN)]);
+
+            assert_eq!(rects[0], Rect::new(0.0, 0.0, 10.0, 0.0));
+        }
+    }
+
+    mod cells {
+        use super::*;
+
+        #[test]
+        fn test_spans_cover_tracks_and_gaps() {
+            let grid = Grid::new(vec![Track::Fixed(40.0); 3]).with_gap(10.0, 0.0);
+            let rects = grid.arrange(AREA, &[cell(1, 0, 0.0, 20.0).with_span(2, 1)]);
+
+            assert_eq!(rects[0], Rect::new(50.0, 0.0, 90.0, 20.0));
+        }
+
+        #[test]
+        fn test_span_zero_is_one_track() {
+            let grid = Grid::new(vec![Track::Fixed(40.0); 2]);
+            let rects = grid.arrange(AREA, &[cell(1, 0, 0.0, 20.0).with_span(0, 0)]);
+
+            assert_eq!(rects[0], Rect::new(40.0, 0.0, 40.0, 20.0));
+        }
+
+        #[test]
+        fn test_huge_indices_and_spans_are_clipped() {
+            let grid = Grid::new(vec![Track::Fixed(40.0); 2]);
+            let cells = [
+                cell(1, 0, 0.0, 20.0).with_span(usize::MAX, 1),
+                cell(usize::MAX, usize::MAX, 10.0, 20.0).with_span(usize::MAX, usize::MAX),
+            ];
+            let rects = grid.arrange(AREA, &cells);
+
+            assert_eq!(rects[0].pos.x, 40.0);
+            assert!(rects[1].pos.x.is_finite() && rects[1].pos.y.is_finite());
+
+            let mut state = GridState::new();
+            let mut frame = state.begin(&grid, AREA);
+            frame.allocate(cells[1]);
+            assert!(frame.end());
+            assert_eq!(state.auto_columns().len(), MAX_TRACKS);
+        }
+
+        #[test]
+        fn test_spanning_cells_grow_their_auto_tracks_evenly() {
+            let grid = Grid::new(vec![Track::Auto, Track::Fixed(20.0), Track::Auto]);
+            let rects = grid.arrange(
+                AREA,
+                &[
+                    cell(0, 0, 100.0, 10.0).with_span(3, 1),
+                    cell(0, 1, 30.0, 10.0),
+                ],
+            );
+
+            // The single-column cell is measured first: 30 + 20 + 0, leaving 50 to share
+            assert_eq!(rects[0], Rect::new(0.0, 0.0, 100.0, 10.0));
+            assert_eq!(rects[1], Rect::new(0.0, 10.0, 55.0, 10.0));
+        }
+
+        #[test]
+        fn test_spanning_a_fraction_doesnt_grow_auto_tracks() {
+            let grid = Grid::new(vec![Track::Auto, Track::Fraction(1.0)]);
+            let rects = grid.arrange(
+                AREA,
+                &[
+                    cell(0, 0, 150.0, 10.0).with_span(2, 1),
+                    cell(0, 1, 30.0, 10.0),
+                ],
+            );
+
+            assert_eq!(rects[1].size.width, 30.0);
+        }
+
+        #[test]
+        fn test_cells_override_the_grid_alignment() {
+            let grid = Grid::new(vec![Track::Fixed(100.0)])
+                .with_rows(vec![Track::Fixed(40.0)])
+                .with_align(Align::Start, Align::Start);
+            let rects = grid.arrange(
+                AREA,
+                &[
+                    cell(0, 0, 20.0, 10.0),
+                    cell(0, 0, 20.0, 10.0).with_align(Align::End, Align::Center),
+                    cell(0, 0, 20.0, 10.0).with_align(Align::Stretch, Align::Stretch),
+                ],
+            );
+
+            assert_eq!(rects[0], Rect::new(0.0, 0.0, 20.0, 10.0));
+            assert_eq!(rects[1], Rect::new(80.0, 15.0, 20.0, 10.0));
+            assert_eq!(rects[2], Rect::new(0.0, 0.0, 100.0, 40.0));
+        }
+    }
+
+    mod frame {
+        use super::*;
+
+        fn run(state: &mut GridState, grid: &Grid, cells: &[GridCell]) -> (Vec<Rect>, bool) {
+            let mut frame = state.begin(grid, AREA);
+            let rects = cells.iter().map(|&cell| frame.allocate(cell)).collect();
+            (rects, frame.end())
+        }
+
+        #[test]
+        fn test_settles_after_one_frame() {
+            let grid = Grid::new(vec![Track::Auto, Track::Fraction(1.0)]);
+            let cells = [cell(0, 0, 60.0, 20.0), cell(1, 0, 10.0, 20.0)];
+            let mut state = GridState::new();
+
+            let (_, changed) = run(&mut state, &grid, &cells);
+            assert!(changed);
+
+            let (rects, changed) = run(&mut state, &grid, &cells);
+            assert!(!changed);
+            assert_eq!(rects, grid.arrange(AREA, &cells));
+        }
+
+        #[test]
+        fn test_remembers_the_widest_content() {
+            let grid = Grid::new(vec![Track::Auto, Track::Fraction(1.0)]);
+            let mut state = GridState::new();
+
+            run(&mut state, &grid, &[cell(0, 0, 60.0, 20.0)]);
+            let (rects, changed) = run(&mut state, &grid, &[cell(0, 0, 40.0, 10.0)]);
+
+            assert!(!changed);
+            assert_eq!(rects[0], Rect::new(0.0, 0.0, 60.0, 20.0));
+            assert_eq!(state.auto_columns(), &[60.0]);
+            assert_eq!(state.auto_rows(), &[20.0]);
+        }
+
+        #[test]
+        fn test_forgetting_shrinks_to_the_next_frame() {
+            let grid = Grid::new(vec![Track::Auto]);
+            let mut state = GridState::new();
+
+            run(&mut state, &grid, &[cell(0, 0, 60.0, 20.0)]);
+            state.forget_auto_sizes();
+            run(&mut state, &grid, &[cell(0, 0, 40.0, 10.0)]);
+
+            assert_eq!(state.auto_columns(), &[40.0]);
+        }
+
+        #[test]
+        fn test_new_rows_go_after_the_known_ones() {
+            let grid = Grid::new(vec![Track::Fixed(50.0)]).with_gap(0.0, 4.0);
+            let mut state = GridState::new();
+
+            run(&mut state, &grid, &[cell(0, 0, 10.0, 20.0)]);
+            let (rects, changed) = run(
+                &mut state,
+                &grid,
+                &[cell(0, 0, 10.0, 20.0), cell(0, 1, 10.0, 20.0)],
+            );
+
+            assert!(changed);
+            assert_eq!(rects[1], Rect::new(0.0, 24.0, 50.0, 0.0));
+        }
+    }
+}
@@ -0,0 +1,433 @@
+//! Absolute and anchored placement on z-ordered layers
+//!
+//! Popups, tooltips and HUDs don't take part in their parent's layout. A [`Placement`] puts them
+//! at an absolute point or at an [`Anchor`] of a parent rectangle, on a [`Layer`] above the
+//! regular widgets. [`LayerStack`] records the placed rectangles of a frame for hit testing, and
+//! [`LayeredDrawList`](crate::rendering::LayeredDrawList) draws the layers in the same order.
+
+use crate::math::{Point, Rect, Size, Vec2};
+
+/// Z-ordered layer, drawn and hit tested from `Background` up to `Debug`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum Layer {
+    /// Below regular widgets, e.g. window backgrounds and canvases
+    Background,
+
+    /// Regular widgets
+    #[default]
+    Main,
+
+    /// Floating panels and HUDs
+    Floating,
+
+    /// Menus, combo box lists and modal dialogs
+    Popup,
+
+    /// Tooltips, above popups
+    Tooltip,
+
+    /// Debug overlays, above everything
+    Debug,
+}
+
+impl Layer {
+    /// Every layer, bottom to top
+    pub const ALL: [Layer; 6] = [
+        Layer::Background,
+        Layer::Main,
+        Layer::Floating,
+        Layer::Popup,
+        Layer::Tooltip,
+        Layer::Debug,
+    ];
+
+    /// Position of the layer in [`Layer::ALL`]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Point of a rectangle: a corner, the middle of a side or the center
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Anchor {
+    /// Top left corner
+    #[default]
+    TopLeft,
+
+    /// Middle of the top side
+    Top,
+
+    /// Top right corner
+    TopRight,
+
+    /// Middle of the left side
+    Left,
+
+    /// Center
+    Center,
+
+    /// Middle of the right side
+    Right,
+
+    /// Bottom left corner
+    BottomLeft,
+
+    /// Middle of the bottom side
+    Bottom,
+
+    /// Bottom right corner
+    BottomRight,
+}
+
+impl Anchor {
+    /// Anchor point of `rect`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::layout::layer::Anchor;
+    /// use ho_gui::math::{Point, Rect};
+    ///
+    /// let rect = Rect::new(10.0, 10.0, 100.0, 50.0);
+    ///
+    /// assert_eq!(Anchor::Center.point(rect), Point::new(60.0, 35.0));
+    /// assert_eq!(Anchor::BottomRight.point(rect), Point::new(110.0, 60.0));
+    /// ```
+    pub fn point(self, rect: Rect) -> Point {
+        let (x, y) = self.factors();
+        Point::new(
+            rect.pos.x + rect.size.width * x,
+            rect.pos.y + rect.size.height * y,
+        )
+    }
+
+    /// Horizontal and vertical position of the anchor, 0.0 at the top left to 1.0 at the bottom
+    /// right
+    const fn factors(self) -> (f32, f32) {
+        match self {
+            Anchor::TopLeft => (0.0, 0.0),
+            Anchor::Top => (0.5, 0.0),
+            Anchor::TopRight => (1.0, 0.0),
+            Anchor::Left => (0.0, 0.5),
+            Anchor::Center => (0.5, 0.5),
+            Anchor::Right => (1.0, 0.5),
+            Anchor::BottomLeft => (0.0, 1.0),
+            Anchor::Bottom => (0.5, 1.0),
+            Anchor::BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
+/// Where a widget is placed outside of the regular layout
+///
+/// # Notes
+///
+/// - The pivot is the point of the widget put at the target, by default the same point as the
+///   parent anchor, so `Anchor::BottomRight` keeps the widget inside the parent's corner
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::layer::{Anchor, Layer, Placement};
+/// use ho_gui::math::{Rect, Size, Vec2};
+///
+/// let window = Rect::new(0.0, 0.0, 800.0, 600.0);
+/// let button = Rect::new(100.0, 100.0, 80.0, 24.0);
+///
+/// // HUD in the top right corner of the window, 8px from the edges
+/// let hud = Placement::anchored(Anchor::TopRight, Vec2::new(-8.0, 8.0))
+///     .with_layer(Layer::Floating);
+/// let hud_rect = hud.place(window, Size::new(100.0, 40.0));
+/// assert_eq!(hud_rect, Rect::new(692.0, 8.0, 100.0, 40.0));
+///
+/// // Menu hanging below a button
+/// let menu = Placement::anchored(Anchor::BottomLeft, Vec2::zero())
+///     .with_pivot(Anchor::TopLeft)
+///     .with_layer(Layer::Popup);
+/// let menu_rect = menu.place(button, Size::new(120.0, 200.0));
+/// assert_eq!(menu_rect, Rect::new(100.0, 124.0, 120.0, 200.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    /// Absolute point, or `None` to use the parent's anchor point
+    pub point: Option<Point>,
+
+    /// Point of the parent the widget is placed at, unless `point` is set
+    pub anchor: Anchor,
+
+    /// Point of the widget put at the target
+    pub pivot: Anchor,
+
+    /// Offset added to the target point
+    pub offset: Vec2,
+
+    /// Layer the widget is drawn and hit tested on
+    pub layer: Layer,
+}
+
+impl Placement {
+    /// Create Placement putting the widget's top left corner at `point` on `Layer::Main`
+    pub const fn absolute(point: Point) -> Self {
+        Self {
+            point: Some(point),
+            anchor: Anchor::TopLeft,
+            pivot: Anchor::TopLeft,
+            offset: Vec2::zero(),
+            layer: Layer::Main,
+        }
+    }
+
+    /// Create Placement putting the widget at the `anchor` point of its parent, moved by
+    /// `offset`, on `Layer::Main`
+    pub const fn anchored(anchor: Anchor, offset: Vec2) -> Self {
+        Self {
+            point: None,
+            anchor,
+            pivot: anchor,
+            offset,
+            layer: Layer::Main,
+        }
+    }
+
+    /// Return the Placement putting the `pivot` point of the widget at the target
+    pub const fn with_pivot(mut self, pivot: Anchor) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    /// Return the Placement on `layer`
+    pub const fn with_layer(mut self, layer: Layer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Rectangle of a widget of `size` placed relative to `parent`
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - Rectangle the anchor refers to, ignored for absolute placements
+    /// * `size` - Size of the widget, negative or NaN sizes are 0.0
+    pub fn place(&self, parent: Rect, size: Size) -> Rect {
+        let size = Size::new(super::to_valid(size.width), super::to_valid(size.height));
+        let target = self.point.unwrap_or_else(|| self.anchor.point(parent)) + self.offset;
+        let (x, y) = self.pivot.factors();
+
+        Rect::new(
+            target.x - size.width * x,
+            target.y - size.height * y,
+            size.width,
+            size.height,
+        )
+    }
+}
+
+/// Rectangles of one frame's widgets on their layers, for hit testing
+///
+/// # Notes
+///
+/// - Widgets on a higher layer are hit first; on the same layer, widgets added later are drawn
+///   on top and hit first
+/// - `T` identifies the widget, e.g. an id or an index
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::layer::{Anchor, Layer, LayerStack, Placement};
+/// use ho_gui::math::{Point, Rect, Size, Vec2};
+///
+/// let mut layers = LayerStack::new();
+/// let window = Rect::new(0.0, 0.0, 400.0, 300.0);
+///
+/// let popup = Placement::anchored(Anchor::Center, Vec2::zero()).with_layer(Layer::Popup);
+/// layers.place("popup", &popup, window, Size::new(100.0, 100.0));
+/// layers.insert("button", Layer::Main, Rect::new(160.0, 140.0, 80.0, 20.0));
+///
+/// // The popup covers the button although it was added first
+/// assert_eq!(layers.hit_test(Point::new(200.0, 150.0)), Some(&"popup"));
+/// assert_eq!(layers.hit_test(Point::new(10.0, 10.0)), None);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerStack<T> {
+    entries: Vec<(T, Layer, Rect)>,
+}
+
+impl<T> LayerStack<T> {
+    /// Create an empty LayerStack
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add the widget `id` covering `rect` on `layer`
+    pub fn insert(&mut self, id: T, layer: Layer, rect: Rect) {
+        self.entries.push((id, layer, rect));
+    }
+
+    /// Add the widget `id` of `size` placed by `placement` relative to `parent`
+    ///
+    /// # Returns
+    ///
+    /// Rectangle of the widget, see [`Placement::place`]
+    pub fn place(&mut self, id: T, placement: &Placement, parent: Rect, size: Size) -> Rect {
+        let rect = placement.place(parent, size);
+        self.insert(id, placement.layer, rect);
+        rect
+    }
+
+    /// Topmost widget containing `point`
+    pub fn hit_test(&self, point: Point) -> Option<&T> {
+        self.hits(point).next()
+    }
+
+    /// Every widget containing `point`, topmost first
+    pub fn hits(&self, point: Point) -> impl Iterator<Item = &T> {
+        Layer::ALL.into_iter().rev().flat_map(move |layer| {
+            self.entries
+                .iter()
+                .rev()
+                .filter(move |(_, entry_layer, rect)| {
+                    *entry_layer == layer && rect.contains_point(point)
+                })
+                .map(|(id, _, _)| id)
+        })
+    }
+
+    /// Number of added widgets
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no widget is added
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove every widget, keeping allocated memory for the next frame
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl<T> Default for LayerStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARENT: Rect = Rect::new(100.0, 100.0, 200.0, 100.0);
+    const SIZE: Size = Size::new(20.0, 10.0);
+
+    mod placement {
+        use super::*;
+
+        #[test]
+        fn test_anchors_keep_the_widget_inside() {
+            let place = |anchor| Placement::anchored(anchor, Vec2::zero()).place(PARENT, SIZE);
+
+            assert_eq!(place(Anchor::TopLeft), Rect::new(100.0, 100.0, 20.0, 10.0));
+            assert_eq!(place(Anchor::Top), Rect::new(190.0, 100.0, 20.0, 10.0));
+            assert_eq!(place(Anchor::Right), Rect::new(280.0, 145.0, 20.0, 10.0));
+            assert_eq!(place(Anchor::Center), Rect::new(190.0, 145.0, 20.0, 10.0));
+            assert_eq!(
+                place(Anchor::BottomLeft),
+                Rect::new(100.0, 190.0, 20.0, 10.0)
+            );
+            assert_eq!(
+                place(Anchor::BottomRight),
+                Rect::new(280.0, 190.0, 20.0, 10.0)
+            );
+        }
+
+        #[test]
+        fn test_pivot_and_offset() {
+            let tooltip = Placement::anchored(Anchor::Top, Vec2::new(0.0, -4.0))
+                .with_pivot(Anchor::Bottom)
+                .with_layer(Layer::Tooltip);
+
+            assert_eq!(
+                tooltip.place(PARENT, SIZE),
+                Rect::new(190.0, 86.0, 20.0, 10.0)
+            );
+            assert_eq!(tooltip.layer, Layer::Tooltip);
+        }
+
+        #[test]
+        fn test_absolute_ignores_the_parent() {
+            let placement = Placement::absolute(Point::new(5.0, 6.0));
+
+            assert_eq!(
+                placement.place(PARENT, SIZE),
+                Rect::new(5.0, 6.0, 20.0, 10.0)
+            );
+            assert_eq!(
+                placement.with_pivot(Anchor::Center).place(PARENT, SIZE),
+                Rect::new(-5.0, 1.0, 20.0, 10.0)
+            );
+            assert_eq!(placement.layer, Layer::Main);
+        }
+
+        #[test]
+        fn test_invalid_sizes_are_zero() {
+            let placement = Placement::anchored(Anchor::Center, Vec2::zero());
+
+            assert_eq!(
+                placement.place(PARENT, Size::new(f32::NAN, -4.0)),
+                Rect::new(200.0, 150.0, 0.0, 0.0)
+            );
+        }
+    }
+
+    mod hit_test {
+        use super::*;
+
+        #[test]
+        fn test_higher_layers_win() {
+            let mut layers = LayerStack::new();
+            layers.insert(1, Layer::Tooltip, PARENT);
+            layers.insert(2, Layer::Debug, PARENT);
+            layers.insert(3, Layer::Background, PARENT);
+            layers.insert(4, Layer::Main, PARENT);
+
+            let point = Point::new(150.0, 150.0);
+            assert_eq!(layers.hit_test(point), Some(&2));
+            assert_eq!(
+                layers.hits(point).copied().collect::<Vec<_>>(),
+                [2, 1, 4, 3]
+            );
+        }
+
+        #[test]
+        fn test_later_widgets_win_on_the_same_layer() {
+            let mut layers = LayerStack::new();
+            layers.insert("panel", Layer::Floating, PARENT);
+            layers.insert(
+                "button",
+                Layer::Floating,
+                Rect::new(110.0, 110.0, 20.0, 20.0),
+            );
+
+            assert_eq!(layers.hit_test(Point::new(120.0, 120.0)), Some(&"button"));
+            assert_eq!(layers.hit_test(Point::new(250.0, 120.0)), Some(&"panel"));
+            assert_eq!(layers.hit_test(Point::new(0.0, 0.0)), None);
+        }
+
+        #[test]
+        fn test_place_records_the_placed_rect() {
+            let mut layers = LayerStack::new();
+            let popup = Placement::anchored(Anchor::BottomLeft, Vec2::zero())
+                .with_pivot(Anchor::TopLeft)
+                .with_layer(Layer::Popup);
+
+            let rect = layers.place(7, &popup, PARENT, SIZE);
+
+            assert_eq!(rect, Rect::new(100.0, 200.0, 20.0, 10.0));
+            assert_eq!(layers.hit_test(Point::new(105.0, 205.0)), Some(&7));
+
+            layers.clear();
+            assert!(layers.is_empty());
+            assert_eq!(layers.len(), 0);
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Responsive layouts: named size classes of the available space
+//!
+//! The same tool runs in a narrow side panel and full screen. Layout code asks which
+//! [`SizeClass`] the available [`Size`] falls into and switches layouts or styles on it.
+//! [`Responsive`] remembers the previous class, so a width wobbling around a breakpoint while
+//! resizing doesn't flip the layout back and forth every frame.
+
+use super::to_valid;
+use crate::math::Size;
+
+/// Named range of available width or height
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum SizeClass {
+    /// Narrow, e.g. side panels and phones in portrait
+    #[default]
+    Compact,
+
+    /// Medium, e.g. tablets and split windows
+    Medium,
+
+    /// Wide, e.g. full-screen desktop windows
+    Expanded,
+}
+
+impl SizeClass {
+    /// Pick the value for this class
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::layout::responsive::SizeClass;
+    ///
+    /// let columns = SizeClass::Medium.select(1, 2, 3);
+    /// assert_eq!(columns, 2);
+    /// ```
+    pub fn select<T>(self, compact: T, medium: T, expanded: T) -> T {
+        match self {
+            SizeClass::Compact => compact,
+            SizeClass::Medium => medium,
+            SizeClass::Expanded => expanded,
+        }
+    }
+}
+
+/// Lengths where one size class ends and the next one starts
+///
+/// # Notes
+///
+/// - With hysteresis `h`, a length has to pass a breakpoint by `h` to change class, so lengths
+///   within `h` of a breakpoint keep the previous class
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Breakpoints {
+    /// Smallest length of `SizeClass::Medium`
+    pub medium: f32,
+
+    /// Smallest length of `SizeClass::Expanded`
+    pub expanded: f32,
+
+    /// Distance a length has to pass a breakpoint by to change class
+    pub hysteresis: f32,
+}
+
+impl Breakpoints {
+    /// Width breakpoints at 600 and 840 pixels, with 16 pixels of hysteresis
+    pub const WIDTH: Self = Self::new(600.0, 840.0).with_hysteresis(16.0);
+
+    /// Height breakpoints at 480 and 900 pixels, with 16 pixels of hysteresis
+    pub const HEIGHT: Self = Self::new(480.0, 900.0).with_hysteresis(16.0);
+
+    /// Create Breakpoints without hysteresis
+    ///
+    /// # Notes
+    ///
+    /// - `expanded` below `medium` is raised to `medium`, so there is no medium class
+    pub const fn new(medium: f32, expanded: f32) -> Self {
+        let medium = to_valid(medium);
+        Self {
+            medium,
+            expanded: to_valid(expanded).max(medium),
+            hysteresis: 0.0,
+        }
+    }
+
+    /// Return the Breakpoints with `hysteresis`, negative or NaN values are 0.0
+    pub const fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = to_valid(hysteresis);
+        self
+    }
+
+    /// Size class of `length`, ignoring hysteresis
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::layout::responsive::{Breakpoints, SizeClass};
+    ///
+    /// assert_eq!(Breakpoints::WIDTH.class(320.0), SizeClass::Compact);
+    /// assert_eq!(Breakpoints::WIDTH.class(600.0), SizeClass::Medium);
+    /// assert_eq!(Breakpoints::WIDTH.class(1920.0), SizeClass::Expanded);
+    /// ```
+    pub fn class(&self, length: f32) -> SizeClass {
+        if length >= self.expanded {
+            SizeClass::Expanded
+        } else if length >= self.medium {
+            SizeClass::Medium
+        } else {
+            SizeClass::Compact
+        }
+    }
+
+    /// Size class of `length` when it was `previous` before
+    ///
+    /// # Returns
+    ///
+    /// `previous`, unless `length` is past a breakpoint by more than the hysteresis. NaN keeps
+    /// `previous` too.
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::layout::responsive::{Breakpoints, SizeClass};
+    ///
+    /// let breakpoints = Breakpoints::new(600.0, 840.0).with_hysteresis(16.0);
+    ///
+    /// assert_eq!(breakpoints.class_from(SizeClass::Compact, 610.0), SizeClass::Compact);
+    /// assert_eq!(breakpoints.class_from(SizeClass::Compact, 616.0), SizeClass::Medium);
+    /// assert_eq!(breakpoints.class_from(SizeClass::Medium, 590.0), SizeClass::Medium);
+    /// assert_eq!(breakpoints.class_from(SizeClass::Medium, 583.0), SizeClass::Compact);
+    /// ```
+    pub fn class_from(&self, previous: SizeClass, length: f32) -> SizeClass {
+        let mut class = previous;
+
+        while let Some(next) = self.next(class)
+            && length >= self.start(next) + self.hysteresis
+        {
+            class = next;
+        }
+        while let Some(previous) = self.previous(class)
+            && length < self.start(class) - self.hysteresis
+        {
+            class = previous;
+        }
+
+        class
+    }
+
+    /// Smallest length of `class`
+    fn start(&self, class: SizeClass) -> f32 {
+        class.select(0.0, self.medium, self.expanded)
+    }
+
+    fn next(&self, class: SizeClass) -> Option<SizeClass> {
+        match class {
+            SizeClass::Compact => Some(SizeClass::Medium),
+            SizeClass::Medium => Some(SizeClass::Expanded),
+            SizeClass::Expanded => None,
+        }
+    }
+
+    fn previous(&self, class: SizeClass) -> Option<SizeClass> {
+        match class {
+            SizeClass::Compact => None,
+            SizeClass::Medium => Some(SizeClass::Compact),
+            SizeClass::Expanded => Some(SizeClass::Medium),
+        }
+    }
+}
+
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Self::WIDTH
+    }
+}
+
+/// Size classes of the available width and height
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SizeClasses {
+    /// Class of the available width
+    pub width: SizeClass,
+
+    /// Class of the available height
+    pub height: SizeClass,
+}
+
+/// Per-container size classes, kept by the widget between frames
+///
+/// # Examples
+/// ```
+/// use ho_gui::layout::responsive::{Responsive, SizeClass};
+/// use ho_gui::math::Size;
+///
+/// let mut responsive = Responsive::new();
+///
+/// // Side panel, then the user drags the window wider
+/// assert_eq!(responsive.update(Size::new(320.0, 800.0)).width, SizeClass::Compact);
+/// assert_eq!(responsive.update(Size::new(605.0, 800.0)).width, SizeClass::Compact);
+/// assert_eq!(responsive.update(Size::new(620.0, 800.0)).width, SizeClass::Medium);
+///
+/// // A stack of panels on compact widths, side by side otherwise
+/// let side_by_side = responsive.classes().width.select(false, true, true);
+/// assert!(side_by_side);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Responsive {
+    width: Breakpoints,
+    height: Breakpoints,
+    classes: Option<SizeClasses>,
+}
+
+impl Responsive {
+    /// Create Responsive with `Breakpoints::WIDTH` and `Breakpoints::HEIGHT`
+    pub const fn new() -> Self {
+        Self {
+            width: Breakpoints::WIDTH,
+            height: Breakpoints::HEIGHT,
+            classes: None,
+        }
+    }
+
+    /// Return the Responsive with `width` and `height` breakpoints
+    pub const fn with_breakpoints(mut self, width: Breakpoints, height: Breakpoints) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Classify the available `size` of this frame
+    ///
+    /// # Notes
+    ///
+    /// - The first size is classified without hysteresis, later ones relative to the classes of
+    ///   the previous frame
+    pub fn update(&mut self, size: Size) -> SizeClasses {
+        let classes = match self.classes {
+            Some(previous) => SizeClasses {
+                width: self.width.class_from(previous.width, size.width),
+                height: self.height.class_from(previous.height, size.height),
+            },
+            None => SizeClasses {
+                width: self.width.class(size.width),
+                height: self.height.class(size.height),
+            },
+        };
+        self.classes = Some(classes);
+
+        classes
+    }
+
+    /// Size classes of the last update, compact before the first one
+    pub fn classes(&self) -> SizeClasses {
+        self.classes.unwrap_or_default()
+    }
+}
+
+impl Default for Responsive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod breakpoints {
+        use super::*;
+
+        #[test]
+        fn test_class_without_hysteresis() {
+            let breakpoints = Breakpoints::new(100.0, 200.0);
+
+            assert_eq!(breakpoints.class(0.0), SizeClass::Compact);
+            assert_eq!(breakpoints.class(99.9), SizeClass::Compact);
+            assert_eq!(breakpoints.class(100.0), SizeClass::Medium);
+            assert_eq!(breakpoints.class(200.0), SizeClass::Expanded);
+            assert_eq!(breakpoints.class(f32::NAN), SizeClass::Compact);
+            assert_eq!(
+                breakpoints.class_from(SizeClass::Expanded, 150.0),
+                SizeClass::Medium
+            );
+        }
+
+        #[test]
+        fn test_hysteresis_band_keeps_the_previous_class() {
+            let breakpoints = Breakpoints::new(100.0, 200.0).with_hysteresis(10.0);
+
+            for length in [91.0, 100.0, 109.0] {
+                assert_eq!(
+                    breakpoints.class_from(SizeClass::Compact, length),
+                    SizeClass::Compact
+                );
+                assert_eq!(
+                    breakpoints.class_from(SizeClass::Medium, length),
+                    SizeClass::Medium
+                );
+            }
+            assert_eq!(
+                breakpoints.class_from(SizeClass::Compact, 110.0),
+                SizeClass::Medium
+            );
+            assert_eq!(
+                breakpoints.class_from(SizeClass::Medium, 89.0),
+                SizeClass::Compact
+            );
+        }
+
+        #[test]
+        fn test_jumps_over_several_classes() {
+            let breakpoints = Breakpoints::new(100.0, 200.0).with_hysteresis(10.0);
+
+            assert_eq!(
+                breakpoints.class_from(SizeClass::Compact, 500.0),
+                SizeClass::Expanded
+            );
+            assert_eq!(
+                breakpoints.class_from(SizeClass::Compact, 205.0),
+                SizeClass::Medium
+            );
+            assert_eq!(
+                breakpoints.class_from(SizeClass::Expanded, 0.0),
+                SizeClass::Compact
+            );
+            assert_eq!(
+                breakpoints.class_from(SizeClass::Expanded, 95.0),
+                SizeClass::Medium
+            );
+        }
+
+        #[test]
+        fn test_nan_keeps_the_previous_class() {
+            let breakpoints = Breakpoints::WIDTH;
+
+            assert_eq!(
+                breakpoints.class_from(SizeClass::Medium, f32::NAN),
+                SizeClass::Medium
+            );
+        }
+
+        #[test]
+        fn test_invalid_breakpoints_are_sanitized() {
+            let breakpoints = Breakpoints::new(f32::NAN, -5.0).with_hysteresis(-1.0);
+            assert_eq!(breakpoints, Breakpoints::new(0.0, 0.0));
+
+            let breakpoints = Breakpoints::new(300.0, 100.0);
+            assert_eq!(breakpoints.expanded, 300.0);
+            assert_eq!(breakpoints.class(300.0), SizeClass::Expanded);
+        }
+    }
+
+    mod responsive {
+        use super::*;
+
+        #[test]
+        fn test_resizing_around_a_breakpoint_doesnt_flicker() {
+            let mut responsive = Responsive::new();
+            responsive.update(Size::new(590.0, 500.0));
+
+            let classes: Vec<SizeClass> = [595.0, 605.0, 598.0, 612.0, 589.0]
+                .into_iter()
+                .map(|width| responsive.update(Size::new(width, 500.0)).width)
+                .collect();
+            assert!(classes.iter().all(|&class| class == SizeClass::Compact));
+
+            assert_eq!(
+                responsive.update(Size::new(640.0, 500.0)).width,
+                SizeClass::Medium
+            );
+            assert_eq!(
+                responsive.update(Size::new(590.0, 500.0)).width,
+                SizeClass::Medium
+            );
+            assert_eq!(
+                responsive.update(Size::new(580.0, 500.0)).width,
+                SizeClass::Compact
+            );
+        }
+
+        #[test]
+        fn test_first_update_has_no_hysteresis() {
+            let mut responsive = Responsive::new();
+            assert_eq!(responsive.classes(), SizeClasses::default());
+            assert_eq!(Responsive::default(), responsive);
+
+            let classes = responsive.update(Size::new(600.0, 900.0));
+
+            assert_eq!(
+                classes,
+                SizeClasses {
+                    width: SizeClass::Medium,
+                    height: SizeClass::Expanded,
+                }
+            );
+            assert_eq!(responsive.classes(), classes);
+        }
+
+        #[test]
+        fn test_custom_breakpoints() {
+            let narrow = Breakpoints::new(200.0, 400.0);
+            let mut responsive = Responsive::new().with_breakpoints(narrow, narrow);
+
+            let classes = responsive.update(Size::new(250.0, 450.0));
+
+            assert_eq!(classes.width, SizeClass::Medium);
+            assert_eq!(classes.height, SizeClass::Expanded);
+        }
+    }
+}
@@ -5,14 +5,22 @@
 
 #![warn(missing_docs)]
 
+pub mod animation;
 pub mod app;
 pub mod color;
 pub mod math;
+pub mod paint;
+pub mod palette;
+pub mod pixel;
 // pub mod widgets;
-// pub mod layout;
-// pub mod rendering;
+pub mod layout;
+pub mod rendering;
 // pub mod input;
 pub mod style;
+pub mod stylesheet;
+pub mod text;
+pub mod theme;
+pub mod ui;
 
 // Re-export commonly used types
 // pub use app::App;
@@ -243,6 +243,7 @@ impl Rect {
 pub type Vec2 = Point;
 
 #[cfg(test)]
+#[allow(clippy::clone_on_copy, clippy::bool_comparison)]
 mod math_tests {
     use super::*;
     use approx::assert_relative_eq;
@@ -0,0 +1,552 @@
+//! Paint for filling shapes with solid colors and gradients
+
+use std::f32::consts::TAU;
+
+use crate::color::Color;
+use crate::math::Point;
+
+/// Source of color used to fill rectangles, paths and borders
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    /// Single flat color
+    Solid(Color),
+
+    /// Gradient along a line
+    LinearGradient(LinearGradient),
+
+    /// Gradient around a center point outwards
+    RadialGradient(RadialGradient),
+
+    /// Gradient sweeping around a center point
+    ConicGradient(ConicGradient),
+}
+
+impl Paint {
+    /// Evaluate the paint at the given point
+    ///
+    /// # Notes
+    ///
+    /// - Gradient without any stop evaluates to `Color::TRANSPARENT`
+    /// - Stops are interpolated in non-premultiplied RGBA
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - Point to evaluate, in the same coordinate space as the gradient geometry
+    ///
+    /// # Returns
+    ///
+    /// Color of the paint at `point`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::math::Point;
+    /// use ho_gui::paint::{LinearGradient, Paint};
+    ///
+    /// let paint: Paint = LinearGradient::new(Point::new(0.0, 0.0), Point::new(10.0, 0.0))
+    ///     .with_stop(0.0, Color::BLACK)
+    ///     .with_stop(1.0, Color::WHITE)
+    ///     .into();
+    ///
+    /// assert_eq!(paint.color_at(Point::new(5.0, 3.0)), Color::rgb(0.5, 0.5, 0.5));
+    /// assert_eq!(paint.color_at(Point::new(20.0, 3.0)), Color::WHITE);
+    /// ```
+    pub fn color_at(&self, point: Point) -> Color {
+        match self {
+            Self::Solid(color) => *color,
+            Self::LinearGradient(gradient) => gradient.color_at(point),
+            Self::RadialGradient(gradient) => gradient.color_at(point),
+            Self::ConicGradient(gradient) => gradient.color_at(point),
+        }
+    }
+
+    /// Check if the paint is a single flat color
+    pub fn is_solid(&self) -> bool {
+        matches!(self, Self::Solid(_))
+    }
+}
+
+impl Default for Paint {
+    fn default() -> Self {
+        Self::Solid(Color::TRANSPARENT)
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<LinearGradient> for Paint {
+    fn from(gradient: LinearGradient) -> Self {
+        Self::LinearGradient(gradient)
+    }
+}
+
+impl From<RadialGradient> for Paint {
+    fn from(gradient: RadialGradient) -> Self {
+        Self::RadialGradient(gradient)
+    }
+}
+
+impl From<ConicGradient> for Paint {
+    fn from(gradient: ConicGradient) -> Self {
+        Self::ConicGradient(gradient)
+    }
+}
+
+/// Color at a position of a gradient
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientStop {
+    /// Position of the stop in range `(0.0..=1.0)`
+    pub offset: f32,
+
+    /// Color of the stop
+    pub color: Color,
+}
+
+impl GradientStop {
+    /// Create GradientStop with given offset and color
+    ///
+    /// # Notes
+    ///
+    /// - `offset` will be clamped to the range `(0.0..=1.0)`, and `f32::NAN` will be set to 0.0
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::paint::GradientStop;
+    ///
+    /// assert_eq!(GradientStop::new(0.5, Color::RED).offset, 0.5);
+    /// assert_eq!(GradientStop::new(2.0, Color::RED).offset, 1.0);
+    /// assert_eq!(GradientStop::new(f32::NAN, Color::RED).offset, 0.0);
+    /// ```
+    pub const fn new(offset: f32, color: Color) -> Self {
+        let offset = if offset.is_nan() || offset < 0.0 {
+            0.0
+        } else if offset > 1.0 {
+            1.0
+        } else {
+            offset
+        };
+
+        Self { offset, color }
+    }
+}
+
+/// How a gradient is extended outside of its `(0.0..=1.0)` range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SpreadMode {
+    /// Edge colors are extended
+    #[default]
+    Pad,
+
+    /// Gradient is repeated
+    Repeat,
+
+    /// Gradient is repeated, mirroring every other repetition
+    Reflect,
+}
+
+impl SpreadMode {
+    /// Map gradient parameter `t` into `(0.0..=1.0)` according to the spread mode
+    pub fn apply(&self, t: f32) -> f32 {
+        if !t.is_finite() {
+            return 0.0;
+        }
+
+        match self {
+            Self::Pad => t.clamp(0.0, 1.0),
+            Self::Repeat => t.rem_euclid(1.0),
+            Self::Reflect => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 { 2.0 - t } else { t }
+            }
+        }
+    }
+}
+
+/// Gradient along the line from `start` to `end`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearGradient {
+    /// Point where the gradient parameter is 0.0
+    pub start: Point,
+
+    /// Point where the gradient parameter is 1.0
+    pub end: Point,
+
+    /// Color stops sorted by offset
+    pub stops: Vec<GradientStop>,
+
+    /// How the gradient is extended beyond `start` and `end`
+    pub spread: SpreadMode,
+}
+
+impl LinearGradient {
+    /// Create LinearGradient from `start` to `end` without any stop and with `SpreadMode::Pad`
+    pub fn new(start: Point, end: Point) -> Self {
+        Self {
+            start,
+            end,
+            stops: Vec::new(),
+            spread: SpreadMode::Pad,
+        }
+    }
+
+    /// Add a color stop, keeping stops sorted by offset
+    pub fn with_stop(mut self, offset: f32, color: Color) -> Self {
+        insert_stop(&mut self.stops, GradientStop::new(offset, color));
+        self
+    }
+
+    /// Set the spread mode
+    pub fn with_spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Evaluate the gradient at the given point
+    pub fn color_at(&self, point: Point) -> Color {
+        let (dx, dy) = (self.end.x - self.start.x, self.end.y - self.start.y);
+        let length_sq = dx * dx + dy * dy;
+
+        // Degenerated line has no direction, so the whole plane is one color. Same as tiny-skia:
+        // padding ends at the last stop, repeating averages all stops.
+        if length_sq <= 0.0 {
+            return match self.spread {
+                SpreadMode::Pad => sample_stops(&self.stops, 1.0),
+                SpreadMode::Repeat | SpreadMode::Reflect => average_stops(&self.stops),
+            };
+        }
+
+        let t = ((point.x - self.start.x) * dx + (point.y - self.start.y) * dy) / length_sq;
+        sample_stops(&self.stops, self.spread.apply(t))
+    }
+}
+
+/// Circular gradient from `center` (0.0) to `radius` (1.0)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadialGradient {
+    /// Center of the gradient
+    pub center: Point,
+
+    /// Distance from the center where the gradient parameter is 1.0
+    pub radius: f32,
+
+    /// Color stops sorted by offset
+    pub stops: Vec<GradientStop>,
+
+    /// How the gradient is extended beyond `radius`
+    pub spread: SpreadMode,
+}
+
+impl RadialGradient {
+    /// Create RadialGradient without any stop and with `SpreadMode::Pad`
+    ///
+    /// # Notes
+    ///
+    /// - `radius` with `NaN` or negative value will be set to 0.0
+    pub fn new(center: Point, radius: f32) -> Self {
+        let radius = if radius.is_nan() || radius < 0.0 {
+            0.0
+        } else {
+            radius
+        };
+
+        Self {
+            center,
+            radius,
+            stops: Vec::new(),
+            spread: SpreadMode::Pad,
+        }
+    }
+
+    /// Add a color stop, keeping stops sorted by offset
+    pub fn with_stop(mut self, offset: f32, color: Color) -> Self {
+        insert_stop(&mut self.stops, GradientStop::new(offset, color));
+        self
+    }
+
+    /// Set the spread mode
+    pub fn with_spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Evaluate the gradient at the given point
+    pub fn color_at(&self, point: Point) -> Color {
+        let t = if self.radius > 0.0 {
+            self.center.distance_to(&point) / self.radius
+        } else {
+            1.0
+        };
+
+        sample_stops(&self.stops, self.spread.apply(t))
+    }
+}
+
+/// Gradient sweeping clockwise around `center`, starting at `start_angle`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConicGradient {
+    /// Center of the sweep
+    pub center: Point,
+
+    /// Angle in radians where the gradient parameter is 0.0, measured clockwise from +x axis
+    pub start_angle: f32,
+
+    /// Color stops sorted by offset
+    pub stops: Vec<GradientStop>,
+
+    /// How the gradient is extended. A full turn always maps into `(0.0..1.0)`, so this only
+    /// matters for stops which don't span the whole turn.
+    pub spread: SpreadMode,
+}
+
+impl ConicGradient {
+    /// Create ConicGradient without any stop and with `SpreadMode::Pad`
+    pub fn new(center: Point, start_angle: f32) -> Self {
+        Self {
+            center,
+            start_angle,
+            stops: Vec::new(),
+            spread: SpreadMode::Pad,
+        }
+    }
+
+    /// Add a color stop, keeping stops sorted by offset
+    pub fn with_stop(mut self, offset: f32, color: Color) -> Self {
+        insert_stop(&mut self.stops, GradientStop::new(offset, color));
+        self
+    }
+
+    /// Set the spread mode
+    pub fn with_spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Evaluate the gradient at the given point
+    pub fn color_at(&self, point: Point) -> Color {
+        let angle = (point.y - self.center.y).atan2(point.x - self.center.x);
+        let t = ((angle - self.start_angle) / TAU).rem_euclid(1.0);
+
+        sample_stops(&self.stops, self.spread.apply(t))
+    }
+}
+
+/// Insert a stop after every stop with the same or lower offset
+fn insert_stop(stops: &mut Vec<GradientStop>, stop: GradientStop) {
+    let idx = stops.partition_point(|s| s.offset <= stop.offset);
+    stops.insert(idx, stop);
+}
+
+/// Sample sorted stops at `t` in range `(0.0..=1.0)`
+fn sample_stops(stops: &[GradientStop], t: f32) -> Color {
+    let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+        return Color::TRANSPARENT;
+    };
+
+    if t <= first.offset {
+        return first.color;
+    }
+
+    if t >= last.offset {
+        return last.color;
+    }
+
+    let idx = stops.partition_point(|s| s.offset <= t);
+    let (from, to) = (stops[idx - 1], stops[idx]);
+    let span = to.offset - from.offset;
+
+    if span <= 0.0 {
+        to.color
+    } else {
+        from.color.lerp(to.color, (t - from.offset) / span)
+    }
+}
+
+/// Mean color of the gradient over `0.0..=1.0`
+fn average_stops(stops: &[GradientStop]) -> Color {
+    let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+        return Color::TRANSPARENT;
+    };
+
+    // Colors are linear between stops and constant before the first and after the last one
+    let mut sum = [0.0; 4];
+    let mut add = |color: Color, weight: f32| {
+        for (sum, c) in sum.iter_mut().zip([color.r, color.g, color.b, color.a]) {
+            *sum += c * weight;
+        }
+    };
+
+    add(first.color, first.offset.max(0.0));
+    for pair in stops.windows(2) {
+        let half_width = (pair[1].offset - pair[0].offset) * 0.5;
+        add(pair[0].color, half_width);
+        add(pair[1].color, half_width);
+    }
+    add(last.color, (1.0 - last.offset).max(0.0));
+
+    let [r, g, b, a] = sum;
+    Color::new(r, g, b, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const TEST_EPSILON: f32 = 1e-5;
+
+    fn assert_color_eq(actual: Color, expected: Color) {
+        assert_relative_eq!(actual.r, expected.r, epsilon = TEST_EPSILON);
+        assert_relative_eq!(actual.g, expected.g, epsilon = TEST_EPSILON);
+        assert_relative_eq!(actual.b, expected.b, epsilon = TEST_EPSILON);
+        assert_relative_eq!(actual.a, expected.a, epsilon = TEST_EPSILON);
+    }
+
+    mod stops {
+        use super::*;
+
+        #[test]
+        fn test_stops_sorted_on_insert() {
+            let gradient = LinearGradient::new(Point::zero(), Point::new(1.0, 0.0))
+                .with_stop(1.0, Color::BLUE)
+                .with_stop(0.0, Color::RED)
+                .with_stop(0.5, Color::GREEN);
+
+            let offsets: Vec<f32> = gradient.stops.iter().map(|s| s.offset).collect();
+            assert_eq!(offsets, vec![0.0, 0.5, 1.0]);
+        }
+
+        #[test]
+        fn test_sample_empty_stops() {
+            assert_eq!(sample_stops(&[], 0.5), Color::TRANSPARENT);
+        }
+
+        #[test]
+        fn test_sample_single_stop() {
+            let stops = [GradientStop::new(0.5, Color::RED)];
+
+            assert_eq!(sample_stops(&stops, 0.0), Color::RED);
+            assert_eq!(sample_stops(&stops, 1.0), Color::RED);
+        }
+
+        #[test]
+        fn test_sample_hard_stop() {
+            // Two stops at the same offset create a hard edge
+            let stops = [
+                GradientStop::new(0.0, Color::RED),
+                GradientStop::new(0.5, Color::RED),
+                GradientStop::new(0.5, Color::BLUE),
+                GradientStop::new(1.0, Color::BLUE),
+            ];
+
+            assert_eq!(sample_stops(&stops, 0.49), Color::RED);
+            assert_eq!(sample_stops(&stops, 0.51), Color::BLUE);
+        }
+    }
+
+    mod spread {
+        use super::*;
+
+        #[test]
+        fn test_spread_modes() {
+            assert_relative_eq!(SpreadMode::Pad.apply(1.25), 1.0);
+            assert_relative_eq!(SpreadMode::Pad.apply(-0.25), 0.0);
+            assert_relative_eq!(SpreadMode::Repeat.apply(1.25), 0.25);
+            assert_relative_eq!(SpreadMode::Repeat.apply(-0.25), 0.75);
+            assert_relative_eq!(SpreadMode::Reflect.apply(1.25), 0.75);
+            assert_relative_eq!(SpreadMode::Reflect.apply(-0.25), 0.25);
+            assert_relative_eq!(SpreadMode::Reflect.apply(f32::NAN), 0.0);
+        }
+    }
+
+    mod gradients {
+        use super::*;
+
+        #[test]
+        fn test_linear_gradient() {
+            let paint: Paint = LinearGradient::new(Point::new(0.0, 0.0), Point::new(0.0, 10.0))
+                .with_stop(0.0, Color::RED)
+                .with_stop(1.0, Color::BLUE)
+                .with_spread(SpreadMode::Repeat)
+                .into();
+
+            assert_color_eq(paint.color_at(Point::new(7.0, 0.0)), Color::RED);
+            assert_color_eq(
+                paint.color_at(Point::new(7.0, 2.5)),
+                Color::rgb(0.75, 0.0, 0.25),
+            );
+            assert_color_eq(
+                paint.color_at(Point::new(7.0, 12.5)),
+                Color::rgb(0.75, 0.0, 0.25),
+            );
+        }
+
+        #[test]
+        fn test_linear_gradient_degenerated() {
+            let gradient = LinearGradient::new(Point::zero(), Point::zero())
+                .with_stop(0.0, Color::RED)
+                .with_stop(1.0, Color::BLUE);
+
+            // tiny-skia draws degenerated padded gradients with the last stop
+            assert_color_eq(gradient.color_at(Point::new(5.0, 5.0)), Color::BLUE);
+
+            let repeated = gradient.with_spread(SpreadMode::Repeat);
+            assert_color_eq(
+                repeated.color_at(Point::new(5.0, 5.0)),
+                Color::new(0.5, 0.0, 0.5, 1.0),
+            );
+        }
+
+        #[test]
+        fn test_radial_gradient() {
+            let paint: Paint = RadialGradient::new(Point::new(10.0, 10.0), 10.0)
+                .with_stop(0.0, Color::WHITE)
+                .with_stop(1.0, Color::BLACK)
+                .into();
+
+            assert_color_eq(paint.color_at(Point::new(10.0, 10.0)), Color::WHITE);
+            assert_color_eq(
+                paint.color_at(Point::new(15.0, 10.0)),
+                Color::rgb(0.5, 0.5, 0.5),
+            );
+            assert_color_eq(paint.color_at(Point::new(30.0, 30.0)), Color::BLACK);
+        }
+
+        #[test]
+        fn test_radial_gradient_invalid_radius() {
+            assert_eq!(RadialGradient::new(Point::zero(), -1.0).radius, 0.0);
+            assert_eq!(RadialGradient::new(Point::zero(), f32::NAN).radius, 0.0);
+        }
+
+        #[test]
+        fn test_conic_gradient() {
+            let paint: Paint = ConicGradient::new(Point::zero(), 0.0)
+                .with_stop(0.0, Color::BLACK)
+                .with_stop(1.0, Color::WHITE)
+                .into();
+
+            // +x axis is the start, +y (downwards) is a quarter turn clockwise
+            assert_color_eq(paint.color_at(Point::new(1.0, 0.0)), Color::BLACK);
+            assert_color_eq(
+                paint.color_at(Point::new(0.0, 1.0)),
+                Color::rgb(0.25, 0.25, 0.25),
+            );
+            assert_color_eq(
+                paint.color_at(Point::new(-1.0, 0.0)),
+                Color::rgb(0.5, 0.5, 0.5),
+            );
+        }
+
+        #[test]
+        fn test_solid_paint() {
+            let paint = Paint::from(Color::RED);
+
+            assert!(paint.is_solid());
+            assert_eq!(paint.color_at(Point::new(100.0, -3.0)), Color::RED);
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Palette generation from a single seed color
+
+use crate::color::{Color, Oklch};
+
+/// Shade steps of a tonal scale, from the lightest (50) to the darkest (900)
+pub const SHADE_STEPS: [u16; 10] = [50, 100, 200, 300, 400, 500, 600, 700, 800, 900];
+
+/// OKLCH lightness of each shade step in [`SHADE_STEPS`]
+const SHADE_LIGHTNESS: [f32; 10] = [0.97, 0.93, 0.86, 0.78, 0.70, 0.62, 0.54, 0.46, 0.37, 0.28];
+
+/// Colors derived from one seed color in the OKLCH color space
+///
+/// # Notes
+///
+/// - Every derived color keeps the hue of its source, so shades stay on-brand.
+/// - Colors which would fall outside of sRGB gamut are mapped into it by reducing chroma.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    /// Color the palette is derived from
+    pub seed: Color,
+
+    /// Tonal scale of the seed hue, ordered as [`SHADE_STEPS`] (lightest first)
+    pub shades: [Color; 10],
+}
+
+impl Palette {
+    /// Create Palette from a seed color
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Brand color the palette is derived from
+    ///
+    /// # Returns
+    ///
+    /// Palette whose shades share the hue and chroma of `seed`, with lightness decreasing from
+    /// shade 50 to shade 900
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::palette::Palette;
+    ///
+    /// let palette = Palette::from_seed(Color::from_hex(0x33_66_CC));
+    /// let light = palette.shade(50).unwrap();
+    /// let dark = palette.shade(900).unwrap();
+    ///
+    /// assert!(light.relative_luminance() > dark.relative_luminance());
+    /// assert_eq!(palette.shade(150), None);
+    /// ```
+    pub fn from_seed(seed: Color) -> Self {
+        let oklch = seed.to_oklch();
+        let shades = SHADE_LIGHTNESS.map(|l| Color::from_oklch(Oklch { l, ..oklch }));
+
+        Self { seed, shades }
+    }
+
+    /// Get a shade of the tonal scale
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - One of [`SHADE_STEPS`] (`50`, `100`, `200` ... `900`)
+    ///
+    /// # Returns
+    ///
+    /// `Some(color)` for a known step, `None` otherwise
+    pub fn shade(&self, step: u16) -> Option<Color> {
+        SHADE_STEPS
+            .iter()
+            .position(|&s| s == step)
+            .map(|idx| self.shades[idx])
+    }
+
+    /// Seed color with its hue rotated by 180 degrees
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::palette::Palette;
+    ///
+    /// let seed = Color::from_hex(0x33_66_CC);
+    /// let complementary = Palette::from_seed(seed).complementary();
+    /// let hue_delta = (complementary.to_oklch().h - seed.to_oklch().h).rem_euclid(360.0);
+    ///
+    /// assert!((hue_delta - 180.0).abs() < 1.0);
+    /// ```
+    pub fn complementary(&self) -> Color {
+        self.rotated(180.0)
+    }
+
+    /// Seed color with its hue rotated by -30 and +30 degrees
+    pub fn analogous(&self) -> [Color; 2] {
+        [self.rotated(-30.0), self.rotated(30.0)]
+    }
+
+    /// Seed color with its hue rotated by +120 and +240 degrees
+    pub fn triadic(&self) -> [Color; 2] {
+        [self.rotated(120.0), self.rotated(240.0)]
+    }
+
+    /// Seed color with its hue rotated by `degrees`, keeping lightness and chroma
+    pub fn rotated(&self, degrees: f32) -> Color {
+        Color::from_oklch(self.seed.to_oklch().rotate_hue(degrees))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const SEEDS: [u32; 5] = [0x33_66_CC, 0xE6_3B_2E, 0x2E_A0_4F, 0xF5_A6_23, 0x80_80_80];
+
+    fn hue_delta(a: Color, b: Color) -> f32 {
+        let delta = (a.to_oklch().h - b.to_oklch().h).rem_euclid(360.0);
+        delta.min(360.0 - delta)
+    }
+
+    #[test]
+    fn test_shades_get_darker() {
+        for seed in SEEDS {
+            let palette = Palette::from_seed(Color::from_hex(seed));
+
+            for pair in palette.shades.windows(2) {
+                assert!(
+                    pair[0].to_oklch().l > pair[1].to_oklch().l,
+                    "Shades should get darker. seed: {:06X}, pair: {:?}",
+                    seed,
+                    pair
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_shades_lightness() {
+        let palette = Palette::from_seed(Color::from_hex(0x33_66_CC));
+
+        for (shade, lightness) in palette.shades.iter().zip(SHADE_LIGHTNESS) {
+            assert_relative_eq!(shade.to_oklch().l, lightness, epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_shades_keep_hue() {
+        let seed = Color::from_hex(0x33_66_CC);
+        let palette = Palette::from_seed(seed);
+
+        // Extreme shades lose most chroma, so only the middle of the scale keeps a stable hue
+        for step in [200, 300, 400, 500, 600, 700] {
+            let shade = palette.shade(step).unwrap();
+            assert!(
+                hue_delta(shade, seed) < 3.0,
+                "Shade {} should keep seed hue. shade: {:?}",
+                step,
+                shade
+            );
+        }
+    }
+
+    #[test]
+    fn test_shade_lookup() {
+        let palette = Palette::from_seed(Color::RED);
+
+        assert_eq!(palette.shade(50), Some(palette.shades[0]));
+        assert_eq!(palette.shade(500), Some(palette.shades[5]));
+        assert_eq!(palette.shade(900), Some(palette.shades[9]));
+        assert_eq!(palette.shade(0), None);
+        assert_eq!(palette.shade(1000), None);
+    }
+
+    #[test]
+    fn test_harmonies() {
+        let seed = Color::from_hex(0x33_66_CC);
+        let palette = Palette::from_seed(seed);
+
+        assert_relative_eq!(
+            hue_delta(palette.complementary(), seed),
+            180.0,
+            epsilon = 2.0
+        );
+
+        for color in palette.analogous() {
+            assert_relative_eq!(hue_delta(color, seed), 30.0, epsilon = 2.0);
+        }
+
+        for color in palette.triadic() {
+            assert_relative_eq!(hue_delta(color, seed), 120.0, epsilon = 2.0);
+        }
+    }
+
+    #[test]
+    fn test_harmonies_keep_lightness() {
+        let seed = Color::from_hex(0x33_66_CC);
+        let palette = Palette::from_seed(seed);
+        let lightness = seed.to_oklch().l;
+
+        for color in [palette.complementary(), palette.rotated(45.0)] {
+            assert_relative_eq!(color.to_oklch().l, lightness, epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_gray_seed() {
+        // Achromatic seed produces an achromatic scale
+        let palette = Palette::from_seed(Color::from_hex(0x80_80_80));
+
+        for shade in palette.shades {
+            assert!(
+                shade.to_oklch().c < 1e-3,
+                "Shade should be gray: {:?}",
+                shade
+            );
+        }
+    }
+}
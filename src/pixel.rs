@@ -0,0 +1,514 @@
+//! Bulk conversions between color buffers and packed pixel buffers
+//!
+//! Byte buffers use the 4-byte [`PixelFormat`]s. RGB565 buffers hold one `u16` per pixel and
+//! half-float buffers one `[u16; 4]` of RGBA halves per pixel.
+//!
+//! Every function works on whole slices with fixed-size per-pixel chunks, so the loops are
+//! straightforward for the compiler to vectorize.
+
+use crate::color::{Color, PixelFormat};
+
+/// Bytes per pixel of every [`PixelFormat`]
+pub const BYTES_PER_PIXEL: usize = 4;
+
+/// Error type for bulk pixel buffer conversions
+#[derive(Debug, Clone, PartialEq)]
+pub enum PixelBufferError {
+    /// The byte buffer length is not a multiple of [`BYTES_PER_PIXEL`]
+    InvalidLength,
+
+    /// Source and destination buffers hold different numbers of pixels
+    SizeMismatch,
+}
+
+impl std::fmt::Display for PixelBufferError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PixelBufferError::InvalidLength => {
+                write!(
+                    f,
+                    "Invalid pixel buffer length (must be a multiple of 4 bytes)"
+                )
+            }
+            PixelBufferError::SizeMismatch => {
+                write!(f, "Source and destination have different pixel counts")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PixelBufferError {}
+
+/// Decode a byte buffer of `format` into colors
+///
+/// # Arguments
+///
+/// * `bytes` - Pixel bytes, 4 bytes per pixel
+/// * `format` - Channel order of `bytes`
+/// * `out` - Destination colors, one per pixel
+///
+/// # Errors
+///
+/// * Returns `PixelBufferError::InvalidLength` if `bytes.len()` is not a multiple of 4
+/// * Returns `PixelBufferError::SizeMismatch` if `out` doesn't hold exactly one color per pixel
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::{Color, PixelFormat};
+/// use ho_gui::pixel::decode_pixels;
+///
+/// let bgra = [0x00, 0x00, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0xFF];
+/// let mut colors = [Color::TRANSPARENT; 2];
+/// decode_pixels(&bgra, PixelFormat::Bgra8, &mut colors).unwrap();
+///
+/// assert_eq!(colors, [Color::RED, Color::BLUE]);
+/// ```
+pub fn decode_pixels(
+    bytes: &[u8],
+    format: PixelFormat,
+    out: &mut [Color],
+) -> Result<(), PixelBufferError> {
+    check_sizes(bytes.len(), out.len())?;
+
+    for (pixel, color) in bytes.chunks_exact(BYTES_PER_PIXEL).zip(out.iter_mut()) {
+        *color = Color::from_bytes([pixel[0], pixel[1], pixel[2], pixel[3]], format);
+    }
+
+    Ok(())
+}
+
+/// Encode colors into a byte buffer of `format`
+///
+/// # Arguments
+///
+/// * `colors` - Source colors, one per pixel
+/// * `format` - Channel order of `out`
+/// * `out` - Destination pixel bytes, 4 bytes per pixel
+///
+/// # Errors
+///
+/// * Returns `PixelBufferError::InvalidLength` if `out.len()` is not a multiple of 4
+/// * Returns `PixelBufferError::SizeMismatch` if `out` doesn't hold exactly one pixel per color
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::{Color, PixelFormat};
+/// use ho_gui::pixel::encode_pixels;
+///
+/// let mut argb = [0u8; 4];
+/// encode_pixels(&[Color::GREEN], PixelFormat::Argb8, &mut argb).unwrap();
+///
+/// assert_eq!(argb, [0xFF, 0x00, 0xFF, 0x00]);
+/// ```
+pub fn encode_pixels(
+    colors: &[Color],
+    format: PixelFormat,
+    out: &mut [u8],
+) -> Result<(), PixelBufferError> {
+    check_sizes(out.len(), colors.len())?;
+
+    for (color, pixel) in colors.iter().zip(out.chunks_exact_mut(BYTES_PER_PIXEL)) {
+        pixel.copy_from_slice(&color.to_bytes(format));
+    }
+
+    Ok(())
+}
+
+/// Convert a byte buffer from one channel order to another
+///
+/// # Arguments
+///
+/// * `src` - Source pixel bytes in `src_format`
+/// * `src_format` - Channel order of `src`
+/// * `dst` - Destination pixel bytes in `dst_format`
+/// * `dst_format` - Channel order of `dst`
+///
+/// # Errors
+///
+/// * Returns `PixelBufferError::InvalidLength` if a buffer length is not a multiple of 4
+/// * Returns `PixelBufferError::SizeMismatch` if `src` and `dst` have different lengths
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::PixelFormat;
+/// use ho_gui::pixel::swizzle_pixels;
+///
+/// let bgra = [0x33, 0x22, 0x11, 0x44];
+/// let mut rgba = [0u8; 4];
+/// swizzle_pixels(&bgra, PixelFormat::Bgra8, &mut rgba, PixelFormat::Rgba8).unwrap();
+///
+/// assert_eq!(rgba, [0x11, 0x22, 0x33, 0x44]);
+/// ```
+pub fn swizzle_pixels(
+    src: &[u8],
+    src_format: PixelFormat,
+    dst: &mut [u8],
+    dst_format: PixelFormat,
+) -> Result<(), PixelBufferError> {
+    if !src.len().is_multiple_of(BYTES_PER_PIXEL) {
+        return Err(PixelBufferError::InvalidLength);
+    }
+    check_sizes(dst.len(), src.len() / BYTES_PER_PIXEL)?;
+
+    let map = swizzle_map(src_format, dst_format);
+    for (from, to) in src
+        .chunks_exact(BYTES_PER_PIXEL)
+        .zip(dst.chunks_exact_mut(BYTES_PER_PIXEL))
+    {
+        to[0] = from[map[0]];
+        to[1] = from[map[1]];
+        to[2] = from[map[2]];
+        to[3] = from[map[3]];
+    }
+
+    Ok(())
+}
+
+/// Convert a byte buffer from one channel order to another in place
+///
+/// # Errors
+///
+/// * Returns `PixelBufferError::InvalidLength` if `bytes.len()` is not a multiple of 4
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::PixelFormat;
+/// use ho_gui::pixel::swizzle_pixels_in_place;
+///
+/// let mut pixels = [0x11, 0x22, 0x33, 0x44];
+/// swizzle_pixels_in_place(&mut pixels, PixelFormat::Rgba8, PixelFormat::Argb8).unwrap();
+///
+/// assert_eq!(pixels, [0x44, 0x11, 0x22, 0x33]);
+/// ```
+pub fn swizzle_pixels_in_place(
+    bytes: &mut [u8],
+    from: PixelFormat,
+    to: PixelFormat,
+) -> Result<(), PixelBufferError> {
+    if !bytes.len().is_multiple_of(BYTES_PER_PIXEL) {
+        return Err(PixelBufferError::InvalidLength);
+    }
+
+    if from == to {
+        return Ok(());
+    }
+
+    let map = swizzle_map(from, to);
+    for pixel in bytes.chunks_exact_mut(BYTES_PER_PIXEL) {
+        let src = [pixel[0], pixel[1], pixel[2], pixel[3]];
+        pixel[0] = src[map[0]];
+        pixel[1] = src[map[1]];
+        pixel[2] = src[map[2]];
+        pixel[3] = src[map[3]];
+    }
+
+    Ok(())
+}
+
+/// Pack colors into u32 values of `format` (see [`Color::to_packed`])
+///
+/// # Errors
+///
+/// * Returns `PixelBufferError::SizeMismatch` if `colors` and `out` have different lengths
+pub fn pack_pixels(
+    colors: &[Color],
+    format: PixelFormat,
+    out: &mut [u32],
+) -> Result<(), PixelBufferError> {
+    if colors.len() != out.len() {
+        return Err(PixelBufferError::SizeMismatch);
+    }
+
+    for (color, packed) in colors.iter().zip(out.iter_mut()) {
+        *packed = color.to_packed(format);
+    }
+
+    Ok(())
+}
+
+/// Unpack u32 values of `format` into colors (see [`Color::from_packed`])
+///
+/// # Errors
+///
+/// * Returns `PixelBufferError::SizeMismatch` if `packed` and `out` have different lengths
+pub fn unpack_pixels(
+    packed: &[u32],
+    format: PixelFormat,
+    out: &mut [Color],
+) -> Result<(), PixelBufferError> {
+    if packed.len() != out.len() {
+        return Err(PixelBufferError::SizeMismatch);
+    }
+
+    for (value, color) in packed.iter().zip(out.iter_mut()) {
+        *color = Color::from_packed(*value, format);
+    }
+
+    Ok(())
+}
+
+/// Pack colors into RGB565 values, dropping alpha (see [`Color::to_rgb565`])
+///
+/// # Errors
+///
+/// * Returns `PixelBufferError::SizeMismatch` if `colors` and `out` have different lengths
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::Color;
+/// use ho_gui::pixel::pack_pixels_rgb565;
+///
+/// let mut packed = [0u16; 2];
+/// pack_pixels_rgb565(&[Color::RED, Color::BLUE], &mut packed).unwrap();
+///
+/// assert_eq!(packed, [0xF800, 0x001F]);
+/// ```
+pub fn pack_pixels_rgb565(colors: &[Color], out: &mut [u16]) -> Result<(), PixelBufferError> {
+    if colors.len() != out.len() {
+        return Err(PixelBufferError::SizeMismatch);
+    }
+
+    for (color, packed) in colors.iter().zip(out.iter_mut()) {
+        *packed = color.to_rgb565();
+    }
+
+    Ok(())
+}
+
+/// Unpack RGB565 values into opaque colors (see [`Color::from_rgb565`])
+///
+/// # Errors
+///
+/// * Returns `PixelBufferError::SizeMismatch` if `packed` and `out` have different lengths
+pub fn unpack_pixels_rgb565(packed: &[u16], out: &mut [Color]) -> Result<(), PixelBufferError> {
+    if packed.len() != out.len() {
+        return Err(PixelBufferError::SizeMismatch);
+    }
+
+    for (value, color) in packed.iter().zip(out.iter_mut()) {
+        *color = Color::from_rgb565(*value);
+    }
+
+    Ok(())
+}
+
+/// Encode colors into RGBA half-float pixels (see [`Color::to_rgba_f16`])
+///
+/// # Errors
+///
+/// * Returns `PixelBufferError::SizeMismatch` if `colors` and `out` have different lengths
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::Color;
+/// use ho_gui::pixel::encode_pixels_f16;
+///
+/// let mut halves = [[0u16; 4]; 1];
+/// encode_pixels_f16(&[Color::new(1.0, 0.5, 0.0, 1.0)], &mut halves).unwrap();
+///
+/// assert_eq!(halves, [[0x3C00, 0x3800, 0x0000, 0x3C00]]);
+/// ```
+pub fn encode_pixels_f16(colors: &[Color], out: &mut [[u16; 4]]) -> Result<(), PixelBufferError> {
+    if colors.len() != out.len() {
+        return Err(PixelBufferError::SizeMismatch);
+    }
+
+    for (color, halves) in colors.iter().zip(out.iter_mut()) {
+        *halves = color.to_rgba_f16();
+    }
+
+    Ok(())
+}
+
+/// Decode RGBA half-float pixels into colors (see [`Color::from_rgba_f16`])
+///
+/// # Errors
+///
+/// * Returns `PixelBufferError::SizeMismatch` if `halves` and `out` have different lengths
+pub fn decode_pixels_f16(halves: &[[u16; 4]], out: &mut [Color]) -> Result<(), PixelBufferError> {
+    if halves.len() != out.len() {
+        return Err(PixelBufferError::SizeMismatch);
+    }
+
+    for (value, color) in halves.iter().zip(out.iter_mut()) {
+        *color = Color::from_rgba_f16(*value);
+    }
+
+    Ok(())
+}
+
+/// Check that `byte_len` is a whole number of pixels and equals `pixel_count` pixels
+fn check_sizes(byte_len: usize, pixel_count: usize) -> Result<(), PixelBufferError> {
+    if !byte_len.is_multiple_of(BYTES_PER_PIXEL) {
+        return Err(PixelBufferError::InvalidLength);
+    }
+
+    if byte_len / BYTES_PER_PIXEL != pixel_count {
+        return Err(PixelBufferError::SizeMismatch);
+    }
+
+    Ok(())
+}
+
+/// For each destination byte, the index of the source byte holding the same channel
+fn swizzle_map(from: PixelFormat, to: PixelFormat) -> [usize; 4] {
+    let from_idx = from.channel_indices();
+    let to_idx = to.channel_indices();
+
+    let mut map = [0usize; 4];
+    for channel in 0..4 {
+        map[to_idx[channel]] = from_idx[channel];
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMATS: [PixelFormat; 3] = [PixelFormat::Rgba8, PixelFormat::Bgra8, PixelFormat::Argb8];
+
+    fn sample_colors() -> Vec<Color> {
+        (0..64u8)
+            .map(|i| Color::rgba(i * 4, 255 - i, i.wrapping_mul(37), 128 + i))
+            .collect()
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let colors = sample_colors();
+
+        for format in FORMATS {
+            let mut bytes = vec![0u8; colors.len() * BYTES_PER_PIXEL];
+            let mut decoded = vec![Color::TRANSPARENT; colors.len()];
+
+            encode_pixels(&colors, format, &mut bytes).unwrap();
+            decode_pixels(&bytes, format, &mut decoded).unwrap();
+
+            assert_eq!(decoded, colors, "Roundtrip failed for {:?}", format);
+        }
+    }
+
+    #[test]
+    fn test_swizzle_matches_per_pixel_conversion() {
+        let colors = sample_colors();
+
+        for src_format in FORMATS {
+            for dst_format in FORMATS {
+                let mut src = vec![0u8; colors.len() * BYTES_PER_PIXEL];
+                let mut expected = src.clone();
+                let mut dst = src.clone();
+
+                encode_pixels(&colors, src_format, &mut src).unwrap();
+                encode_pixels(&colors, dst_format, &mut expected).unwrap();
+                swizzle_pixels(&src, src_format, &mut dst, dst_format).unwrap();
+                assert_eq!(dst, expected, "{:?} -> {:?}", src_format, dst_format);
+
+                swizzle_pixels_in_place(&mut src, src_format, dst_format).unwrap();
+                assert_eq!(
+                    src, expected,
+                    "in place {:?} -> {:?}",
+                    src_format, dst_format
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pack_unpack_roundtrip() {
+        let colors = sample_colors();
+
+        for format in FORMATS {
+            let mut packed = vec![0u32; colors.len()];
+            let mut unpacked = vec![Color::TRANSPARENT; colors.len()];
+
+            pack_pixels(&colors, format, &mut packed).unwrap();
+            unpack_pixels(&packed, format, &mut unpacked).unwrap();
+
+            assert_eq!(unpacked, colors);
+        }
+    }
+
+    #[test]
+    fn test_rgb565_matches_per_pixel_conversion() {
+        let colors = sample_colors();
+        let mut packed = vec![0u16; colors.len()];
+        let mut unpacked = vec![Color::TRANSPARENT; colors.len()];
+
+        pack_pixels_rgb565(&colors, &mut packed).unwrap();
+        unpack_pixels_rgb565(&packed, &mut unpacked).unwrap();
+
+        for ((color, value), back) in colors.iter().zip(&packed).zip(&unpacked) {
+            assert_eq!(*value, color.to_rgb565());
+            assert_eq!(*back, Color::from_rgb565(*value));
+        }
+    }
+
+    #[test]
+    fn test_f16_roundtrip() {
+        // Every 8-bit level is exact in half-float
+        let colors = sample_colors();
+        let mut halves = vec![[0u16; 4]; colors.len()];
+        let mut decoded = vec![Color::TRANSPARENT; colors.len()];
+
+        encode_pixels_f16(&colors, &mut halves).unwrap();
+        decode_pixels_f16(&halves, &mut decoded).unwrap();
+
+        for (color, back) in colors.iter().zip(&decoded) {
+            assert_eq!(back.to_rgba_u8(), color.to_rgba_u8());
+        }
+        assert_eq!(halves[0], colors[0].to_rgba_f16());
+    }
+
+    #[test]
+    fn test_invalid_sizes() {
+        let mut colors = [Color::TRANSPARENT; 2];
+        let mut bytes = [0u8; 8];
+
+        assert_eq!(
+            decode_pixels(&[0u8; 7], PixelFormat::Rgba8, &mut colors),
+            Err(PixelBufferError::InvalidLength)
+        );
+        assert_eq!(
+            decode_pixels(&[0u8; 12], PixelFormat::Rgba8, &mut colors),
+            Err(PixelBufferError::SizeMismatch)
+        );
+        assert_eq!(
+            encode_pixels(&[Color::RED], PixelFormat::Rgba8, &mut bytes),
+            Err(PixelBufferError::SizeMismatch)
+        );
+        assert_eq!(
+            swizzle_pixels(
+                &[0u8; 6],
+                PixelFormat::Rgba8,
+                &mut bytes,
+                PixelFormat::Bgra8
+            ),
+            Err(PixelBufferError::InvalidLength)
+        );
+        assert_eq!(
+            swizzle_pixels_in_place(&mut [0u8; 5], PixelFormat::Rgba8, PixelFormat::Bgra8),
+            Err(PixelBufferError::InvalidLength)
+        );
+        assert_eq!(
+            pack_pixels(&colors, PixelFormat::Rgba8, &mut [0u32; 1]),
+            Err(PixelBufferError::SizeMismatch)
+        );
+        assert_eq!(
+            pack_pixels_rgb565(&colors, &mut [0u16; 3]),
+            Err(PixelBufferError::SizeMismatch)
+        );
+        assert_eq!(
+            unpack_pixels_rgb565(&[0u16; 1], &mut colors),
+            Err(PixelBufferError::SizeMismatch)
+        );
+        assert_eq!(
+            encode_pixels_f16(&colors, &mut [[0u16; 4]; 1]),
+            Err(PixelBufferError::SizeMismatch)
+        );
+        assert_eq!(
+            decode_pixels_f16(&[[0u16; 4]; 3], &mut colors),
+            Err(PixelBufferError::SizeMismatch)
+        );
+    }
+}
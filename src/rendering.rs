@@ -0,0 +1,1067 @@
+//! Draw commands and renderers
+//!
+//! Widgets record [`DrawCommand`]s into a [`DrawList`] every frame, and a [`Renderer`] turns the
+//! list into pixels.
+
+pub mod software;
+
+use crate::color::{BlendMode, Color};
+use crate::layout::layer::Layer;
+use crate::math::{Point, Rect};
+use crate::paint::Paint;
+use crate::style::{Border, BorderStyle, Borders, CornerRadius, Shadow};
+
+/// Single segment of a [`Path`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathElement {
+    /// Start a new sub-path at the point
+    MoveTo(Point),
+
+    /// Straight line to the point
+    LineTo(Point),
+
+    /// Quadratic bezier curve with a control point and an end point
+    QuadTo(Point, Point),
+
+    /// Cubic bezier curve with two control points and an end point
+    CubicTo(Point, Point, Point),
+
+    /// Close the current sub-path with a straight line to its start
+    Close,
+}
+
+/// Vector path made of lines and bezier curves
+///
+/// # Examples
+/// ```
+/// use ho_gui::math::Point;
+/// use ho_gui::rendering::{Path, PathElement};
+///
+/// let triangle = Path::new()
+///     .move_to(Point::new(0.0, 0.0))
+///     .line_to(Point::new(10.0, 0.0))
+///     .line_to(Point::new(5.0, 10.0))
+///     .close();
+///
+/// assert_eq!(triangle.elements().len(), 4);
+/// assert_eq!(triangle.elements()[3], PathElement::Close);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Path {
+    elements: Vec<PathElement>,
+}
+
+impl Path {
+    /// Create an empty Path
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a closed path outlining `rect` with rounded corners
+    ///
+    /// # Notes
+    ///
+    /// - Radii are fit into the rect first, see [`CornerRadius::fit`]
+    /// - Corners are approximated with cubic bezier curves
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::math::Rect;
+    /// use ho_gui::rendering::Path;
+    /// use ho_gui::style::CornerRadius;
+    ///
+    /// let rounded = Path::rounded_rect(Rect::new(0.0, 0.0, 40.0, 20.0), CornerRadius::all(4.0));
+    /// let square = Path::rounded_rect(Rect::new(0.0, 0.0, 40.0, 20.0), CornerRadius::zero());
+    ///
+    /// // Every corner adds a curve
+    /// assert_eq!(rounded.elements().len(), square.elements().len() + 4);
+    /// ```
+    pub fn rounded_rect(rect: Rect, radius: CornerRadius) -> Self {
+        let radius = radius.fit(rect.size);
+        let circular = |r: f32| Point::new(r, r);
+
+        Self::rounded_rect_elliptical(
+            rect,
+            [
+                circular(radius.top_left),
+                circular(radius.top_right),
+                circular(radius.bottom_right),
+                circular(radius.bottom_left),
+            ],
+        )
+    }
+
+    /// Create a closed path outlining `rect` with elliptical corners. Each radius holds the
+    /// horizontal radius in `x` and the vertical radius in `y`, clockwise from the top left.
+    ///
+    /// Radii are expected to fit into the rect already.
+    fn rounded_rect_elliptical(rect: Rect, radii: [Point; 4]) -> Self {
+        // Distance of bezier control points approximating a quarter ellipse
+        const KAPPA: Point = Point::new(0.552_284_8, 0.552_284_8);
+
+        let (left, top, right, bottom) = (rect.left(), rect.top(), rect.right(), rect.bottom());
+        let [top_left, top_right, bottom_right, bottom_left] = radii;
+
+        let corner = |path: Self, r: Point, start: Point, corner: Point, end: Point| {
+            if r.x == 0.0 || r.y == 0.0 {
+                return path.line_to(corner);
+            }
+
+            let control_1 = start + (corner - start) * KAPPA;
+            let control_2 = end + (corner - end) * KAPPA;
+            path.line_to(start).cubic_to(control_1, control_2, end)
+        };
+
+        let start = if top_left.y == 0.0 { 0.0 } else { top_left.x };
+        let path = Self::new().move_to(Point::new(left + start, top));
+
+        let r = top_right;
+        let path = corner(
+            path,
+            r,
+            Point::new(right - r.x, top),
+            Point::new(right, top),
+            Point::new(right, top + r.y),
+        );
+
+        let r = bottom_right;
+        let path = corner(
+            path,
+            r,
+            Point::new(right, bottom - r.y),
+            Point::new(right, bottom),
+            Point::new(right - r.x, bottom),
+        );
+
+        let r = bottom_left;
+        let path = corner(
+            path,
+            r,
+            Point::new(left + r.x, bottom),
+            Point::new(left, bottom),
+            Point::new(left, bottom - r.y),
+        );
+
+        let r = top_left;
+        let path = corner(
+            path,
+            r,
+            Point::new(left, top + r.y),
+            Point::new(left, top),
+            Point::new(left + r.x, top),
+        );
+
+        path.close()
+    }
+
+    /// Start a new sub-path at `point`
+    pub fn move_to(mut self, point: Point) -> Self {
+        self.elements.push(PathElement::MoveTo(point));
+        self
+    }
+
+    /// Add a straight line to `point`
+    pub fn line_to(mut self, point: Point) -> Self {
+        self.elements.push(PathElement::LineTo(point));
+        self
+    }
+
+    /// Add a quadratic bezier curve through `control` to `point`
+    pub fn quad_to(mut self, control: Point, point: Point) -> Self {
+        self.elements.push(PathElement::QuadTo(control, point));
+        self
+    }
+
+    /// Add a cubic bezier curve through `control_1` and `control_2` to `point`
+    pub fn cubic_to(mut self, control_1: Point, control_2: Point, point: Point) -> Self {
+        self.elements
+            .push(PathElement::CubicTo(control_1, control_2, point));
+        self
+    }
+
+    /// Close the current sub-path
+    pub fn close(mut self) -> Self {
+        self.elements.push(PathElement::Close);
+        self
+    }
+
+    /// Append every sub-path of `other`
+    pub fn extend(mut self, other: Path) -> Self {
+        self.elements.extend(other.elements);
+        self
+    }
+
+    /// Path elements in order
+    pub fn elements(&self) -> &[PathElement] {
+        &self.elements
+    }
+
+    /// Check if the path has no element
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+}
+
+/// Single drawing operation recorded into a [`DrawList`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCommand {
+    /// Fill a rectangle
+    FillRect {
+        /// Rectangle to fill
+        rect: Rect,
+
+        /// Fill paint
+        paint: Paint,
+
+        /// Blend mode used to combine the fill with the destination
+        blend_mode: BlendMode,
+    },
+
+    /// Stroke the outline of a rectangle. The stroke is drawn inside of the rectangle.
+    StrokeRect {
+        /// Rectangle to stroke
+        rect: Rect,
+
+        /// Stroke width
+        width: f32,
+
+        /// Stroke paint
+        paint: Paint,
+
+        /// Blend mode used to combine the stroke with the destination
+        blend_mode: BlendMode,
+    },
+
+    /// Fill a rectangle with rounded corners
+    FillRoundedRect {
+        /// Rectangle to fill
+        rect: Rect,
+
+        /// Corner radii, fit into the rectangle when drawn
+        radius: CornerRadius,
+
+        /// Fill paint
+        paint: Paint,
+
+        /// Blend mode used to combine the fill with the destination
+        blend_mode: BlendMode,
+    },
+
+    /// Stroke the outline of a rectangle with rounded corners. The stroke is drawn inside of the
+    /// rectangle and its inner edge follows the corners with radii reduced by the stroke width.
+    StrokeRoundedRect {
+        /// Rectangle to stroke
+        rect: Rect,
+
+        /// Outer corner radii, fit into the rectangle when drawn
+        radius: CornerRadius,
+
+        /// Stroke width
+        width: f32,
+
+        /// Stroke paint
+        paint: Paint,
+
+        /// Blend mode used to combine the stroke with the destination
+        blend_mode: BlendMode,
+    },
+
+    /// Stroke each side of a rectangle with its own border. Borders are drawn inside of the
+    /// rectangle and adjacent sides meet at the diagonal of their corner.
+    StrokeBorders {
+        /// Rectangle to stroke
+        rect: Rect,
+
+        /// Outer corner radii, fit into the rectangle when drawn
+        radius: CornerRadius,
+
+        /// Border of every side
+        borders: Borders,
+
+        /// Blend mode used to combine the borders with the destination
+        blend_mode: BlendMode,
+    },
+
+    /// Draw a blurred shadow of a (rounded) rectangle. Drop shadows are drawn only outside of
+    /// the rectangle, inset shadows only inside of it.
+    BoxShadow {
+        /// Rectangle casting the shadow
+        rect: Rect,
+
+        /// Corner radii of the rectangle, fit into it when drawn
+        radius: CornerRadius,
+
+        /// Shadow to draw
+        shadow: Shadow,
+
+        /// Blend mode used to combine the shadow with the destination
+        blend_mode: BlendMode,
+    },
+
+    /// Fill a path using the non-zero winding rule
+    FillPath {
+        /// Path to fill
+        path: Path,
+
+        /// Fill paint
+        paint: Paint,
+
+        /// Blend mode used to combine the fill with the destination
+        blend_mode: BlendMode,
+    },
+}
+
+/// Ordered list of draw commands for a single frame
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DrawList {
+    commands: Vec<DrawCommand>,
+}
+
+impl DrawList {
+    /// Create an empty DrawList
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a draw command
+    pub fn push(&mut self, command: DrawCommand) {
+        self.commands.push(command);
+    }
+
+    /// Append a command filling `rect` with `paint` using `BlendMode::SourceOver`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::{BlendMode, Color};
+    /// use ho_gui::math::Rect;
+    /// use ho_gui::paint::Paint;
+    /// use ho_gui::rendering::{DrawCommand, DrawList};
+    ///
+    /// let mut draw_list = DrawList::new();
+    /// draw_list.fill_rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::RED);
+    ///
+    /// assert_eq!(
+    ///     draw_list.commands(),
+    ///     &[DrawCommand::FillRect {
+    ///         rect: Rect::new(0.0, 0.0, 10.0, 10.0),
+    ///         paint: Paint::Solid(Color::RED),
+    ///         blend_mode: BlendMode::SourceOver,
+    ///     }],
+    /// );
+    /// ```
+    pub fn fill_rect(&mut self, rect: Rect, paint: impl Into<Paint>) {
+        self.fill_rect_blended(rect, paint, BlendMode::SourceOver);
+    }
+
+    /// Append a command filling `rect` with `paint` using the given blend mode
+    pub fn fill_rect_blended(
+        &mut self,
+        rect: Rect,
+        paint: impl Into<Paint>,
+        blend_mode: BlendMode,
+    ) {
+        self.push(DrawCommand::FillRect {
+            rect,
+            paint: paint.into(),
+            blend_mode,
+        });
+    }
+
+    /// Append a command stroking the outline of `rect` with `border` using `BlendMode::SourceOver`
+    pub fn stroke_rect(&mut self, rect: Rect, border: Border) {
+        self.stroke_rect_blended(rect, border, BlendMode::SourceOver);
+    }
+
+    /// Append a command stroking the outline of `rect` with `border` using the given blend mode
+    ///
+    /// # Notes
+    ///
+    /// - Borders other than `BorderStyle::Solid` are recorded as `DrawCommand::StrokeBorders`
+    pub fn stroke_rect_blended(&mut self, rect: Rect, border: Border, blend_mode: BlendMode) {
+        if border.style != BorderStyle::Solid {
+            self.stroke_borders_blended(rect, CornerRadius::zero(), border.into(), blend_mode);
+            return;
+        }
+
+        self.push(DrawCommand::StrokeRect {
+            rect,
+            width: border.width,
+            paint: Paint::Solid(border.color),
+            blend_mode,
+        });
+    }
+
+    /// Append a command stroking the outline of `rect` with `width` and `paint` using
+    /// `BlendMode::SourceOver`
+    pub fn stroke_rect_with_paint(&mut self, rect: Rect, width: f32, paint: impl Into<Paint>) {
+        self.push(DrawCommand::StrokeRect {
+            rect,
+            width,
+            paint: paint.into(),
+            blend_mode: BlendMode::SourceOver,
+        });
+    }
+
+    /// Append a command filling `rect` with rounded corners with `paint` using
+    /// `BlendMode::SourceOver`
+    pub fn fill_rounded_rect(&mut self, rect: Rect, radius: CornerRadius, paint: impl Into<Paint>) {
+        self.fill_rounded_rect_blended(rect, radius, paint, BlendMode::SourceOver);
+    }
+
+    /// Append a command filling `rect` with rounded corners with `paint` using the given blend
+    /// mode
+    pub fn fill_rounded_rect_blended(
+        &mut self,
+        rect: Rect,
+        radius: CornerRadius,
+        paint: impl Into<Paint>,
+        blend_mode: BlendMode,
+    ) {
+        self.push(DrawCommand::FillRoundedRect {
+            rect,
+            radius,
+            paint: paint.into(),
+            blend_mode,
+        });
+    }
+
+    /// Append a command stroking the rounded outline of `rect` with `border` using
+    /// `BlendMode::SourceOver`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::math::Rect;
+    /// use ho_gui::rendering::DrawList;
+    /// use ho_gui::style::{Border, CornerRadius};
+    ///
+    /// let mut draw_list = DrawList::new();
+    /// let rect = Rect::new(0.0, 0.0, 80.0, 24.0);
+    ///
+    /// draw_list.fill_rounded_rect(rect, CornerRadius::all(6.0), Color::WHITE);
+    /// draw_list.stroke_rounded_rect(rect, CornerRadius::all(6.0), Border::new(1.0, Color::BLACK));
+    ///
+    /// assert_eq!(draw_list.len(), 2);
+    /// ```
+    pub fn stroke_rounded_rect(&mut self, rect: Rect, radius: CornerRadius, border: Border) {
+        self.stroke_rounded_rect_blended(rect, radius, border, BlendMode::SourceOver);
+    }
+
+    /// Append a command stroking the rounded outline of `rect` with `border` using the given
+    /// blend mode
+    ///
+    /// # Notes
+    ///
+    /// - Borders other than `BorderStyle::Solid` are recorded as `DrawCommand::StrokeBorders`
+    pub fn stroke_rounded_rect_blended(
+        &mut self,
+        rect: Rect,
+        radius: CornerRadius,
+        border: Border,
+        blend_mode: BlendMode,
+    ) {
+        if border.style != BorderStyle::Solid {
+            self.stroke_borders_blended(rect, radius, border.into(), blend_mode);
+            return;
+        }
+
+        self.push(DrawCommand::StrokeRoundedRect {
+            rect,
+            radius,
+            width: border.width,
+            paint: Paint::Solid(border.color),
+            blend_mode,
+        });
+    }
+
+    /// Append a command stroking the rounded outline of `rect` with `width` and `paint` using
+    /// `BlendMode::SourceOver`
+    pub fn stroke_rounded_rect_with_paint(
+        &mut self,
+        rect: Rect,
+        radius: CornerRadius,
+        width: f32,
+        paint: impl Into<Paint>,
+    ) {
+        self.push(DrawCommand::StrokeRoundedRect {
+            rect,
+            radius,
+            width,
+            paint: paint.into(),
+            blend_mode: BlendMode::SourceOver,
+        });
+    }
+
+    /// Append a command stroking every side of `rect` with its own border using
+    /// `BlendMode::SourceOver`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::math::Rect;
+    /// use ho_gui::rendering::DrawList;
+    /// use ho_gui::style::{Border, Borders, CornerRadius};
+    ///
+    /// // Tab with a dashed focus indicator on the bottom only
+    /// let borders = Borders::none().with_bottom(Border::dashed(2.0, Color::BLUE));
+    ///
+    /// let mut draw_list = DrawList::new();
+    /// draw_list.stroke_borders(Rect::new(0.0, 0.0, 80.0, 24.0), CornerRadius::top(6.0), borders);
+    ///
+    /// assert_eq!(draw_list.len(), 1);
+    /// ```
+    pub fn stroke_borders(&mut self, rect: Rect, radius: CornerRadius, borders: Borders) {
+        self.stroke_borders_blended(rect, radius, borders, BlendMode::SourceOver);
+    }
+
+    /// Append a command stroking every side of `rect` with its own border using the given blend
+    /// mode
+    ///
+    /// # Notes
+    ///
+    /// - Every side is drawn with the solid color of its border. Gradient borders are only
+    ///   available for solid borders of the same width on all sides, through
+    ///   `stroke_rect_with_paint` and `stroke_rounded_rect_with_paint`
+    pub fn stroke_borders_blended(
+        &mut self,
+        rect: Rect,
+        radius: CornerRadius,
+        borders: Borders,
+        blend_mode: BlendMode,
+    ) {
+        self.push(DrawCommand::StrokeBorders {
+            rect,
+            radius,
+            borders,
+            blend_mode,
+        });
+    }
+
+    /// Append a command drawing `shadow` of `rect` with rounded corners using
+    /// `BlendMode::SourceOver`
+    ///
+    /// # Notes
+    ///
+    /// - Record drop shadows before filling the rect, and inset shadows after it
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::math::{Rect, Vec2};
+    /// use ho_gui::rendering::DrawList;
+    /// use ho_gui::style::{CornerRadius, Shadow};
+    ///
+    /// let popup = Rect::new(20.0, 20.0, 200.0, 120.0);
+    /// let radius = CornerRadius::all(8.0);
+    /// let shadow = Shadow::new(Vec2::new(0.0, 4.0), 12.0, 0.0, Color::BLACK);
+    ///
+    /// let mut draw_list = DrawList::new();
+    /// draw_list.box_shadow(popup, radius, shadow);
+    /// draw_list.fill_rounded_rect(popup, radius, Color::WHITE);
+    ///
+    /// assert_eq!(draw_list.len(), 2);
+    /// ```
+    pub fn box_shadow(&mut self, rect: Rect, radius: CornerRadius, shadow: Shadow) {
+        self.box_shadow_blended(rect, radius, shadow, BlendMode::SourceOver);
+    }
+
+    /// Append a command drawing `shadow` of `rect` with rounded corners using the given blend
+    /// mode
+    pub fn box_shadow_blended(
+        &mut self,
+        rect: Rect,
+        radius: CornerRadius,
+        shadow: Shadow,
+        blend_mode: BlendMode,
+    ) {
+        self.push(DrawCommand::BoxShadow {
+            rect,
+            radius,
+            shadow,
+            blend_mode,
+        });
+    }
+
+    /// Append a command filling `path` with `paint` using `BlendMode::SourceOver`
+    pub fn fill_path(&mut self, path: Path, paint: impl Into<Paint>) {
+        self.fill_path_blended(path, paint, BlendMode::SourceOver);
+    }
+
+    /// Append a command filling `path` with `paint` using the given blend mode
+    pub fn fill_path_blended(
+        &mut self,
+        path: Path,
+        paint: impl Into<Paint>,
+        blend_mode: BlendMode,
+    ) {
+        self.push(DrawCommand::FillPath {
+            path,
+            paint: paint.into(),
+            blend_mode,
+        });
+    }
+
+    /// Recorded commands in drawing order
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    /// Number of recorded commands
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Check if no command is recorded
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Remove all commands, keeping allocated memory for the next frame
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+}
+
+/// Draw lists of one frame split into z-ordered [`Layer`]s
+///
+/// # Notes
+///
+/// - Layers are drawn from `Layer::Background` up to `Layer::Debug`, so a popup recorded before
+///   the widgets under it still covers them
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::Color;
+/// use ho_gui::layout::layer::Layer;
+/// use ho_gui::math::Rect;
+/// use ho_gui::rendering::{DrawCommand, LayeredDrawList};
+///
+/// let mut layers = LayeredDrawList::new();
+/// layers.layer_mut(Layer::Popup).fill_rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::RED);
+/// layers.layer_mut(Layer::Main).fill_rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::BLUE);
+///
+/// let colors: Vec<_> = layers
+///     .commands()
+///     .map(|command| match command {
+///         DrawCommand::FillRect { paint, .. } => paint.clone(),
+///         _ => unreachable!(),
+///     })
+///     .collect();
+/// assert_eq!(colors, [Color::BLUE.into(), Color::RED.into()]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayeredDrawList {
+    layers: [DrawList; Layer::ALL.len()],
+}
+
+impl LayeredDrawList {
+    /// Create a LayeredDrawList with every layer empty
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draw list of `layer`
+    pub fn layer(&self, layer: Layer) -> &DrawList {
+        &self.layers[layer.index()]
+    }
+
+    /// Draw list of `layer`, to record commands into
+    pub fn layer_mut(&mut self, layer: Layer) -> &mut DrawList {
+        &mut self.layers[layer.index()]
+    }
+
+    /// Recorded commands of every layer in drawing order
+    pub fn commands(&self) -> impl Iterator<Item = &DrawCommand> {
+        self.layers.iter().flat_map(DrawList::commands)
+    }
+
+    /// Number of recorded commands on every layer
+    pub fn len(&self) -> usize {
+        self.layers.iter().map(DrawList::len).sum()
+    }
+
+    /// Check if no command is recorded on any layer
+    pub fn is_empty(&self) -> bool {
+        self.layers.iter().all(DrawList::is_empty)
+    }
+
+    /// Remove all commands, keeping allocated memory for the next frame
+    pub fn clear(&mut self) {
+        self.layers.iter_mut().for_each(DrawList::clear);
+    }
+}
+
+/// 8x8 Bayer threshold matrix for ordered dithering, ranked `0..64`
+const BAYER_8X8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// 16x16 blue-noise threshold matrix ranked `0..256`, generated with the void-and-cluster
+/// method (Ulichney 1993, gaussian sigma 1.5, toroidal)
+const BLUE_NOISE_16X16: [[u8; 16]; 16] = [
+    [
+        234, 50, 188, 19, 58, 171, 121, 47, 163, 3, 247, 104, 22, 132, 14, 65,
+    ],
+    [
+        209, 8, 118, 97, 240, 205, 23, 228, 138, 64, 123, 170, 72, 224, 99, 149,
+    ],
+    [
+        85, 139, 229, 165, 78, 146, 111, 84, 176, 216, 30, 231, 153, 201, 42, 180,
+    ],
+    [
+        25, 62, 195, 29, 43, 185, 7, 249, 41, 100, 191, 48, 87, 5, 128, 243,
+    ],
+    [
+        221, 152, 101, 253, 130, 220, 59, 200, 156, 12, 136, 112, 254, 174, 69, 109,
+    ],
+    [
+        46, 189, 2, 73, 172, 90, 142, 116, 80, 237, 210, 61, 147, 33, 206, 160,
+    ],
+    [
+        81, 124, 217, 113, 208, 15, 241, 27, 168, 45, 178, 20, 193, 96, 225, 18,
+    ],
+    [
+        242, 164, 60, 35, 157, 53, 181, 68, 223, 105, 125, 83, 236, 131, 55, 141,
+    ],
+    [
+        197, 10, 227, 134, 246, 95, 126, 198, 148, 1, 244, 161, 71, 9, 182, 106,
+    ],
+    [
+        40, 93, 179, 75, 192, 6, 218, 36, 91, 57, 202, 34, 215, 155, 233, 74,
+    ],
+    [
+        252, 120, 150, 24, 110, 63, 166, 119, 232, 183, 133, 103, 49, 117, 31, 167,
+    ],
+    [
+        16, 212, 51, 238, 207, 137, 255, 21, 76, 151, 13, 250, 190, 88, 203, 135,
+    ],
+    [
+        102, 184, 82, 169, 38, 89, 187, 52, 204, 98, 173, 67, 129, 4, 222, 56,
+    ],
+    [
+        230, 144, 0, 127, 226, 11, 154, 114, 239, 39, 219, 28, 235, 145, 175, 77,
+    ],
+    [
+        196, 37, 248, 70, 107, 199, 66, 177, 17, 143, 115, 159, 86, 44, 108, 26,
+    ],
+    [
+        122, 92, 158, 214, 140, 32, 245, 94, 213, 79, 194, 54, 211, 186, 251, 162,
+    ],
+];
+
+/// Dithering applied when float colors are quantized into 8-bit pixels
+///
+/// Dithering trades the visible bands of smooth gradients (especially on large, dark areas) for
+/// fine noise which the eye averages out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Dithering {
+    /// Round to the nearest 8-bit level
+    #[default]
+    None,
+
+    /// Ordered dithering with an 8x8 Bayer matrix. Cheap, with a regular cross-hatch pattern.
+    Ordered,
+
+    /// Dithering with a 16x16 blue-noise matrix. Less structured than `Ordered`.
+    BlueNoise,
+}
+
+impl Dithering {
+    /// Threshold offset at pixel `(x, y)` in units of one 8-bit level, in range `(-0.5..0.5)`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::rendering::Dithering;
+    ///
+    /// assert_eq!(Dithering::None.offset(3, 5), 0.0);
+    /// assert!(Dithering::Ordered.offset(0, 0) < 0.0);
+    /// assert!(Dithering::BlueNoise.offset(7, 2).abs() < 0.5);
+    /// ```
+    pub fn offset(&self, x: u32, y: u32) -> f32 {
+        let (rank, levels) = match self {
+            Self::None => return 0.0,
+            Self::Ordered => (BAYER_8X8[y as usize % 8][x as usize % 8], 64.0),
+            Self::BlueNoise => (BLUE_NOISE_16X16[y as usize % 16][x as usize % 16], 256.0),
+        };
+
+        (rank as f32 + 0.5) / levels - 0.5
+    }
+
+    /// Quantize `color` at pixel `(x, y)` into 8-bit RGBA
+    ///
+    /// # Note
+    ///
+    /// Exact 0.0 and 1.0 components are kept exact, so dithering never adds noise to pure black,
+    /// pure white or fully opaque pixels.
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::rendering::Dithering;
+    ///
+    /// assert_eq!(Dithering::None.quantize(Color::WHITE, 0, 0), (255, 255, 255, 255));
+    /// assert_eq!(Dithering::Ordered.quantize(Color::BLACK, 3, 1), (0, 0, 0, 255));
+    /// ```
+    pub fn quantize(&self, color: Color, x: u32, y: u32) -> (u8, u8, u8, u8) {
+        if *self == Self::None {
+            return color.to_rgba_u8();
+        }
+
+        let offset = self.offset(x, y);
+        let quantize = |c: f32| (c * 255.0 + offset).round().clamp(0.0, 255.0) as u8;
+
+        (
+            quantize(color.r),
+            quantize(color.g),
+            quantize(color.b),
+            quantize(color.a),
+        )
+    }
+}
+
+/// Backend which turns a [`DrawList`] into pixels
+pub trait Renderer {
+    /// Draw every command of `draw_list` in order
+    ///
+    /// # Notes
+    ///
+    /// - A frame may be drawn with several calls, post-processing waits for `finish_frame`
+    fn render(&mut self, draw_list: &DrawList);
+
+    /// Finish the frame drawn by the `render` calls since the last `finish_frame`, applying
+    /// whole-frame post-processing. Renderers without post-processing do nothing.
+    fn finish_frame(&mut self) {}
+
+    /// Draw every layer of `layers`, bottom to top
+    ///
+    /// # Notes
+    ///
+    /// - Layers are parts of one frame, post-processing still waits for `finish_frame`
+    fn render_layers(&mut self, layers: &LayeredDrawList) {
+        for layer in Layer::ALL {
+            self.render(layers.layer(layer));
+        }
+    }
+}
+
+/// Error type for renderer operations
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderError {
+    /// The requested render target has zero or too large width or height
+    InvalidSize,
+}
+
+impl std::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::InvalidSize => {
+                write!(
+                    f,
+                    "Invalid render target size (must be non-zero and fit in memory)"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_list_records_in_order() {
+        let mut draw_list = DrawList::new();
+        let rect = Rect::new(1.0, 2.0, 3.0, 4.0);
+        let border = Border::new(1.0, Color::BLACK);
+
+        draw_list.fill_rect(rect, Color::RED);
+        draw_list.stroke_rect_blended(rect, border, BlendMode::Multiply);
+
+        assert_eq!(draw_list.len(), 2);
+        assert_eq!(
+            draw_list.commands()[0],
+            DrawCommand::FillRect {
+                rect,
+                paint: Paint::Solid(Color::RED),
+                blend_mode: BlendMode::SourceOver,
+            }
+        );
+        assert_eq!(
+            draw_list.commands()[1],
+            DrawCommand::StrokeRect {
+                rect,
+                width: 1.0,
+                paint: Paint::Solid(Color::BLACK),
+                blend_mode: BlendMode::Multiply,
+            }
+        );
+    }
+
+    #[test]
+    fn test_draw_list_clear() {
+        let mut draw_list = DrawList::new();
+        draw_list.fill_rect(Rect::new(0.0, 0.0, 1.0, 1.0), Color::RED);
+        draw_list.clear();
+
+        assert!(draw_list.is_empty());
+    }
+
+    #[test]
+    fn test_layered_draw_list_orders_layers() {
+        let mut layers = LayeredDrawList::new();
+        let rect = Rect::new(0.0, 0.0, 1.0, 1.0);
+
+        layers.layer_mut(Layer::Debug).fill_rect(rect, Color::RED);
+        layers
+            .layer_mut(Layer::Background)
+            .fill_rect(rect, Color::BLACK);
+        layers.layer_mut(Layer::Main).fill_rect(rect, Color::BLUE);
+        layers
+            .layer_mut(Layer::Background)
+            .fill_rect(rect, Color::WHITE);
+
+        let paints: Vec<&Paint> = layers
+            .commands()
+            .map(|command| match command {
+                DrawCommand::FillRect { paint, .. } => paint,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            paints,
+            [Color::BLACK, Color::WHITE, Color::BLUE, Color::RED]
+                .map(Paint::Solid)
+                .each_ref()
+        );
+        assert_eq!(layers.len(), 4);
+        assert_eq!(layers.layer(Layer::Background).len(), 2);
+
+        layers.clear();
+        assert!(layers.is_empty());
+    }
+
+    #[test]
+    fn test_rounded_rect_path() {
+        let rect = Rect::new(10.0, 20.0, 40.0, 30.0);
+        let path = Path::rounded_rect(rect, CornerRadius::new(5.0, 0.0, 0.0, 0.0));
+        let elements = path.elements();
+
+        assert_eq!(elements[0], PathElement::MoveTo(Point::new(15.0, 20.0)));
+        assert_eq!(elements.last(), Some(&PathElement::Close));
+
+        // Only the top left corner is curved, and it ends where the path started
+        let curves: Vec<_> = elements
+            .iter()
+            .filter_map(|element| match element {
+                PathElement::CubicTo(_, _, end) => Some(*end),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(curves, vec![Point::new(15.0, 20.0)]);
+    }
+
+    #[test]
+    fn test_rounded_rect_path_fits_radius() {
+        let rect = Rect::new(0.0, 0.0, 100.0, 20.0);
+        let path = Path::rounded_rect(rect, CornerRadius::all(f32::INFINITY));
+
+        // Pill: corners of radius 10 start at x = 10
+        assert_eq!(
+            path.elements()[0],
+            PathElement::MoveTo(Point::new(10.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_styled_border_records_stroke_borders() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let border = Border::dotted(1.0, Color::BLACK);
+
+        let mut draw_list = DrawList::new();
+        draw_list.stroke_rect(rect, border);
+        draw_list.stroke_rounded_rect(rect, CornerRadius::all(2.0), border);
+
+        assert_eq!(
+            draw_list.commands(),
+            &[
+                DrawCommand::StrokeBorders {
+                    rect,
+                    radius: CornerRadius::zero(),
+                    borders: Borders::all(border),
+                    blend_mode: BlendMode::SourceOver,
+                },
+                DrawCommand::StrokeBorders {
+                    rect,
+                    radius: CornerRadius::all(2.0),
+                    borders: Borders::all(border),
+                    blend_mode: BlendMode::SourceOver,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dithering_offsets_are_balanced() {
+        // Every threshold is used exactly once per tile, so the mean offset is zero
+        for (dithering, tile) in [(Dithering::Ordered, 8), (Dithering::BlueNoise, 16)] {
+            let mut sum = 0.0;
+            for y in 0..tile {
+                for x in 0..tile {
+                    let offset = dithering.offset(x, y);
+                    assert!(offset > -0.5 && offset < 0.5);
+                    sum += offset;
+                }
+            }
+
+            assert!(
+                sum.abs() < 1e-3,
+                "{:?} offsets should average to zero",
+                dithering
+            );
+        }
+    }
+
+    #[test]
+    fn test_dithering_tiles() {
+        assert_eq!(
+            Dithering::Ordered.offset(1, 2),
+            Dithering::Ordered.offset(9, 10)
+        );
+        assert_eq!(
+            Dithering::BlueNoise.offset(1, 2),
+            Dithering::BlueNoise.offset(17, 18)
+        );
+    }
+
+    #[test]
+    fn test_blue_noise_matrix_is_permutation() {
+        let mut seen = [false; 256];
+        for row in BLUE_NOISE_16X16 {
+            for rank in row {
+                assert!(!seen[rank as usize], "Duplicated rank {}", rank);
+                seen[rank as usize] = true;
+            }
+        }
+    }
+
+    #[test]
+    fn test_quantize_preserves_extremes() {
+        for dithering in [Dithering::None, Dithering::Ordered, Dithering::BlueNoise] {
+            for y in 0..16 {
+                for x in 0..16 {
+                    assert_eq!(dithering.quantize(Color::BLACK, x, y), (0, 0, 0, 255));
+                    assert_eq!(dithering.quantize(Color::WHITE, x, y), (255, 255, 255, 255));
+                }
+            }
+        }
+    }
+}
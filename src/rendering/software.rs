@@ -0,0 +1,1623 @@
+//! CPU renderer backed by tiny-skia
+
+use tiny_skia::{
+    ColorU8, FillRule, FilterQuality, LineCap, Mask, PathBuilder, Pattern, Pixmap, PixmapPaint,
+    Shader, Stroke, StrokeDash, Transform,
+};
+
+use super::{Dithering, DrawCommand, DrawList, Path, PathElement, RenderError, Renderer};
+use crate::color::{BlendMode, Color, ColorVision};
+use crate::math::{Point, Rect};
+use crate::paint::{Paint, SpreadMode};
+use crate::style::{Border, BorderStyle, Borders, CornerRadius, Shadow};
+
+/// Software renderer drawing into an in-memory RGBA pixmap
+#[derive(Debug, Clone)]
+pub struct SoftwareRenderer {
+    pixmap: Pixmap,
+    color_vision: ColorVision,
+    dithering: Dithering,
+    // Whether something was drawn since the last finish_frame
+    unfinished: bool,
+}
+
+impl SoftwareRenderer {
+    /// Create a renderer with a transparent render target of `width` x `height` pixels
+    ///
+    /// # Errors
+    ///
+    /// * Returns `RenderError::InvalidSize` if `width` or `height` is zero or too large
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::math::Rect;
+    /// use ho_gui::rendering::{DrawList, Renderer};
+    /// use ho_gui::rendering::software::SoftwareRenderer;
+    ///
+    /// let mut renderer = SoftwareRenderer::new(4, 4).unwrap();
+    /// let mut draw_list = DrawList::new();
+    /// draw_list.fill_rect(Rect::new(0.0, 0.0, 4.0, 4.0), Color::RED);
+    /// renderer.render(&draw_list);
+    ///
+    /// assert_eq!(renderer.pixel(1, 1), Some(Color::RED));
+    /// ```
+    pub fn new(width: u32, height: u32) -> Result<Self, RenderError> {
+        let pixmap = Pixmap::new(width, height).ok_or(RenderError::InvalidSize)?;
+        Ok(Self {
+            pixmap,
+            color_vision: ColorVision::Normal,
+            dithering: Dithering::None,
+            unfinished: false,
+        })
+    }
+
+    /// Width of the render target in pixels
+    pub fn width(&self) -> u32 {
+        self.pixmap.width()
+    }
+
+    /// Height of the render target in pixels
+    pub fn height(&self) -> u32 {
+        self.pixmap.height()
+    }
+
+    /// Color vision simulated on rendered frames
+    pub fn color_vision(&self) -> ColorVision {
+        self.color_vision
+    }
+
+    /// Set the color vision to simulate. This is a debug aid for checking how a frame looks to
+    /// users with color vision deficiencies.
+    ///
+    /// # Notes
+    ///
+    /// - The simulation is applied to the whole render target once per frame, by `finish_frame`
+    ///   after every `render` call of the frame
+    /// - `ColorVision::Normal` disables the simulation
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::{Color, ColorVision};
+    /// use ho_gui::math::Rect;
+    /// use ho_gui::rendering::{DrawList, Renderer};
+    /// use ho_gui::rendering::software::SoftwareRenderer;
+    ///
+    /// let mut renderer = SoftwareRenderer::new(4, 4).unwrap();
+    /// renderer.set_color_vision(ColorVision::Grayscale);
+    ///
+    /// let mut draw_list = DrawList::new();
+    /// draw_list.fill_rect(Rect::new(0.0, 0.0, 4.0, 4.0), Color::RED);
+    /// renderer.render(&draw_list);
+    /// renderer.finish_frame();
+    ///
+    /// let pixel = renderer.pixel(0, 0).unwrap();
+    /// assert_eq!(pixel.r, pixel.g);
+    /// assert_eq!(pixel.g, pixel.b);
+    /// ```
+    pub fn set_color_vision(&mut self, vision: ColorVision) {
+        self.color_vision = vision;
+    }
+
+    /// Dithering applied to gradients
+    pub fn dithering(&self) -> Dithering {
+        self.dithering
+    }
+
+    /// Set dithering applied when gradients are quantized into 8-bit pixels. Turn it on to avoid
+    /// banding of gradients on large, dark areas.
+    ///
+    /// # Note
+    ///
+    /// Solid colors are never dithered. With dithering on, gradients are evaluated per pixel on
+    /// the CPU instead of using tiny-skia shaders.
+    pub fn set_dithering(&mut self, dithering: Dithering) {
+        self.dithering = dithering;
+    }
+
+    /// Fill the whole render target with `color`, starting a new frame
+    pub fn clear(&mut self, color: Color) {
+        self.pixmap.fill(to_skia_color(color));
+        self.unfinished = true;
+    }
+
+    /// Read back a single pixel as a non-premultiplied color
+    ///
+    /// # Returns
+    ///
+    /// `None` if `(x, y)` is out of the render target
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        let pixel = self.pixmap.pixel(x, y)?.demultiply();
+        Some(Color::rgba(
+            pixel.red(),
+            pixel.green(),
+            pixel.blue(),
+            pixel.alpha(),
+        ))
+    }
+
+    /// Premultiplied RGBA8 pixel data of the render target, row by row
+    pub fn data(&self) -> &[u8] {
+        self.pixmap.data()
+    }
+
+    fn simulate_color_vision(&mut self) {
+        let vision = self.color_vision;
+
+        for pixel in self.pixmap.pixels_mut() {
+            let straight = pixel.demultiply();
+            if straight.alpha() == 0 {
+                continue;
+            }
+
+            let color = Color::rgba(
+                straight.red(),
+                straight.green(),
+                straight.blue(),
+                straight.alpha(),
+            );
+            let (r, g, b, a) = color.simulate_vision(vision).to_rgba_u8();
+            *pixel = ColorU8::from_rgba(r, g, b, a).premultiply();
+        }
+    }
+
+    fn fill_rect(&mut self, rect: Rect, paint: &Paint, blend_mode: BlendMode) {
+        let Some(rect) = to_skia_rect(rect) else {
+            return;
+        };
+
+        self.fill_skia_path(&PathBuilder::from_rect(rect), paint, blend_mode);
+    }
+
+    fn stroke_rect(&mut self, rect: Rect, width: f32, paint: &Paint, blend_mode: BlendMode) {
+        if width.is_nan() || width <= 0.0 {
+            return;
+        }
+
+        // Stroke is centered on the path, so inset by half of the width to keep it inside
+        let half = width / 2.0;
+        let inset = Rect::new(
+            rect.left() + half,
+            rect.top() + half,
+            rect.size.width - width,
+            rect.size.height - width,
+        );
+
+        let Some(skia_rect) = to_skia_rect(inset) else {
+            // Border is thicker than the rect itself, which covers the whole rect
+            self.fill_rect(rect, paint, blend_mode);
+            return;
+        };
+
+        let stroke = Stroke {
+            width,
+            ..Stroke::default()
+        };
+
+        // Outline the stroke so that every paint is filled the same way
+        if let Some(outline) = PathBuilder::from_rect(skia_rect).stroke(&stroke, 1.0) {
+            self.fill_skia_path(&outline, paint, blend_mode);
+        }
+    }
+
+    fn fill_rounded_rect(
+        &mut self,
+        rect: Rect,
+        radius: CornerRadius,
+        paint: &Paint,
+        blend_mode: BlendMode,
+    ) {
+        if radius.is_zero() {
+            self.fill_rect(rect, paint, blend_mode);
+            return;
+        }
+
+        if to_skia_rect(rect).is_none() {
+            return;
+        }
+
+        self.fill_path(&Path::rounded_rect(rect, radius), paint, blend_mode);
+    }
+
+    fn stroke_rounded_rect(
+        &mut self,
+        rect: Rect,
+        radius: CornerRadius,
+        width: f32,
+        paint: &Paint,
+        blend_mode: BlendMode,
+    ) {
+        if width.is_nan() || width <= 0.0 {
+            return;
+        }
+
+        if radius.is_zero() {
+            self.stroke_rect(rect, width, paint, blend_mode);
+            return;
+        }
+
+        let inner = Rect::new(
+            rect.left() + width,
+            rect.top() + width,
+            rect.size.width - width * 2.0,
+            rect.size.height - width * 2.0,
+        );
+
+        if to_skia_rect(inner).is_none() {
+            // Border is thicker than the rect itself, which covers the whole rect
+            self.fill_rounded_rect(rect, radius, paint, blend_mode);
+            return;
+        }
+
+        // Ring between the outer curve and the inner curve, like CSS borders. Even-odd filling
+        // cuts the inner path out regardless of its direction.
+        let outer_radius = radius.fit(rect.size);
+        let ring = Path::rounded_rect(rect, outer_radius)
+            .extend(Path::rounded_rect(inner, outer_radius.shrink(width)));
+
+        if let Some(path) = to_skia_path(&ring) {
+            self.fill_skia_path_masked(&path, paint, blend_mode, FillRule::EvenOdd, None);
+        }
+    }
+
+    fn stroke_borders(
+        &mut self,
+        rect: Rect,
+        radius: CornerRadius,
+        borders: &Borders,
+        blend_mode: BlendMode,
+    ) {
+        if to_skia_rect(rect).is_none() {
+            return;
+        }
+
+        let edges = BorderEdges::new(rect, radius, borders);
+
+        // Identical sides are drawn in one pass, so no seams show up at the corner diagonals
+        if borders.is_uniform() {
+            self.stroke_border_side(&edges, borders.top, None, blend_mode);
+            return;
+        }
+
+        let sides = [borders.top, borders.right, borders.bottom, borders.left];
+        for (side, border) in sides.into_iter().enumerate() {
+            if border.width == 0.0 {
+                continue;
+            }
+
+            if let Some(mask) = self.border_side_mask(&edges, side) {
+                self.stroke_border_side(&edges, border, Some(&mask), blend_mode);
+            }
+        }
+    }
+
+    /// Mask covering the trapezoid of one side, cut along the corner diagonals. Sides are
+    /// numbered clockwise from the top.
+    fn border_side_mask(&self, edges: &BorderEdges, side: usize) -> Option<Mask> {
+        let (outer, inner) = (edges.outer_corners(), edges.inner_corners());
+        let (a, b) = (side, (side + 1) % 4);
+
+        // Push outer corners further out along the diagonal, so anti-aliased outer edges stay in
+        // the mask. Masks are aliased, so adjacent sides share every pixel exactly once.
+        let extend = |idx: usize| outer[idx] + (outer[idx] - inner[idx]);
+
+        let mut builder = PathBuilder::new();
+        builder.move_to(inner[a].x, inner[a].y);
+        builder.line_to(extend(a).x, extend(a).y);
+        builder.line_to(extend(b).x, extend(b).y);
+        builder.line_to(inner[b].x, inner[b].y);
+        builder.close();
+
+        let mut mask = Mask::new(self.pixmap.width(), self.pixmap.height())?;
+        mask.fill_path(
+            &builder.finish()?,
+            FillRule::Winding,
+            false,
+            Transform::identity(),
+        );
+
+        Some(mask)
+    }
+
+    fn stroke_border_side(
+        &mut self,
+        edges: &BorderEdges,
+        border: Border,
+        mask: Option<&Mask>,
+        blend_mode: BlendMode,
+    ) {
+        let paint = Paint::Solid(border.color);
+
+        match border.style {
+            BorderStyle::Solid => self.fill_border_band(edges, 0.0, 1.0, &paint, blend_mode, mask),
+            BorderStyle::Double => {
+                self.fill_border_band(edges, 0.0, 1.0 / 3.0, &paint, blend_mode, mask);
+                self.fill_border_band(edges, 2.0 / 3.0, 1.0, &paint, blend_mode, mask);
+            }
+            BorderStyle::Dashed | BorderStyle::Dotted => {
+                let width = border.width;
+                let (dash, cap) = if border.style == BorderStyle::Dashed {
+                    (vec![width * 2.0, width], LineCap::Butt)
+                } else {
+                    // Zero length dashes with round caps become dots of the stroke width
+                    (vec![0.0, width * 2.0], LineCap::Round)
+                };
+
+                let stroke = Stroke {
+                    width,
+                    line_cap: cap,
+                    ..Stroke::default()
+                };
+
+                // Outlining a path doesn't apply `Stroke::dash`, so the path is dashed first
+                let Some(center) = edges
+                    .edge(0.5)
+                    .as_ref()
+                    .and_then(to_skia_path)
+                    .zip(StrokeDash::new(dash, 0.0))
+                    .and_then(|(path, dash)| path.dash(&dash, 1.0))
+                else {
+                    return;
+                };
+
+                if let Some(outline) = center.stroke(&stroke, 1.0) {
+                    self.fill_skia_path_masked(
+                        &outline,
+                        &paint,
+                        blend_mode,
+                        FillRule::Winding,
+                        mask,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Fill the band between the edges at fractions `from` and `to` of the border widths
+    fn fill_border_band(
+        &mut self,
+        edges: &BorderEdges,
+        from: f32,
+        to: f32,
+        paint: &Paint,
+        blend_mode: BlendMode,
+        mask: Option<&Mask>,
+    ) {
+        let Some(outer) = edges.edge(from) else {
+            return;
+        };
+
+        // Inner edge collapses when borders are thicker than the rect, which fills the rect
+        let band = match edges.edge(to) {
+            Some(inner) => outer.extend(inner),
+            None => outer,
+        };
+
+        if let Some(path) = to_skia_path(&band) {
+            self.fill_skia_path_masked(&path, paint, blend_mode, FillRule::EvenOdd, mask);
+        }
+    }
+
+    fn box_shadow(
+        &mut self,
+        rect: Rect,
+        radius: CornerRadius,
+        shadow: &Shadow,
+        blend_mode: BlendMode,
+    ) {
+        if to_skia_rect(rect).is_none() {
+            return;
+        }
+
+        // Inset shadows cut the spread box out of the rect, so spread shrinks the cut out
+        let radius = radius.fit(rect.size);
+        let spread = if shadow.inset {
+            -shadow.spread
+        } else {
+            shadow.spread
+        };
+        let shape = Rect::new(
+            rect.left() + shadow.offset.x - spread,
+            rect.top() + shadow.offset.y - spread,
+            rect.size.width + spread * 2.0,
+            rect.size.height + spread * 2.0,
+        );
+        let spread_radius = |r: f32| if r > 0.0 { r + spread } else { 0.0 };
+        let shape_radius = CornerRadius::new(
+            spread_radius(radius.top_left),
+            spread_radius(radius.top_right),
+            spread_radius(radius.bottom_right),
+            spread_radius(radius.bottom_left),
+        );
+
+        if !shadow.inset && to_skia_rect(shape).is_none() {
+            return;
+        }
+
+        // Blurs much wider than the render target barely change across it, so they are capped
+        // to keep the layer within a few times the target size
+        let target = self.width().max(self.height()) as f32;
+        let sigma = if shadow.blur.is_nan() {
+            0.0
+        } else {
+            (shadow.blur / 2.0).clamp(0.0, target / 3.0)
+        };
+        let margin = (sigma * 3.0).ceil();
+
+        // Pixels the blurred shadow can reach, limited to a blur margin around the render target
+        let reach = if shadow.inset { rect } else { shape };
+        let (width, height) = (self.width() as f32, self.height() as f32);
+        let x0 = (reach.left().floor() - margin).clamp(-margin, width + margin) as i32;
+        let y0 = (reach.top().floor() - margin).clamp(-margin, height + margin) as i32;
+        let x1 = (reach.right().ceil() + margin).clamp(-margin, width + margin) as i32;
+        let y1 = (reach.bottom().ceil() + margin).clamp(-margin, height + margin) as i32;
+        if x1 <= x0 || y1 <= y0 {
+            return;
+        }
+
+        let (width, height) = ((x1 - x0) as u32, (y1 - y0) as u32);
+        let transform = Transform::from_translate(-x0 as f32, -y0 as f32);
+        let coverage = |rect: Rect, radius: CornerRadius| -> Option<Mask> {
+            let path = to_skia_path(&Path::rounded_rect(rect, radius))?;
+            let mut mask = Mask::new(width, height)?;
+            mask.fill_path(&path, FillRule::Winding, true, transform);
+            Some(mask)
+        };
+
+        let mut alpha: Vec<f32> = match coverage(shape, shape_radius) {
+            Some(mask) => mask.data().iter().map(|&a| a as f32 / 255.0).collect(),
+            None => vec![0.0; (width * height) as usize],
+        };
+        if shadow.inset {
+            alpha.iter_mut().for_each(|a| *a = 1.0 - *a);
+        }
+
+        blur_alpha(&mut alpha, width as usize, height as usize, sigma);
+
+        // Drop shadows are hidden behind the rect, inset shadows don't leak out of it
+        let Some(clip) = coverage(rect, radius) else {
+            return;
+        };
+        let Some(mut layer) = Pixmap::new(width, height) else {
+            return;
+        };
+
+        let color = shadow.color;
+        for ((pixel, a), clip) in layer.pixels_mut().iter_mut().zip(alpha).zip(clip.data()) {
+            let inside = *clip as f32 / 255.0;
+            let a = a * if shadow.inset { inside } else { 1.0 - inside };
+
+            let (r, g, b, a) = color.with_alpha(color.a * a).to_rgba_u8();
+            *pixel = ColorU8::from_rgba(r, g, b, a).premultiply();
+        }
+
+        let paint = PixmapPaint {
+            blend_mode: to_skia_blend_mode(blend_mode),
+            quality: FilterQuality::Nearest,
+            ..PixmapPaint::default()
+        };
+        self.pixmap
+            .draw_pixmap(x0, y0, layer.as_ref(), &paint, Transform::identity(), None);
+    }
+
+    fn fill_path(&mut self, path: &Path, paint: &Paint, blend_mode: BlendMode) {
+        if let Some(path) = to_skia_path(path) {
+            self.fill_skia_path(&path, paint, blend_mode);
+        }
+    }
+
+    fn fill_skia_path(&mut self, path: &tiny_skia::Path, paint: &Paint, blend_mode: BlendMode) {
+        self.fill_skia_path_masked(path, paint, blend_mode, FillRule::Winding, None);
+    }
+
+    fn fill_skia_path_masked(
+        &mut self,
+        path: &tiny_skia::Path,
+        paint: &Paint,
+        blend_mode: BlendMode,
+        fill_rule: FillRule,
+        mask: Option<&Mask>,
+    ) {
+        let native_shader = if self.dithering == Dithering::None || paint.is_solid() {
+            to_skia_shader(paint)
+        } else {
+            None
+        };
+
+        let pattern_pixmap;
+        let shader = match native_shader {
+            Some(shader) => shader,
+            None => {
+                // Paints tiny-skia can't shade natively (or has to dither) are rasterized over the
+                // visible part of the path bounds
+                let target =
+                    tiny_skia::Rect::from_xywh(0.0, 0.0, self.width() as f32, self.height() as f32);
+                let bounds = target.and_then(|target| path.bounds().intersect(&target));
+                let Some((pixmap, x, y)) =
+                    bounds.and_then(|bounds| rasterize_paint(paint, bounds, self.dithering))
+                else {
+                    return;
+                };
+                pattern_pixmap = pixmap;
+
+                Pattern::new(
+                    pattern_pixmap.as_ref(),
+                    tiny_skia::SpreadMode::Pad,
+                    FilterQuality::Nearest,
+                    1.0,
+                    Transform::from_translate(x as f32, y as f32),
+                )
+            }
+        };
+
+        let skia_paint = tiny_skia::Paint {
+            shader,
+            blend_mode: to_skia_blend_mode(blend_mode),
+            ..tiny_skia::Paint::default()
+        };
+
+        self.pixmap
+            .fill_path(path, &skia_paint, fill_rule, Transform::identity(), mask);
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn render(&mut self, draw_list: &DrawList) {
+        for command in draw_list.commands() {
+            match command {
+                DrawCommand::FillRect {
+                    rect,
+                    paint,
+                    blend_mode,
+                } => self.fill_rect(*rect, paint, *blend_mode),
+                DrawCommand::StrokeRect {
+                    rect,
+                    width,
+                    paint,
+                    blend_mode,
+                } => self.stroke_rect(*rect, *width, paint, *blend_mode),
+                DrawCommand::FillRoundedRect {
+                    rect,
+                    radius,
+                    paint,
+                    blend_mode,
+                } => self.fill_rounded_rect(*rect, *radius, paint, *blend_mode),
+                DrawCommand::StrokeRoundedRect {
+                    rect,
+                    radius,
+                    width,
+                    paint,
+                    blend_mode,
+                } => self.stroke_rounded_rect(*rect, *radius, *width, paint, *blend_mode),
+                DrawCommand::StrokeBorders {
+                    rect,
+                    radius,
+                    borders,
+                    blend_mode,
+                } => self.stroke_borders(*rect, *radius, borders, *blend_mode),
+                DrawCommand::BoxShadow {
+                    rect,
+                    radius,
+                    shadow,
+                    blend_mode,
+                } => self.box_shadow(*rect, *radius, shadow, *blend_mode),
+                DrawCommand::FillPath {
+                    path,
+                    paint,
+                    blend_mode,
+                } => self.fill_path(path, paint, *blend_mode),
+            }
+        }
+
+        self.unfinished = true;
+    }
+
+    fn finish_frame(&mut self) {
+        // Pixels of a finished frame are already simulated
+        if self.unfinished && self.color_vision != ColorVision::Normal {
+            self.simulate_color_vision();
+        }
+        self.unfinished = false;
+    }
+}
+
+/// Geometry of a box with per-side border widths
+struct BorderEdges {
+    rect: Rect,
+    radius: CornerRadius,
+    widths: [f32; 4],
+}
+
+impl BorderEdges {
+    fn new(rect: Rect, radius: CornerRadius, borders: &Borders) -> Self {
+        let mut widths = [
+            borders.top.width,
+            borders.right.width,
+            borders.bottom.width,
+            borders.left.width,
+        ];
+
+        // Borders thicker than the rect are scaled down to meet, so the inner edge never crosses
+        let scale = |a: f32, b: f32, length: f32| {
+            if a + b > length {
+                length / (a + b)
+            } else {
+                1.0
+            }
+        };
+        let horizontal = scale(widths[3], widths[1], rect.size.width);
+        let vertical = scale(widths[0], widths[2], rect.size.height);
+        widths[1] *= horizontal;
+        widths[3] *= horizontal;
+        widths[0] *= vertical;
+        widths[2] *= vertical;
+
+        Self {
+            rect,
+            radius: radius.fit(rect.size),
+            widths,
+        }
+    }
+
+    /// Edge inset by `t` times the border widths, with corner radii shrunk like CSS padding
+    /// edges. `None` if the edge has collapsed.
+    fn edge(&self, t: f32) -> Option<Path> {
+        let [top, right, bottom, left] = self.widths.map(|width| width * t);
+        let rect = Rect::new(
+            self.rect.left() + left,
+            self.rect.top() + top,
+            self.rect.size.width - left - right,
+            self.rect.size.height - top - bottom,
+        );
+
+        if rect.size.width <= 0.0 || rect.size.height <= 0.0 {
+            return None;
+        }
+
+        let radius = self.radius;
+        let shrink = |r: f32, horizontal: f32, vertical: f32| {
+            Point::new((r - horizontal).max(0.0), (r - vertical).max(0.0))
+        };
+
+        Some(Path::rounded_rect_elliptical(
+            rect,
+            [
+                shrink(radius.top_left, left, top),
+                shrink(radius.top_right, right, top),
+                shrink(radius.bottom_right, right, bottom),
+                shrink(radius.bottom_left, left, bottom),
+            ],
+        ))
+    }
+
+    /// Corners of the border box, clockwise from the top left
+    fn outer_corners(&self) -> [Point; 4] {
+        let rect = self.rect;
+        [
+            Point::new(rect.left(), rect.top()),
+            Point::new(rect.right(), rect.top()),
+            Point::new(rect.right(), rect.bottom()),
+            Point::new(rect.left(), rect.bottom()),
+        ]
+    }
+
+    /// Corners of the box inside of the borders, clockwise from the top left
+    fn inner_corners(&self) -> [Point; 4] {
+        let rect = self.rect;
+        let [top, right, bottom, left] = self.widths;
+        [
+            Point::new(rect.left() + left, rect.top() + top),
+            Point::new(rect.right() - right, rect.top() + top),
+            Point::new(rect.right() - right, rect.bottom() - bottom),
+            Point::new(rect.left() + left, rect.bottom() - bottom),
+        ]
+    }
+}
+
+/// Native tiny-skia shader for the paint, or `None` if the paint has to be rasterized
+fn to_skia_shader(paint: &Paint) -> Option<Shader<'static>> {
+    let stops = |stops: &[crate::paint::GradientStop]| -> Vec<tiny_skia::GradientStop> {
+        stops
+            .iter()
+            .map(|stop| tiny_skia::GradientStop::new(stop.offset, to_skia_color(stop.color)))
+            .collect()
+    };
+
+    match paint {
+        Paint::Solid(color) => Some(Shader::SolidColor(to_skia_color(*color))),
+        Paint::LinearGradient(gradient) => tiny_skia::LinearGradient::new(
+            to_skia_point(gradient.start),
+            to_skia_point(gradient.end),
+            stops(&gradient.stops),
+            to_skia_spread_mode(gradient.spread),
+            Transform::identity(),
+        ),
+        Paint::RadialGradient(gradient) => tiny_skia::RadialGradient::new(
+            to_skia_point(gradient.center),
+            to_skia_point(gradient.center),
+            gradient.radius,
+            stops(&gradient.stops),
+            to_skia_spread_mode(gradient.spread),
+            Transform::identity(),
+        ),
+        Paint::ConicGradient(_) => None,
+    }
+}
+
+/// Evaluate `paint` at every pixel center covered by `bounds`, quantizing with `dithering`.
+/// Callers clip `bounds` to the render target, as the pixmap covers all of them.
+///
+/// Returns the premultiplied pixmap and its top-left pixel position.
+fn rasterize_paint(
+    paint: &Paint,
+    bounds: tiny_skia::Rect,
+    dithering: Dithering,
+) -> Option<(Pixmap, i32, i32)> {
+    let bounds = bounds.round_out()?;
+    let mut pixmap = Pixmap::new(bounds.width(), bounds.height())?;
+    let width = bounds.width() as usize;
+
+    for (idx, pixel) in pixmap.pixels_mut().iter_mut().enumerate() {
+        // Dither pattern is anchored to the render target, so it doesn't move with shapes
+        let x = bounds.x() + (idx % width) as i32;
+        let y = bounds.y() + (idx / width) as i32;
+        let color = paint.color_at(Point::new(x as f32 + 0.5, y as f32 + 0.5));
+
+        let (r, g, b, a) = dithering.quantize(color, x.max(0) as u32, y.max(0) as u32);
+        *pixel = ColorU8::from_rgba(r, g, b, a).premultiply();
+    }
+
+    Some((pixmap, bounds.x(), bounds.y()))
+}
+
+/// Approximate a Gaussian blur with standard deviation `sigma` by three box blurs in place.
+/// Pixels outside of the buffer repeat its edge.
+fn blur_alpha(alpha: &mut [f32], width: usize, height: usize, sigma: f32) {
+    if sigma <= 0.0 || width == 0 || height == 0 {
+        return;
+    }
+
+    let mut line = vec![0.0; width.max(height)];
+    let mut blurred = vec![0.0; width.max(height)];
+
+    for radius in box_blur_radii(sigma) {
+        if radius == 0 {
+            continue;
+        }
+
+        for row in alpha.chunks_exact_mut(width) {
+            line[..width].copy_from_slice(row);
+            box_blur_line(&line[..width], row, radius);
+        }
+
+        for x in 0..width {
+            for y in 0..height {
+                line[y] = alpha[y * width + x];
+            }
+
+            box_blur_line(&line[..height], &mut blurred[..height], radius);
+
+            for y in 0..height {
+                alpha[y * width + x] = blurred[y];
+            }
+        }
+    }
+}
+
+/// Radii of three successive box blurs approximating a Gaussian with standard deviation `sigma`
+///
+/// Box sizes are picked so that their combined variance matches the Gaussian, as described in
+/// W. Jarosz, "Fast Image Convolutions" (2001).
+fn box_blur_radii(sigma: f32) -> [usize; 3] {
+    const PASSES: f32 = 3.0;
+
+    let variance = 12.0 * sigma * sigma;
+    let mut lower = (variance / PASSES + 1.0).sqrt().floor() as i32;
+    if lower % 2 == 0 {
+        lower -= 1;
+    }
+
+    let upper = lower + 2;
+    let lower_f = lower as f32;
+    let lower_passes =
+        ((variance - PASSES * lower_f * lower_f - 4.0 * PASSES * lower_f - 3.0 * PASSES)
+            / (-4.0 * lower_f - 4.0))
+            .round() as i32;
+
+    std::array::from_fn(|pass| {
+        let size = if (pass as i32) < lower_passes {
+            lower
+        } else {
+            upper
+        };
+        (size.max(1) as usize - 1) / 2
+    })
+}
+
+/// Box blur of `src` into `dst` with a window of `2 * radius + 1` values
+fn box_blur_line(src: &[f32], dst: &mut [f32], radius: usize) {
+    let last = src.len() as isize - 1;
+    let at = |idx: isize| src[idx.clamp(0, last) as usize];
+    let radius = radius as isize;
+    let size = (2 * radius + 1) as f32;
+
+    let mut sum: f32 = (-radius..=radius).map(at).sum();
+    for (idx, value) in dst.iter_mut().enumerate() {
+        *value = sum / size;
+
+        let idx = idx as isize;
+        sum += at(idx + radius + 1) - at(idx - radius);
+    }
+}
+
+fn to_skia_color(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba(color.r, color.g, color.b, color.a)
+        .unwrap_or(tiny_skia::Color::TRANSPARENT)
+}
+
+fn to_skia_point(point: Point) -> tiny_skia::Point {
+    tiny_skia::Point::from_xy(point.x, point.y)
+}
+
+fn to_skia_rect(rect: Rect) -> Option<tiny_skia::Rect> {
+    tiny_skia::Rect::from_xywh(rect.left(), rect.top(), rect.size.width, rect.size.height)
+}
+
+fn to_skia_path(path: &Path) -> Option<tiny_skia::Path> {
+    let mut builder = PathBuilder::new();
+
+    for element in path.elements() {
+        match *element {
+            PathElement::MoveTo(p) => builder.move_to(p.x, p.y),
+            PathElement::LineTo(p) => builder.line_to(p.x, p.y),
+            PathElement::QuadTo(c, p) => builder.quad_to(c.x, c.y, p.x, p.y),
+            PathElement::CubicTo(c1, c2, p) => builder.cubic_to(c1.x, c1.y, c2.x, c2.y, p.x, p.y),
+            PathElement::Close => builder.close(),
+        }
+    }
+
+    builder.finish()
+}
+
+fn to_skia_spread_mode(spread: SpreadMode) -> tiny_skia::SpreadMode {
+    match spread {
+        SpreadMode::Pad => tiny_skia::SpreadMode::Pad,
+        SpreadMode::Repeat => tiny_skia::SpreadMode::Repeat,
+        SpreadMode::Reflect => tiny_skia::SpreadMode::Reflect,
+    }
+}
+
+fn to_skia_blend_mode(blend_mode: BlendMode) -> tiny_skia::BlendMode {
+    match blend_mode {
+        BlendMode::Clear => tiny_skia::BlendMode::Clear,
+        BlendMode::Source => tiny_skia::BlendMode::Source,
+        BlendMode::Destination => tiny_skia::BlendMode::Destination,
+        BlendMode::SourceOver => tiny_skia::BlendMode::SourceOver,
+        BlendMode::DestinationOver => tiny_skia::BlendMode::DestinationOver,
+        BlendMode::SourceIn => tiny_skia::BlendMode::SourceIn,
+        BlendMode::DestinationIn => tiny_skia::BlendMode::DestinationIn,
+        BlendMode::SourceOut => tiny_skia::BlendMode::SourceOut,
+        BlendMode::DestinationOut => tiny_skia::BlendMode::DestinationOut,
+        BlendMode::SourceAtop => tiny_skia::BlendMode::SourceAtop,
+        BlendMode::DestinationAtop => tiny_skia::BlendMode::DestinationAtop,
+        BlendMode::Xor => tiny_skia::BlendMode::Xor,
+        BlendMode::Plus => tiny_skia::BlendMode::Plus,
+        BlendMode::Multiply => tiny_skia::BlendMode::Multiply,
+        BlendMode::Screen => tiny_skia::BlendMode::Screen,
+        BlendMode::Overlay => tiny_skia::BlendMode::Overlay,
+        BlendMode::Darken => tiny_skia::BlendMode::Darken,
+        BlendMode::Lighten => tiny_skia::BlendMode::Lighten,
+        BlendMode::Difference => tiny_skia::BlendMode::Difference,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::layer::Layer;
+    use crate::math::Vec2;
+    use crate::paint::{ConicGradient, LinearGradient, RadialGradient};
+    use crate::rendering::LayeredDrawList;
+    use crate::style::{Border, Borders, CornerRadius, Shadow};
+
+    // One 8-bit step of rounding error in the pipeline
+    const PIXEL_TOLERANCE: f32 = 2.0 / 255.0;
+
+    fn assert_color_near(actual: Color, expected: Color) {
+        let close = |a: f32, b: f32| (a - b).abs() <= PIXEL_TOLERANCE;
+
+        assert!(
+            close(actual.r, expected.r)
+                && close(actual.g, expected.g)
+                && close(actual.b, expected.b)
+                && close(actual.a, expected.a),
+            "Pixel mismatch. actual: {:?}, expected: {:?}",
+            actual,
+            expected,
+        );
+    }
+
+    #[test]
+    fn test_new_invalid_size() {
+        assert!(matches!(
+            SoftwareRenderer::new(0, 10),
+            Err(RenderError::InvalidSize)
+        ));
+    }
+
+    #[test]
+    fn test_fill_rect() {
+        let mut renderer = SoftwareRenderer::new(8, 8).unwrap();
+        let mut draw_list = DrawList::new();
+        draw_list.fill_rect(Rect::new(2.0, 2.0, 4.0, 4.0), Color::RED);
+
+        renderer.render(&draw_list);
+
+        assert_eq!(renderer.pixel(3, 3), Some(Color::RED));
+        assert_eq!(renderer.pixel(0, 0), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(8, 8), None);
+    }
+
+    #[test]
+    fn test_render_layers_bottom_to_top() {
+        let mut renderer = SoftwareRenderer::new(8, 8).unwrap();
+        let mut layers = LayeredDrawList::new();
+        layers
+            .layer_mut(Layer::Popup)
+            .fill_rect(Rect::new(2.0, 2.0, 4.0, 4.0), Color::RED);
+        layers
+            .layer_mut(Layer::Main)
+            .fill_rect(Rect::new(0.0, 0.0, 8.0, 8.0), Color::BLUE);
+
+        renderer.render_layers(&layers);
+
+        assert_eq!(renderer.pixel(3, 3), Some(Color::RED));
+        assert_eq!(renderer.pixel(0, 0), Some(Color::BLUE));
+    }
+
+    #[test]
+    fn test_render_layers_color_vision_once() {
+        let mut layers = LayeredDrawList::new();
+        layers
+            .layer_mut(Layer::Main)
+            .fill_rect(Rect::new(0.0, 0.0, 8.0, 8.0), Color::RED);
+        layers
+            .layer_mut(Layer::Popup)
+            .fill_rect(Rect::new(2.0, 2.0, 4.0, 4.0), Color::GREEN);
+
+        let mut single = DrawList::new();
+        single.fill_rect(Rect::new(0.0, 0.0, 8.0, 8.0), Color::RED);
+        single.fill_rect(Rect::new(2.0, 2.0, 4.0, 4.0), Color::GREEN);
+        let mut expected = SoftwareRenderer::new(8, 8).unwrap();
+        expected.set_color_vision(ColorVision::Protanopia);
+        expected.render(&single);
+        expected.finish_frame();
+
+        let mut renderer = SoftwareRenderer::new(8, 8).unwrap();
+        renderer.set_color_vision(ColorVision::Protanopia);
+        renderer.render_layers(&layers);
+        renderer.finish_frame();
+
+        assert_eq!(renderer.data(), expected.data());
+        assert_ne!(renderer.pixel(0, 0), Some(Color::RED));
+    }
+
+    #[test]
+    fn test_stroke_rect_inside() {
+        let mut renderer = SoftwareRenderer::new(8, 8).unwrap();
+        let mut draw_list = DrawList::new();
+        draw_list.stroke_rect(Rect::new(0.0, 0.0, 8.0, 8.0), Border::new(2.0, Color::BLUE));
+
+        renderer.render(&draw_list);
+
+        assert_eq!(renderer.pixel(0, 0), Some(Color::BLUE));
+        assert_eq!(renderer.pixel(1, 4), Some(Color::BLUE));
+        assert_eq!(renderer.pixel(4, 4), Some(Color::TRANSPARENT));
+    }
+
+    #[test]
+    fn test_blend_modes_match_color_blend() {
+        let backdrop = Color::rgb(0.5, 0.25, 1.0);
+        let source = Color::rgb(0.25, 0.5, 0.75).with_alpha(0.5);
+        let rect = Rect::new(0.0, 0.0, 2.0, 2.0);
+
+        for mode in [
+            BlendMode::SourceOver,
+            BlendMode::SourceAtop,
+            BlendMode::Xor,
+            BlendMode::Multiply,
+            BlendMode::Screen,
+            BlendMode::Overlay,
+            BlendMode::Darken,
+            BlendMode::Lighten,
+            BlendMode::Difference,
+        ] {
+            let mut renderer = SoftwareRenderer::new(2, 2).unwrap();
+            renderer.clear(backdrop);
+
+            let mut draw_list = DrawList::new();
+            draw_list.fill_rect_blended(rect, source, mode);
+            renderer.render(&draw_list);
+
+            assert_color_near(renderer.pixel(0, 0).unwrap(), source.blend(backdrop, mode));
+        }
+    }
+
+    #[test]
+    fn test_fill_path() {
+        let mut renderer = SoftwareRenderer::new(8, 8).unwrap();
+        let mut draw_list = DrawList::new();
+        let triangle = Path::new()
+            .move_to(Point::new(0.0, 0.0))
+            .line_to(Point::new(8.0, 0.0))
+            .line_to(Point::new(0.0, 8.0))
+            .close();
+        draw_list.fill_path(triangle, Color::GREEN);
+
+        renderer.render(&draw_list);
+
+        assert_eq!(renderer.pixel(1, 1), Some(Color::GREEN));
+        assert_eq!(renderer.pixel(7, 7), Some(Color::TRANSPARENT));
+    }
+
+    #[test]
+    fn test_gradients_match_paint_color_at() {
+        let center = Point::new(8.0, 8.0);
+        let degenerated = LinearGradient::new(center, center)
+            .with_stop(0.0, Color::RED)
+            .with_stop(1.0, Color::BLUE);
+        let paints: [Paint; 5] = [
+            LinearGradient::new(Point::new(0.0, 0.0), Point::new(16.0, 0.0))
+                .with_stop(0.0, Color::RED)
+                .with_stop(1.0, Color::BLUE)
+                .into(),
+            degenerated.clone().into(),
+            degenerated.with_spread(SpreadMode::Repeat).into(),
+            RadialGradient::new(center, 8.0)
+                .with_stop(0.0, Color::WHITE)
+                .with_stop(1.0, Color::BLACK)
+                .into(),
+            ConicGradient::new(center, 0.0)
+                .with_stop(0.0, Color::BLACK)
+                .with_stop(1.0, Color::WHITE)
+                .into(),
+        ];
+
+        for paint in paints {
+            let mut renderer = SoftwareRenderer::new(16, 16).unwrap();
+            let mut draw_list = DrawList::new();
+            draw_list.fill_rect(Rect::new(0.0, 0.0, 16.0, 16.0), paint.clone());
+            renderer.render(&draw_list);
+
+            for (x, y) in [(2, 8), (12, 4), (5, 13)] {
+                let expected = paint.color_at(Point::new(x as f32 + 0.5, y as f32 + 0.5));
+                assert_color_near(renderer.pixel(x, y).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_huge_gradient_fill_is_clipped_to_the_target() {
+        let paint: Paint = ConicGradient::new(Point::new(8.0, 8.0), 0.0)
+            .with_stop(0.0, Color::BLACK)
+            .with_stop(1.0, Color::WHITE)
+            .into();
+        let mut renderer = SoftwareRenderer::new(16, 16).unwrap();
+        let mut draw_list = DrawList::new();
+        draw_list.fill_rect(Rect::new(-4000.0, -4000.0, 8000.0, 8000.0), paint.clone());
+        // Outside of the target, nothing is rasterized
+        draw_list.fill_rect(Rect::new(100.0, 100.0, 8000.0, 8000.0), paint.clone());
+        renderer.render(&draw_list);
+
+        for (x, y) in [(0, 0), (12, 4), (15, 15)] {
+            let expected = paint.color_at(Point::new(x as f32 + 0.5, y as f32 + 0.5));
+            assert_color_near(renderer.pixel(x, y).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_stroke_rect_with_gradient() {
+        let mut renderer = SoftwareRenderer::new(8, 8).unwrap();
+        let mut draw_list = DrawList::new();
+        let gradient = ConicGradient::new(Point::new(4.0, 4.0), 0.0)
+            .with_stop(0.0, Color::RED)
+            .with_stop(1.0, Color::RED);
+        draw_list.stroke_rect_with_paint(Rect::new(0.0, 0.0, 8.0, 8.0), 1.0, gradient);
+
+        renderer.render(&draw_list);
+
+        assert_eq!(renderer.pixel(0, 3), Some(Color::RED));
+        assert_eq!(renderer.pixel(3, 3), Some(Color::TRANSPARENT));
+    }
+
+    #[test]
+    fn test_stroke_rounded_rect_with_gradient() {
+        let mut renderer = SoftwareRenderer::new(8, 8).unwrap();
+        let mut draw_list = DrawList::new();
+        let gradient = LinearGradient::new(Point::zero(), Point::new(8.0, 0.0))
+            .with_stop(0.0, Color::RED)
+            .with_stop(1.0, Color::RED);
+        let rect = Rect::new(0.0, 0.0, 8.0, 8.0);
+        draw_list.stroke_rounded_rect_with_paint(rect, CornerRadius::all(3.0), 1.0, gradient);
+
+        renderer.render(&draw_list);
+
+        assert_eq!(renderer.pixel(0, 4), Some(Color::RED));
+        assert!(renderer.pixel(0, 0).unwrap().a < 0.1, "Corners are rounded");
+        assert_eq!(renderer.pixel(4, 4), Some(Color::TRANSPARENT));
+    }
+
+    #[test]
+    fn test_color_vision_simulation() {
+        let mut draw_list = DrawList::new();
+        draw_list.fill_rect(Rect::new(0.0, 0.0, 2.0, 2.0), Color::RED);
+
+        let mut renderer = SoftwareRenderer::new(4, 4).unwrap();
+        renderer.set_color_vision(ColorVision::Protanopia);
+        renderer.render(&draw_list);
+        renderer.finish_frame();
+
+        let expected = Color::RED.simulate_vision(ColorVision::Protanopia);
+        assert_color_near(renderer.pixel(0, 0).unwrap(), expected);
+
+        // Untouched transparent pixels stay transparent
+        assert_eq!(renderer.pixel(3, 3), Some(Color::TRANSPARENT));
+    }
+
+    #[test]
+    fn test_color_vision_applied_once_per_frame() {
+        let (mut first, mut second) = (DrawList::new(), DrawList::new());
+        first.fill_rect(Rect::new(0.0, 0.0, 4.0, 4.0), Color::RED);
+        second.fill_rect(Rect::new(2.0, 2.0, 2.0, 2.0), Color::GREEN);
+
+        let mut single = first.clone();
+        single.fill_rect(Rect::new(2.0, 2.0, 2.0, 2.0), Color::GREEN);
+        let mut expected = SoftwareRenderer::new(4, 4).unwrap();
+        expected.set_color_vision(ColorVision::Deuteranopia);
+        expected.render(&single);
+        expected.finish_frame();
+
+        let mut renderer = SoftwareRenderer::new(4, 4).unwrap();
+        renderer.set_color_vision(ColorVision::Deuteranopia);
+        renderer.render(&first);
+        renderer.render(&second);
+        renderer.finish_frame();
+        renderer.finish_frame();
+
+        assert_eq!(renderer.data(), expected.data());
+        assert_ne!(renderer.pixel(0, 0), Some(Color::RED));
+    }
+
+    #[test]
+    fn test_dithering_spreads_between_levels() {
+        // A flat gradient between 8-bit levels 16 and 17, a quarter of the way to 17
+        let level = 16.25 / 255.0;
+        let gray = Color::rgb(level, level, level);
+        let gradient = LinearGradient::new(Point::zero(), Point::new(16.0, 0.0))
+            .with_stop(0.0, gray)
+            .with_stop(1.0, gray);
+        let rect = Rect::new(0.0, 0.0, 16.0, 16.0);
+
+        for (dithering, expected_upper) in [
+            (Dithering::None, 0),
+            (Dithering::Ordered, 64),
+            (Dithering::BlueNoise, 64),
+        ] {
+            let mut renderer = SoftwareRenderer::new(16, 16).unwrap();
+            renderer.set_dithering(dithering);
+
+            let mut draw_list = DrawList::new();
+            draw_list.fill_rect(rect, gradient.clone());
+            renderer.render(&draw_list);
+
+            let mut upper = 0;
+            for y in 0..16 {
+                for x in 0..16 {
+                    let (r, _, _, _) = renderer.pixel(x, y).unwrap().to_rgba_u8();
+                    assert!(r == 16 || r == 17, "Unexpected level {}", r);
+                    if r == 17 {
+                        upper += 1;
+                    }
+                }
+            }
+
+            // A quarter of the pixels are rounded up, so the area averages to 16.25
+            assert_eq!(upper, expected_upper, "{:?}", dithering);
+        }
+    }
+
+    #[test]
+    fn test_dithering_huge_fill_rasterizes_the_target_only() {
+        let level = 16.25 / 255.0;
+        let gray = Color::rgb(level, level, level);
+        let gradient = LinearGradient::new(Point::zero(), Point::new(16.0, 0.0))
+            .with_stop(0.0, gray)
+            .with_stop(1.0, gray);
+        let mut renderer = SoftwareRenderer::new(16, 16).unwrap();
+        renderer.set_dithering(Dithering::Ordered);
+
+        // The paint covers 40000x40000 pixels, but only the 16x16 visible ones are evaluated
+        let mut draw_list = DrawList::new();
+        draw_list.fill_rect(Rect::new(-20000.0, -20000.0, 40000.0, 40000.0), gradient);
+        renderer.render(&draw_list);
+
+        let upper = renderer
+            .data()
+            .chunks_exact(4)
+            .filter(|pixel| pixel[0] == 17)
+            .count();
+        assert_eq!(upper, 64);
+    }
+
+    #[test]
+    fn test_dithering_skips_solid_colors() {
+        let color = Color::rgb(16.25 / 255.0, 0.5, 0.5);
+        let mut renderer = SoftwareRenderer::new(8, 8).unwrap();
+        renderer.set_dithering(Dithering::Ordered);
+
+        let mut draw_list = DrawList::new();
+        draw_list.fill_rect(Rect::new(0.0, 0.0, 8.0, 8.0), color);
+        renderer.render(&draw_list);
+
+        let first = renderer.pixel(0, 0);
+        for y in 0..8 {
+            for x in 0..8 {
+                assert_eq!(renderer.pixel(x, y), first);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fill_rounded_rect() {
+        let mut renderer = SoftwareRenderer::new(40, 40).unwrap();
+        let mut draw_list = DrawList::new();
+        draw_list.fill_rounded_rect(
+            Rect::new(0.0, 0.0, 40.0, 40.0),
+            CornerRadius::new(16.0, 0.0, 16.0, 0.0),
+            Color::RED,
+        );
+        renderer.render(&draw_list);
+
+        // Rounded corners are cut off, square corners are filled
+        assert_eq!(renderer.pixel(0, 0), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(39, 39), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(39, 0), Some(Color::RED));
+        assert_eq!(renderer.pixel(0, 39), Some(Color::RED));
+
+        // Edges between the corners and the center are filled
+        assert_eq!(renderer.pixel(20, 0), Some(Color::RED));
+        assert_eq!(renderer.pixel(0, 20), Some(Color::RED));
+        assert_eq!(renderer.pixel(20, 20), Some(Color::RED));
+    }
+
+    #[test]
+    fn test_stroke_rounded_rect_follows_curve() {
+        let mut renderer = SoftwareRenderer::new(40, 40).unwrap();
+        let mut draw_list = DrawList::new();
+        draw_list.stroke_rounded_rect(
+            Rect::new(0.0, 0.0, 40.0, 40.0),
+            CornerRadius::all(16.0),
+            Border::new(2.0, Color::BLUE),
+        );
+        renderer.render(&draw_list);
+
+        // Corner outside of the curve and the inside of the ring are untouched
+        assert_eq!(renderer.pixel(0, 0), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(3, 3), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(20, 20), Some(Color::TRANSPARENT));
+
+        // Straight edges
+        assert_eq!(renderer.pixel(20, 0), Some(Color::BLUE));
+        assert_eq!(renderer.pixel(39, 20), Some(Color::BLUE));
+
+        // On the curve at 45 degrees, 1px inside of the outer edge
+        let offset = 16.0 - 15.0 * std::f32::consts::FRAC_1_SQRT_2;
+        assert_eq!(
+            renderer.pixel(offset as u32, offset as u32),
+            Some(Color::BLUE)
+        );
+    }
+
+    #[test]
+    fn test_stroke_rounded_rect_thicker_than_rect() {
+        let mut renderer = SoftwareRenderer::new(10, 10).unwrap();
+        let mut draw_list = DrawList::new();
+        draw_list.stroke_rounded_rect(
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+            CornerRadius::all(4.0),
+            Border::new(6.0, Color::GREEN),
+        );
+        renderer.render(&draw_list);
+
+        assert_eq!(renderer.pixel(5, 5), Some(Color::GREEN));
+        assert_eq!(renderer.pixel(0, 0), Some(Color::TRANSPARENT));
+    }
+
+    #[test]
+    fn test_zero_radius_matches_rect() {
+        let rect = Rect::new(2.0, 2.0, 12.0, 8.0);
+        let border = Border::new(2.0, Color::BLACK);
+
+        let mut square = SoftwareRenderer::new(16, 12).unwrap();
+        let mut draw_list = DrawList::new();
+        draw_list.fill_rect(rect, Color::RED);
+        draw_list.stroke_rect(rect, border);
+        square.render(&draw_list);
+
+        let mut rounded = SoftwareRenderer::new(16, 12).unwrap();
+        let mut draw_list = DrawList::new();
+        draw_list.fill_rounded_rect(rect, CornerRadius::zero(), Color::RED);
+        draw_list.stroke_rounded_rect(rect, CornerRadius::zero(), border);
+        rounded.render(&draw_list);
+
+        assert_eq!(square.data(), rounded.data());
+    }
+
+    fn render_borders(size: u32, radius: CornerRadius, borders: Borders) -> SoftwareRenderer {
+        let mut renderer = SoftwareRenderer::new(size, size).unwrap();
+        let mut draw_list = DrawList::new();
+        let rect = Rect::new(0.0, 0.0, size as f32, size as f32);
+        draw_list.stroke_borders(rect, radius, borders);
+        renderer.render(&draw_list);
+
+        renderer
+    }
+
+    #[test]
+    fn test_per_side_borders() {
+        let borders = Borders::new(
+            Border::new(4.0, Color::RED),
+            Border::new(2.0, Color::GREEN),
+            Border::none(),
+            Border::new(4.0, Color::BLUE),
+        );
+        let renderer = render_borders(20, CornerRadius::zero(), borders);
+
+        assert_eq!(renderer.pixel(10, 1), Some(Color::RED));
+        assert_eq!(renderer.pixel(19, 10), Some(Color::GREEN));
+        assert_eq!(renderer.pixel(1, 10), Some(Color::BLUE));
+        assert_eq!(renderer.pixel(10, 19), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(10, 10), Some(Color::TRANSPARENT));
+
+        // Top left corner is split along its diagonal
+        assert_eq!(renderer.pixel(2, 0), Some(Color::RED));
+        assert_eq!(renderer.pixel(0, 2), Some(Color::BLUE));
+
+        // Bottom left corner belongs to the left side only
+        assert_eq!(renderer.pixel(1, 19), Some(Color::BLUE));
+    }
+
+    #[test]
+    fn test_per_side_borders_cover_corners_fully() {
+        // Sides of different colors meet without gaps or overlaps
+        let borders =
+            Borders::all(Border::new(4.0, Color::RED)).with_left(Border::new(4.0, Color::BLUE));
+        let renderer = render_borders(20, CornerRadius::zero(), borders);
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let pixel = renderer.pixel(x, y).unwrap();
+                assert_eq!(pixel.a, 1.0, "Corner pixel ({}, {}) has a seam", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_per_side_borders_follow_rounded_corner() {
+        let borders =
+            Borders::all(Border::new(3.0, Color::RED)).with_top(Border::new(3.0, Color::BLUE));
+        let renderer = render_borders(40, CornerRadius::all(12.0), borders);
+
+        assert_eq!(renderer.pixel(0, 0), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(20, 1), Some(Color::BLUE));
+        assert_eq!(renderer.pixel(1, 20), Some(Color::RED));
+
+        // Both halves of the curve, 1.5px inside of the outer edge at 30 and 60 degrees
+        let at = |degrees: f32| {
+            let (sin, cos) = degrees.to_radians().sin_cos();
+            let x = 12.0 - 10.5 * cos;
+            let y = 12.0 - 10.5 * sin;
+            renderer.pixel(x as u32, y as u32)
+        };
+        assert_eq!(at(30.0), Some(Color::RED));
+        assert_eq!(at(60.0), Some(Color::BLUE));
+    }
+
+    #[test]
+    fn test_dashed_and_dotted_borders() {
+        for border in [
+            Border::dashed(2.0, Color::BLACK),
+            Border::dotted(2.0, Color::BLACK),
+        ] {
+            let renderer = render_borders(64, CornerRadius::zero(), Borders::all(border));
+
+            // Along the middle of the top border, some pixels are drawn and some are gaps. Dots
+            // are round, so their pixels are only mostly covered.
+            let row: Vec<_> = (8..56).map(|x| renderer.pixel(x, 1).unwrap().a).collect();
+            assert!(
+                row.iter().any(|&a| a > 0.75) && row.contains(&0.0),
+                "{:?} border should have dashes and gaps",
+                border.style
+            );
+
+            // Nothing is drawn inside of the border
+            assert_eq!(renderer.pixel(32, 4), Some(Color::TRANSPARENT));
+        }
+    }
+
+    #[test]
+    fn test_double_border() {
+        let renderer = render_borders(
+            30,
+            CornerRadius::zero(),
+            Borders::all(Border::double(6.0, Color::BLACK)),
+        );
+
+        // Two 2px lines with a 2px gap
+        assert_eq!(renderer.pixel(15, 0), Some(Color::BLACK));
+        assert_eq!(renderer.pixel(15, 1), Some(Color::BLACK));
+        assert_eq!(renderer.pixel(15, 2), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(15, 3), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(15, 4), Some(Color::BLACK));
+        assert_eq!(renderer.pixel(15, 5), Some(Color::BLACK));
+        assert_eq!(renderer.pixel(15, 6), Some(Color::TRANSPARENT));
+    }
+
+    #[test]
+    fn test_uniform_solid_borders_match_stroke_rect() {
+        let rect = Rect::new(0.0, 0.0, 16.0, 16.0);
+        let border = Border::new(3.0, Color::RED);
+
+        let mut stroked = SoftwareRenderer::new(16, 16).unwrap();
+        let mut draw_list = DrawList::new();
+        draw_list.stroke_rect(rect, border);
+        stroked.render(&draw_list);
+
+        let borders = render_borders(16, CornerRadius::zero(), Borders::all(border));
+
+        assert_eq!(stroked.data(), borders.data());
+    }
+
+    fn render_shadow(rect: Rect, radius: CornerRadius, shadow: Shadow) -> SoftwareRenderer {
+        let mut renderer = SoftwareRenderer::new(64, 64).unwrap();
+        let mut draw_list = DrawList::new();
+        draw_list.box_shadow(rect, radius, shadow);
+        renderer.render(&draw_list);
+
+        renderer
+    }
+
+    fn alpha(renderer: &SoftwareRenderer, x: u32, y: u32) -> f32 {
+        renderer.pixel(x, y).unwrap().a
+    }
+
+    #[test]
+    fn test_box_blur_radii_match_variance() {
+        for sigma in [1.0_f32, 2.5, 4.0, 10.0] {
+            // Variance of a box of size n is (n^2 - 1) / 12
+            let variance: f32 = box_blur_radii(sigma)
+                .iter()
+                .map(|&radius| {
+                    let size = (2 * radius + 1) as f32;
+                    (size * size - 1.0) / 12.0
+                })
+                .sum();
+
+            assert!(
+                (variance.sqrt() - sigma).abs() < 0.5,
+                "Sigma {} approximated as {}",
+                sigma,
+                variance.sqrt()
+            );
+        }
+    }
+
+    #[test]
+    fn test_blur_keeps_total_alpha() {
+        let (width, height) = (32, 32);
+        let mut alpha = vec![0.0; width * height];
+        alpha[16 * width + 16] = 1.0;
+
+        blur_alpha(&mut alpha, width, height, 2.0);
+
+        let total: f32 = alpha.iter().sum();
+        assert!((total - 1.0).abs() < 1e-3, "Blur lost energy: {}", total);
+        assert!(alpha[16 * width + 16] < 0.1);
+        assert!(alpha[16 * width + 18] > 0.0);
+    }
+
+    #[test]
+    fn test_drop_shadow() {
+        let rect = Rect::new(16.0, 16.0, 32.0, 32.0);
+        let shadow = Shadow::new(Vec2::new(4.0, 4.0), 8.0, 0.0, Color::BLACK);
+        let renderer = render_shadow(rect, CornerRadius::zero(), shadow);
+
+        // Shadow is hidden behind the rect
+        assert_eq!(renderer.pixel(32, 32), Some(Color::TRANSPARENT));
+
+        // Offset shadow is stronger on the bottom right than on the top left
+        assert!(alpha(&renderer, 32, 50) > alpha(&renderer, 32, 14));
+        assert!(alpha(&renderer, 50, 32) > alpha(&renderer, 14, 32));
+
+        // Shadow fades out with the distance
+        assert!(alpha(&renderer, 32, 50) > alpha(&renderer, 32, 54));
+        assert!(alpha(&renderer, 32, 54) > alpha(&renderer, 32, 58));
+        assert_eq!(alpha(&renderer, 32, 63), 0.0);
+    }
+
+    #[test]
+    fn test_shadow_edge_is_half_covered() {
+        // Gaussian blur of a straight edge has half of the coverage on the edge
+        let rect = Rect::new(-64.0, -64.0, 96.0, 192.0);
+        let shadow = Shadow::new(Vec2::new(8.0, 0.0), 8.0, 0.0, Color::BLACK);
+        let renderer = render_shadow(rect, CornerRadius::zero(), shadow);
+
+        // Shadow edge runs along x = 40, between pixels 39 and 40
+        let edge = (alpha(&renderer, 39, 32) + alpha(&renderer, 40, 32)) / 2.0;
+        assert!((edge - 0.5).abs() < 0.05, "Edge coverage: {}", edge);
+    }
+
+    #[test]
+    fn test_hard_shadow_with_spread() {
+        let rect = Rect::new(16.0, 16.0, 32.0, 32.0);
+        let shadow = Shadow::new(Vec2::zero(), 0.0, 4.0, Color::RED);
+        let renderer = render_shadow(rect, CornerRadius::zero(), shadow);
+
+        assert_eq!(renderer.pixel(13, 32), Some(Color::RED));
+        assert_eq!(renderer.pixel(11, 32), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(16, 32), Some(Color::TRANSPARENT));
+    }
+
+    #[test]
+    fn test_rounded_shadow() {
+        let rect = Rect::new(16.0, 16.0, 32.0, 32.0);
+        let shadow = Shadow::new(Vec2::zero(), 0.0, 4.0, Color::RED);
+        let renderer = render_shadow(rect, CornerRadius::all(8.0), shadow);
+
+        // Spread corner has a radius of 12, so the outer corner stays empty
+        assert_eq!(renderer.pixel(12, 12), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(13, 32), Some(Color::RED));
+
+        // Shadow fills the area cut off by the rect's rounded corner
+        assert_eq!(renderer.pixel(17, 17), Some(Color::RED));
+    }
+
+    #[test]
+    fn test_inset_shadow() {
+        let rect = Rect::new(16.0, 16.0, 32.0, 32.0);
+        let shadow = Shadow::new(Vec2::new(0.0, 4.0), 4.0, 0.0, Color::BLACK).with_inset(true);
+        let renderer = render_shadow(rect, CornerRadius::zero(), shadow);
+
+        // Nothing leaks outside of the rect
+        assert_eq!(renderer.pixel(32, 14), Some(Color::TRANSPARENT));
+        assert_eq!(renderer.pixel(14, 32), Some(Color::TRANSPARENT));
+
+        // Shadow darkens the top edge, pushed down by the offset, and leaves the center clear
+        assert!(alpha(&renderer, 32, 17) > 0.9);
+        assert!(alpha(&renderer, 32, 17) > alpha(&renderer, 32, 46));
+        assert_eq!(alpha(&renderer, 32, 34), 0.0);
+    }
+
+    #[test]
+    fn test_huge_shadows_stay_within_the_target() {
+        let rect = Rect::new(16.0, 16.0, 32.0, 32.0);
+        for blur in [f32::INFINITY, 1e9, 1e5] {
+            let renderer =
+                render_shadow(rect, CornerRadius::zero(), Shadow::glow(blur, Color::BLACK));
+
+            // Capped blur still spreads a faint, even shadow around the rect
+            let edge = alpha(&renderer, 0, 32);
+            assert!(edge > 0.0 && edge < 0.5, "Blur {}: {}", blur, edge);
+
+            let inset = Shadow::glow(blur, Color::BLACK).with_inset(true);
+            render_shadow(rect, CornerRadius::zero(), inset);
+        }
+
+        let far = Shadow::new(Vec2::new(3e9, 3e9), 4.0, 0.0, Color::BLACK);
+        let renderer = render_shadow(rect, CornerRadius::zero(), far);
+        assert!(renderer.data().iter().all(|&byte| byte == 0));
+
+        let far = Shadow::new(Vec2::new(-3e9, 3e9), 4.0, 1e30, Color::BLACK).with_inset(true);
+        render_shadow(rect, CornerRadius::zero(), far);
+    }
+}
@@ -1,6 +1,14 @@
-//! Padding for rectangle, square components
+//! Box model styles (padding, margin, border, corner radius, shadow) for rectangle, square
+//! components
 
+pub(crate) mod parse;
+
+use std::time::Duration;
+
+use crate::animation::{Animated, Interpolate, Transition};
 use crate::color::Color;
+use crate::math::{Rect, Size, Vec2};
+use crate::paint::Paint;
 
 /// Padding inside of rectangle, square components
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -268,17 +276,252 @@ impl Padding {
     }
 }
 
+/// Margin outside of rectangle, square components
+///
+/// # Notes
+///
+/// - Space kept free around the component, where [`Padding`] is kept free inside of it
+/// - Values lower than 0.0, or `f32::NAN` will be set to 0.0, like [`Padding`]
+/// - `f32::INFINITY` is preserved as valid margin.
+///
+/// # Examples
+/// ```
+/// use ho_gui::style::Margin;
+///
+/// // Card separated from its neighbours by 8 pixels, and from the header by 16
+/// let margin = Margin::new(8.0, 8.0, 16.0, 8.0);
+///
+/// assert_eq!(margin.left, 8.0);
+/// assert_eq!(margin.top, 16.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Margin {
+    /// Left margin
+    pub left: f32,
+
+    /// Right margin
+    pub right: f32,
+
+    /// Top margin
+    pub top: f32,
+
+    /// Bottom margin
+    pub bottom: f32,
+}
+
+impl Margin {
+    /// Create Margin object
+    ///
+    /// # Notes
+    ///
+    /// - Values lower than 0.0, or `f32::NAN` will be set to 0.0
+    /// - `f32::INFINITY` is preserved as valid margin.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - Left margin
+    /// * `right` - Right margin
+    /// * `top` - Top margin
+    /// * `bottom` - Bottom margin
+    ///
+    /// # Returns
+    ///
+    /// Returns `Margin` with specified `(l, r, t, b)` margin properties
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::style::Margin;
+    ///
+    /// let margin = Margin::new(1.0, f32::NAN, 1.0, -1.0);
+    ///
+    /// assert_eq!(margin.left, 1.0);
+    /// assert_eq!(margin.right, 0.0); // NaN set to 0.0
+    /// assert_eq!(margin.top, 1.0);
+    /// assert_eq!(margin.bottom, 0.0); // values under 0.0 set to 0.0
+    /// ```
+    pub const fn new(left: f32, right: f32, top: f32, bottom: f32) -> Self {
+        Self {
+            left: Padding::to_valid(left),
+            right: Padding::to_valid(right),
+            top: Padding::to_valid(top),
+            bottom: Padding::to_valid(bottom),
+        }
+    }
+
+    /// Create a Margin object with all sides (left, right, top, bottom) set to the same value
+    ///
+    /// # Notes
+    ///
+    /// - Values lower than 0.0, or `f32::NAN` will be set to 0.0
+    /// - `f32::INFINITY` is preserved as valid margin.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Margin value, which will be set to all sides
+    ///
+    /// # Returns
+    ///
+    /// Returns `Margin { left: value, right: value, top: value, bottom: value }`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::style::Margin;
+    ///
+    /// let margin = Margin::all(4.0);
+    ///
+    /// // every side set to same value (4.0)
+    /// assert_eq!(margin, Margin::new(4.0, 4.0, 4.0, 4.0));
+    ///
+    /// // f32::NAN and values under 0.0 set to 0.0
+    /// assert_eq!(Margin::all(f32::NAN), Margin::zero());
+    /// assert_eq!(Margin::all(-4.0), Margin::zero());
+    /// ```
+    pub const fn all(value: f32) -> Self {
+        Self::new(value, value, value, value)
+    }
+
+    /// Create a Margin object with only horizontal margins (left, right)
+    ///
+    /// # Notes
+    ///
+    /// - Vertical margins (top, bottom) will be set to 0.0
+    /// - `f32::NAN` or values under 0.0 will be set to 0.0
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Margin value, which will be set to horizontal sides (left, right)
+    ///
+    /// # Returns
+    ///
+    /// Returns `Margin { left: value, right: value, top: 0.0, bottom: 0.0 }`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::style::Margin;
+    ///
+    /// let margin = Margin::horizontal(4.0);
+    ///
+    /// assert_eq!(margin.left, 4.0);
+    /// assert_eq!(margin.right, 4.0);
+    /// assert_eq!(margin.top, 0.0);
+    /// assert_eq!(margin.bottom, 0.0);
+    /// ```
+    pub const fn horizontal(value: f32) -> Self {
+        Self::new(value, value, 0.0, 0.0)
+    }
+
+    /// Create a Margin object with only vertical margins (top, bottom)
+    ///
+    /// # Notes
+    ///
+    /// - Horizontal margins (left, right) will be set to 0.0
+    /// - `f32::NAN` or values under 0.0 will be set to 0.0
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Margin value, which will be set to vertical sides (top, bottom)
+    ///
+    /// # Returns
+    ///
+    /// Returns `Margin { left: 0.0, right: 0.0, top: value, bottom: value }`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::style::Margin;
+    ///
+    /// let margin = Margin::vertical(4.0);
+    ///
+    /// assert_eq!(margin.left, 0.0);
+    /// assert_eq!(margin.right, 0.0);
+    /// assert_eq!(margin.top, 4.0);
+    /// assert_eq!(margin.bottom, 4.0);
+    /// ```
+    pub const fn vertical(value: f32) -> Self {
+        Self::new(0.0, 0.0, value, value)
+    }
+
+    /// Create a Margin object with specified horizontal (left, right), and vertical margins
+    /// (top, bottom)
+    ///
+    /// # Notes
+    ///
+    /// - `f32::NAN` or values under 0.0 will be set to 0.0
+    ///
+    /// # Arguments
+    ///
+    /// * `horizontal` - Horizontal (left, right) margin value
+    /// * `vertical` - Vertical (top, bottom) margin value
+    ///
+    /// # Returns
+    ///
+    /// Returns `Margin { left: horizontal, right: horizontal, top: vertical, bottom: vertical }`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::style::Margin;
+    ///
+    /// let margin = Margin::symmetric(1.0, 2.0);
+    ///
+    /// assert_eq!(margin, Margin::new(1.0, 1.0, 2.0, 2.0));
+    /// assert_eq!(Margin::symmetric(-1.0, f32::NAN), Margin::zero());
+    /// ```
+    pub const fn symmetric(horizontal: f32, vertical: f32) -> Self {
+        Self::new(horizontal, horizontal, vertical, vertical)
+    }
+
+    /// Create a Margin object with all sides (left, right, top, bottom) set to 0.0
+    ///
+    /// # Returns
+    ///
+    /// Returns `Margin { left: 0.0, right: 0.0, top: 0.0, bottom: 0.0 }`
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::style::Margin;
+    ///
+    /// let margin = Margin::zero();
+    ///
+    /// assert_eq!(margin, Margin::all(0.0));
+    /// ```
+    pub const fn zero() -> Self {
+        Self::all(0.0)
+    }
+}
+
+/// Line style of a [`Border`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u8)]
+pub enum BorderStyle {
+    /// Single continuous line
+    #[default]
+    Solid,
+
+    /// Dashes twice as long as the border width, separated by gaps of the border width
+    Dashed,
+
+    /// Round dots with the border width as diameter, separated by gaps of the border width
+    Dotted,
+
+    /// Two parallel lines, each a third of the border width, with a gap of the same size
+    Double,
+}
+
 /// Border property for UI components
 ///
 /// # Notes
 ///
-/// More properties (radius, dot-lined ...) will be supported in future release
+/// - Corner rounding is set separately with [`CornerRadius`]
+/// - Use [`Borders`] for different borders per side
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)] // Memory layout consistency
 pub struct Border {
     /// Border width
     pub width: f32,
 
+    /// Border line style. Placed before `color` to fit in its alignment padding.
+    pub style: BorderStyle,
+
     /// Border color
     pub color: Color,
 }
@@ -289,7 +532,7 @@ impl Border {
     /// # Notes
     ///
     /// - width with `NaN` or negative value will be set to 0.0
-    /// - More properties (radius, dot-lined ...) will be supported in future release
+    /// - Style is `BorderStyle::Solid`
     ///
     /// # Arguments
     ///
@@ -329,7 +572,11 @@ impl Border {
     /// ```
     pub const fn new(width: f32, color: Color) -> Self {
         let width = Self::to_valid(width);
-        Self { width, color }
+        Self {
+            width,
+            style: BorderStyle::Solid,
+            color,
+        }
     }
 
     /// Create empty, transparent border
@@ -360,7 +607,6 @@ impl Border {
     ///
     /// - It behaves just as same as Border::new()
     /// - Width with `NaN` or negative value will be set to 0.0
-    /// - More properties (radius, dot-lined ...) will be supported in future release
     ///
     /// # Arguments
     ///
@@ -402,93 +648,1014 @@ impl Border {
         Self::new(width, color)
     }
 
+    /// Create dashed Border with given width and color
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::style::{Border, BorderStyle};
+    ///
+    /// let border = Border::dashed(2.0, Color::BLACK);
+    ///
+    /// assert_eq!(border.style, BorderStyle::Dashed);
+    /// assert_eq!(border, Border::new(2.0, Color::BLACK).with_style(BorderStyle::Dashed));
+    /// ```
+    pub const fn dashed(width: f32, color: Color) -> Self {
+        Self::new(width, color).with_style(BorderStyle::Dashed)
+    }
+
+    /// Create dotted Border with given width and color
+    pub const fn dotted(width: f32, color: Color) -> Self {
+        Self::new(width, color).with_style(BorderStyle::Dotted)
+    }
+
+    /// Create double-line Border with given width and color
+    ///
+    /// # Notes
+    ///
+    /// - `width` is the total width of both lines and the gap between them
+    pub const fn double(width: f32, color: Color) -> Self {
+        Self::new(width, color).with_style(BorderStyle::Double)
+    }
+
+    /// Return the Border with its line style replaced by `style`
+    pub const fn with_style(mut self, style: BorderStyle) -> Self {
+        self.style = style;
+        self
+    }
+
     const fn to_valid(x: f32) -> f32 {
         if x.is_nan() || x < 0.0 { 0.0 } else { x }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    mod padding {
-        use super::*;
-
-        #[test]
-        fn test_new() {
-            let pad = Padding::new(f32::EPSILON, f32::INFINITY, -f32::EPSILON, f32::NAN);
+/// Independent borders for each side of rectangle components
+///
+/// # Notes
+///
+/// - Sides meet at the diagonal from the outer corner to the inner corner, like CSS
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Borders {
+    /// Top border
+    pub top: Border,
 
-            assert_eq!(
-                pad.left,
-                f32::EPSILON,
-                "Valid value should remain unchanged. value: {}",
-                pad.left
-            );
+    /// Right border
+    pub right: Border,
 
-            assert_eq!(
-                pad.right,
-                f32::INFINITY,
-                "Positive infinity should remain unchanged. value: {}",
-                pad.right
-            );
+    /// Bottom border
+    pub bottom: Border,
 
-            assert_eq!(
-                pad.top, 0.0,
-                "Negative value should set to 0.0. value: {}",
-                pad.top
-            );
+    /// Left border
+    pub left: Border,
+}
 
-            assert_eq!(
-                pad.bottom, 0.0,
-                "NaN should set to 0.0. value: {}",
-                pad.bottom
-            );
+impl Borders {
+    /// Create Borders with a border per side, clockwise from the top
+    ///
+    /// # Arguments
+    ///
+    /// * `top` - Top border
+    /// * `right` - Right border
+    /// * `bottom` - Bottom border
+    /// * `left` - Left border
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::style::{Border, Borders};
+    ///
+    /// // Underlined tab
+    /// let borders = Borders::new(
+    ///     Border::none(),
+    ///     Border::none(),
+    ///     Border::new(2.0, Color::BLUE),
+    ///     Border::none(),
+    /// );
+    ///
+    /// assert_eq!(borders.bottom.width, 2.0);
+    /// assert!(!borders.is_uniform());
+    /// ```
+    pub const fn new(top: Border, right: Border, bottom: Border, left: Border) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
         }
+    }
 
-        #[test]
-        fn test_all() {
-            let valid = Padding::all(f32::EPSILON);
-            let infinity = Padding::all(f32::INFINITY);
-            let negative = Padding::all(-f32::EPSILON);
-            let nan = Padding::all(f32::NAN);
+    /// Create Borders with the same border on every side
+    pub const fn all(border: Border) -> Self {
+        Self::new(border, border, border, border)
+    }
 
-            assert_eq!(
-                (valid.left, valid.right, valid.top, valid.bottom),
-                (f32::EPSILON, f32::EPSILON, f32::EPSILON, f32::EPSILON),
-                "Valid value should remain unchanged. value: {:?}",
-                valid,
-            );
+    /// Create Borders with `horizontal` on the left and right sides, and `vertical` on the top
+    /// and bottom sides
+    pub const fn symmetric(horizontal: Border, vertical: Border) -> Self {
+        Self::new(vertical, horizontal, vertical, horizontal)
+    }
 
-            assert_eq!(
-                (infinity.left, infinity.right, infinity.top, infinity.bottom),
-                (f32::INFINITY, f32::INFINITY, f32::INFINITY, f32::INFINITY),
-                "Positive infinity should remain unchanged. value: {:?}",
-                infinity,
-            );
+    /// Create Borders without any visible side
+    pub const fn none() -> Self {
+        Self::all(Border::none())
+    }
 
-            assert_eq!(
-                (negative.left, negative.right, negative.top, negative.bottom),
-                (0.0, 0.0, 0.0, 0.0),
-                "Negative value should set to 0.0. value: {:?}",
-                negative,
-            );
+    /// Return the Borders with the top side replaced
+    pub const fn with_top(mut self, border: Border) -> Self {
+        self.top = border;
+        self
+    }
 
-            assert_eq!(
-                (nan.left, nan.right, nan.top, nan.bottom),
-                (0.0, 0.0, 0.0, 0.0),
-                "NaN should set to 0.0. value: {:?}",
-                nan,
-            );
-        }
+    /// Return the Borders with the right side replaced
+    pub const fn with_right(mut self, border: Border) -> Self {
+        self.right = border;
+        self
+    }
 
-        #[test]
-        fn test_horizontal() {
-            let valid = Padding::horizontal(f32::EPSILON);
-            let infinity = Padding::horizontal(f32::INFINITY);
-            let negative = Padding::horizontal(-f32::EPSILON);
-            let nan = Padding::horizontal(f32::NAN);
+    /// Return the Borders with the bottom side replaced
+    pub const fn with_bottom(mut self, border: Border) -> Self {
+        self.bottom = border;
+        self
+    }
 
-            // check if horizontal values are set as specified
+    /// Return the Borders with the left side replaced
+    pub const fn with_left(mut self, border: Border) -> Self {
+        self.left = border;
+        self
+    }
+
+    /// Width of every side as a [`Padding`], which is the space the borders take inside of a box
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::style::{Border, Borders, Padding};
+    ///
+    /// let borders = Borders::all(Border::new(1.0, Color::BLACK))
+    ///     .with_left(Border::new(4.0, Color::BLUE));
+    ///
+    /// assert_eq!(borders.widths(), Padding::new(4.0, 1.0, 1.0, 1.0));
+    /// ```
+    pub const fn widths(&self) -> Padding {
+        Padding::new(
+            self.left.width,
+            self.right.width,
+            self.top.width,
+            self.bottom.width,
+        )
+    }
+
+    /// Check if every side has the same width, style and color
+    pub fn is_uniform(&self) -> bool {
+        self.top == self.right && self.top == self.bottom && self.top == self.left
+    }
+}
+
+impl From<Border> for Borders {
+    fn from(border: Border) -> Self {
+        Self::all(border)
+    }
+}
+
+/// Corner rounding radii for rectangle components
+///
+/// # Notes
+///
+/// - Radii larger than the rectangle are scaled down when drawn, see [`CornerRadius::fit`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[repr(C)]
+pub struct CornerRadius {
+    /// Top left corner radius
+    pub top_left: f32,
+
+    /// Top right corner radius
+    pub top_right: f32,
+
+    /// Bottom right corner radius
+    pub bottom_right: f32,
+
+    /// Bottom left corner radius
+    pub bottom_left: f32,
+}
+
+impl CornerRadius {
+    /// Create CornerRadius with a radius per corner, clockwise from the top left
+    ///
+    /// # Notes
+    ///
+    /// - Values lower than 0.0, or `f32::NAN` will be set to 0.0
+    ///
+    /// # Arguments
+    ///
+    /// * `top_left` - Top left corner radius
+    /// * `top_right` - Top right corner radius
+    /// * `bottom_right` - Bottom right corner radius
+    /// * `bottom_left` - Bottom left corner radius
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::style::CornerRadius;
+    ///
+    /// let radius = CornerRadius::new(1.0, f32::NAN, 3.0, -4.0);
+    ///
+    /// assert_eq!(radius.top_left, 1.0);
+    /// assert_eq!(radius.top_right, 0.0); // NaN set to 0.0
+    /// assert_eq!(radius.bottom_right, 3.0);
+    /// assert_eq!(radius.bottom_left, 0.0); // values under 0.0 set to 0.0
+    /// ```
+    pub const fn new(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+        Self {
+            top_left: Self::to_valid(top_left),
+            top_right: Self::to_valid(top_right),
+            bottom_right: Self::to_valid(bottom_right),
+            bottom_left: Self::to_valid(bottom_left),
+        }
+    }
+
+    /// Create CornerRadius with every corner set to the same radius
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::style::CornerRadius;
+    ///
+    /// let radius = CornerRadius::all(8.0);
+    ///
+    /// assert_eq!(radius, CornerRadius::new(8.0, 8.0, 8.0, 8.0));
+    /// ```
+    pub const fn all(radius: f32) -> Self {
+        Self::new(radius, radius, radius, radius)
+    }
+
+    /// Create CornerRadius rounding only the top corners, e.g. for tabs
+    pub const fn top(radius: f32) -> Self {
+        Self::new(radius, radius, 0.0, 0.0)
+    }
+
+    /// Create CornerRadius rounding only the bottom corners
+    pub const fn bottom(radius: f32) -> Self {
+        Self::new(0.0, 0.0, radius, radius)
+    }
+
+    /// Create CornerRadius rounding only the left corners
+    pub const fn left(radius: f32) -> Self {
+        Self::new(radius, 0.0, 0.0, radius)
+    }
+
+    /// Create CornerRadius rounding only the right corners
+    pub const fn right(radius: f32) -> Self {
+        Self::new(0.0, radius, radius, 0.0)
+    }
+
+    /// Create CornerRadius with square corners
+    pub const fn zero() -> Self {
+        Self::all(0.0)
+    }
+
+    /// Check if every corner is square
+    pub const fn is_zero(&self) -> bool {
+        self.top_left == 0.0
+            && self.top_right == 0.0
+            && self.bottom_right == 0.0
+            && self.bottom_left == 0.0
+    }
+
+    /// Scale radii down so that adjacent corners don't overlap on a box of `size`
+    ///
+    /// # Notes
+    ///
+    /// - Follows CSS: every radius is scaled by the same factor, so the shape keeps its proportions
+    /// - Radii which already fit are returned unchanged
+    /// - `f32::INFINITY` radii make a pill
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::math::Size;
+    /// use ho_gui::style::CornerRadius;
+    ///
+    /// // Pill shape: 20 + 20 radii on a 30 high box are scaled down to 15
+    /// let radius = CornerRadius::all(20.0).fit(Size::new(100.0, 30.0));
+    ///
+    /// assert_eq!(radius, CornerRadius::all(15.0));
+    /// ```
+    pub fn fit(&self, size: Size) -> Self {
+        // Infinite radii are clamped first, so they produce a pill instead of a NaN scale
+        let longest = size.width.max(size.height).max(0.0);
+        let finite = |r: f32| if r.is_infinite() { longest } else { r };
+        let clamped = Self::new(
+            finite(self.top_left),
+            finite(self.top_right),
+            finite(self.bottom_right),
+            finite(self.bottom_left),
+        );
+
+        let ratio = |length: f32, a: f32, b: f32| {
+            if a + b > length {
+                length.max(0.0) / (a + b)
+            } else {
+                1.0
+            }
+        };
+
+        let scale = ratio(size.width, clamped.top_left, clamped.top_right)
+            .min(ratio(size.width, clamped.bottom_left, clamped.bottom_right))
+            .min(ratio(size.height, clamped.top_left, clamped.bottom_left))
+            .min(ratio(size.height, clamped.top_right, clamped.bottom_right));
+
+        if scale >= 1.0 {
+            return clamped;
+        }
+
+        Self::new(
+            clamped.top_left * scale,
+            clamped.top_right * scale,
+            clamped.bottom_right * scale,
+            clamped.bottom_left * scale,
+        )
+    }
+
+    /// Shrink every radius by `amount`, e.g. for the inner edge of a border
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::style::CornerRadius;
+    ///
+    /// let inner = CornerRadius::new(8.0, 2.0, 0.0, 4.0).shrink(4.0);
+    ///
+    /// assert_eq!(inner, CornerRadius::new(4.0, 0.0, 0.0, 0.0));
+    /// ```
+    pub const fn shrink(&self, amount: f32) -> Self {
+        Self::new(
+            self.top_left - amount,
+            self.top_right - amount,
+            self.bottom_right - amount,
+            self.bottom_left - amount,
+        )
+    }
+
+    const fn to_valid(x: f32) -> f32 {
+        if x.is_nan() || x < 0.0 { 0.0 } else { x }
+    }
+}
+
+/// Box shadow for rectangle components, also used for glows
+///
+/// # Notes
+///
+/// - Shadows follow the [`CornerRadius`] of the box they are drawn for
+/// - Blur is a radius like CSS `box-shadow`, which corresponds to a Gaussian with a standard
+///   deviation of half of the blur
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Shadow {
+    /// Offset of the shadow from the box
+    pub offset: Vec2,
+
+    /// Blur radius. 0.0 draws a hard edged shadow.
+    pub blur: f32,
+
+    /// Distance the shadow grows (or shrinks, if negative) before it is blurred
+    pub spread: f32,
+
+    /// Draw the shadow inside of the box instead of around it
+    pub inset: bool,
+
+    /// Shadow color
+    pub color: Color,
+}
+
+impl Shadow {
+    /// Create a drop Shadow drawn around the box
+    ///
+    /// # Notes
+    ///
+    /// - `blur` with `NaN` or negative value will be set to 0.0
+    /// - `spread` with `NaN` will be set to 0.0. Negative spread is valid and shrinks the shadow.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Offset of the shadow from the box
+    /// * `blur` - Blur radius
+    /// * `spread` - Distance the shadow grows before it is blurred
+    /// * `color` - Shadow color
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::math::Vec2;
+    /// use ho_gui::style::Shadow;
+    ///
+    /// let shadow = Shadow::new(Vec2::new(0.0, 4.0), -8.0, -2.0, Color::BLACK);
+    ///
+    /// assert_eq!(shadow.blur, 0.0); // values under 0.0 set to 0.0
+    /// assert_eq!(shadow.spread, -2.0); // negative spread shrinks the shadow
+    /// assert!(!shadow.inset);
+    /// ```
+    pub const fn new(offset: Vec2, blur: f32, spread: f32, color: Color) -> Self {
+        Self {
+            offset,
+            blur: if blur.is_nan() || blur < 0.0 {
+                0.0
+            } else {
+                blur
+            },
+            spread: if spread.is_nan() { 0.0 } else { spread },
+            inset: false,
+            color,
+        }
+    }
+
+    /// Create a glow: a Shadow without offset, blurred equally around the box
+    pub const fn glow(blur: f32, color: Color) -> Self {
+        Self::new(Vec2::zero(), blur, 0.0, color)
+    }
+
+    /// Create a Shadow which draws nothing
+    pub const fn none() -> Self {
+        Self::new(Vec2::zero(), 0.0, 0.0, Color::TRANSPARENT)
+    }
+
+    /// Return the Shadow drawn inside of the box (`true`) or around it (`false`)
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::math::Vec2;
+    /// use ho_gui::style::Shadow;
+    ///
+    /// // Pressed button look
+    /// let pressed = Shadow::new(Vec2::new(0.0, 2.0), 4.0, 0.0, Color::BLACK).with_inset(true);
+    ///
+    /// assert!(pressed.inset);
+    /// ```
+    pub const fn with_inset(mut self, inset: bool) -> Self {
+        self.inset = inset;
+        self
+    }
+}
+
+/// Box model style of a widget: margin, borders, padding, background, corner radius and size
+/// constraints
+///
+/// # Notes
+///
+/// - Boxes are laid out from the outside in: margin, then borders, then padding, then content
+/// - `min_size` and `max_size` constrain the border box, like CSS `box-sizing: border-box`
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::Color;
+/// use ho_gui::math::Rect;
+/// use ho_gui::style::{Border, BoxStyle, CornerRadius, Margin, Padding};
+///
+/// let button = BoxStyle::new()
+///     .with_margin(Margin::all(4.0))
+///     .with_border(Border::new(1.0, Color::BLACK))
+///     .with_padding(Padding::symmetric(12.0, 6.0))
+///     .with_background(Color::WHITE)
+///     .with_corner_radius(CornerRadius::all(6.0));
+///
+/// let rects = button.layout(Rect::new(0.0, 0.0, 100.0, 40.0));
+///
+/// assert_eq!(rects.border, Rect::new(4.0, 4.0, 92.0, 32.0));
+/// assert_eq!(rects.padding, Rect::new(5.0, 5.0, 90.0, 30.0));
+/// assert_eq!(rects.content, Rect::new(17.0, 11.0, 66.0, 18.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxStyle {
+    /// Space outside of the borders
+    pub margin: Margin,
+
+    /// Border of every side
+    pub borders: Borders,
+
+    /// Space between the borders and the content
+    pub padding: Padding,
+
+    /// Paint filling the padding box
+    pub background: Paint,
+
+    /// Corner radii of the border box
+    pub corner_radius: CornerRadius,
+
+    /// Minimum size of the border box
+    pub min_size: Size,
+
+    /// Maximum size of the border box
+    pub max_size: Size,
+}
+
+impl Default for BoxStyle {
+    fn default() -> Self {
+        Self {
+            margin: Margin::zero(),
+            borders: Borders::none(),
+            padding: Padding::zero(),
+            background: Paint::default(),
+            corner_radius: CornerRadius::zero(),
+            min_size: Size::zero(),
+            max_size: Size::new(f32::INFINITY, f32::INFINITY),
+        }
+    }
+}
+
+impl BoxStyle {
+    /// Create BoxStyle without margin, borders, padding, background or size constraints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the BoxStyle with `margin`
+    pub fn with_margin(mut self, margin: Margin) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Return the BoxStyle with the same `border` on every side
+    pub fn with_border(mut self, border: Border) -> Self {
+        self.borders = Borders::all(border);
+        self
+    }
+
+    /// Return the BoxStyle with per-side `borders`
+    pub fn with_borders(mut self, borders: Borders) -> Self {
+        self.borders = borders;
+        self
+    }
+
+    /// Return the BoxStyle with `padding`
+    pub fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Return the BoxStyle with `background`
+    pub fn with_background(mut self, background: impl Into<Paint>) -> Self {
+        self.background = background.into();
+        self
+    }
+
+    /// Return the BoxStyle with `corner_radius`
+    pub fn with_corner_radius(mut self, corner_radius: CornerRadius) -> Self {
+        self.corner_radius = corner_radius;
+        self
+    }
+
+    /// Return the BoxStyle with `min_size` of the border box
+    pub fn with_min_size(mut self, min_size: Size) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Return the BoxStyle with `max_size` of the border box
+    pub fn with_max_size(mut self, max_size: Size) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Resolve the box model inside of `outer`, the margin box given to the widget by layout
+    ///
+    /// # Notes
+    ///
+    /// - The border box is clamped to `min_size` and `max_size` and kept at the top left of
+    ///   the space inside of the margin. It may overflow `outer` to satisfy `min_size`.
+    /// - Inner boxes never get a negative size. Space which is too small is taken from the
+    ///   padding and then the content.
+    ///
+    /// # Returns
+    ///
+    /// Margin, border, padding and content rects, from the outside in
+    pub fn layout(&self, outer: Rect) -> BoxRects {
+        let margin = self.margin;
+        let available = deflate(outer, margin.left, margin.right, margin.top, margin.bottom);
+        let border = Rect {
+            pos: available.pos,
+            size: self.constrain(available.size),
+        };
+
+        let widths = self.borders.widths();
+        let padding = deflate(border, widths.left, widths.right, widths.top, widths.bottom);
+
+        let pad = self.padding;
+        let content = deflate(padding, pad.left, pad.right, pad.top, pad.bottom);
+
+        BoxRects {
+            margin: outer,
+            border,
+            padding,
+            content,
+        }
+    }
+
+    /// Size of the margin box needed to fit `content`
+    ///
+    /// # Notes
+    ///
+    /// - Inverse of [`BoxStyle::layout`], used by layouts to measure widgets
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::math::Size;
+    /// use ho_gui::style::{Border, BoxStyle, Margin, Padding};
+    ///
+    /// let style = BoxStyle::new()
+    ///     .with_margin(Margin::all(2.0))
+    ///     .with_border(Border::new(1.0, Color::BLACK))
+    ///     .with_padding(Padding::all(4.0))
+    ///     .with_min_size(Size::new(40.0, 0.0));
+    ///
+    /// // 10 + 2 * (4 + 1) = 20 high border box, widened to the 40 minimum, plus margin
+    /// assert_eq!(style.outer_size(Size::new(10.0, 10.0)), Size::new(44.0, 24.0));
+    /// ```
+    pub fn outer_size(&self, content: Size) -> Size {
+        let widths = self.borders.widths();
+        let (pad, margin) = (self.padding, self.margin);
+
+        let border = self.constrain(Size::new(
+            content.width + pad.left + pad.right + widths.left + widths.right,
+            content.height + pad.top + pad.bottom + widths.top + widths.bottom,
+        ));
+
+        Size::new(
+            border.width + margin.left + margin.right,
+            border.height + margin.top + margin.bottom,
+        )
+    }
+
+    /// Clamp a border box size to `min_size` and `max_size`. The minimum wins over the maximum.
+    fn constrain(&self, size: Size) -> Size {
+        Size::new(
+            size.width.min(self.max_size.width).max(self.min_size.width),
+            size.height
+                .min(self.max_size.height)
+                .max(self.min_size.height),
+        )
+    }
+}
+
+/// Rects of a resolved box model, see [`BoxStyle::layout`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxRects {
+    /// Margin box, the whole space given to the widget
+    pub margin: Rect,
+
+    /// Border box, drawn with background and borders
+    pub border: Rect,
+
+    /// Padding box, inside of the borders
+    pub padding: Rect,
+
+    /// Content box, where the widget draws its content
+    pub content: Rect,
+}
+
+/// Shrink `rect` by the given amounts per side, without producing a negative size
+fn deflate(rect: Rect, left: f32, right: f32, top: f32, bottom: f32) -> Rect {
+    let width = (rect.size.width - left - right).max(0.0);
+    let height = (rect.size.height - top - bottom).max(0.0);
+
+    Rect::new(rect.left() + left, rect.top() + top, width, height)
+}
+
+/// Interaction state reported by a widget for the current frame
+///
+/// # Notes
+///
+/// - Flags can be combined, e.g. a focused button under the mouse is both `hovered` and `focused`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct WidgetState {
+    /// Mouse is over the widget
+    pub hovered: bool,
+
+    /// Widget is being pressed or dragged
+    pub active: bool,
+
+    /// Widget has keyboard focus
+    pub focused: bool,
+
+    /// Widget ignores input
+    pub disabled: bool,
+}
+
+impl WidgetState {
+    /// State of a widget without any interaction
+    pub const fn normal() -> Self {
+        Self {
+            hovered: false,
+            active: false,
+            focused: false,
+            disabled: false,
+        }
+    }
+
+    /// Return the state with the `hovered` flag set to `hovered`
+    pub const fn with_hovered(mut self, hovered: bool) -> Self {
+        self.hovered = hovered;
+        self
+    }
+
+    /// Return the state with the `active` flag set to `active`
+    pub const fn with_active(mut self, active: bool) -> Self {
+        self.active = active;
+        self
+    }
+
+    /// Return the state with the `focused` flag set to `focused`
+    pub const fn with_focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    /// Return the state with the `disabled` flag set to `disabled`
+    pub const fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// Style value (e.g. [`BoxStyle`] or text [`Color`]) which depends on the [`WidgetState`]
+///
+/// # Notes
+///
+/// - Only `normal` is required. States without an override fall back to the next matching state,
+///   and finally to `normal`.
+/// - When several flags are set, overrides are looked up in the order: disabled, active,
+///   hovered, focused. A disabled widget ignores the other flags.
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::Color;
+/// use ho_gui::style::{StateStyle, WidgetState};
+///
+/// let background = StateStyle::new(Color::WHITE)
+///     .with_hovered(Color::from_hex(0xEE_EE_EE))
+///     .with_active(Color::from_hex(0xDD_DD_DD));
+///
+/// let hovered = WidgetState::normal().with_hovered(true);
+/// let pressed = hovered.with_active(true);
+/// let focused = WidgetState::normal().with_focused(true);
+///
+/// assert_eq!(background.resolve(hovered), &Color::from_hex(0xEE_EE_EE));
+/// assert_eq!(background.resolve(pressed), &Color::from_hex(0xDD_DD_DD));
+/// // No focused override, so the normal color is used
+/// assert_eq!(background.resolve(focused), &Color::WHITE);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StateStyle<T> {
+    /// Value without any interaction, and fallback of every other state
+    pub normal: T,
+
+    /// Override while hovered
+    pub hovered: Option<T>,
+
+    /// Override while pressed or dragged
+    pub active: Option<T>,
+
+    /// Override while focused
+    pub focused: Option<T>,
+
+    /// Override while disabled
+    pub disabled: Option<T>,
+
+    /// Transition between the values of different states, see [`StateStyle::animate`]
+    pub transition: Transition,
+}
+
+impl<T> StateStyle<T> {
+    /// Create StateStyle using `normal` in every state
+    pub const fn new(normal: T) -> Self {
+        Self {
+            normal,
+            hovered: None,
+            active: None,
+            focused: None,
+            disabled: None,
+            transition: Transition::NONE,
+        }
+    }
+
+    /// Return the StateStyle with a hovered override
+    pub fn with_hovered(mut self, hovered: T) -> Self {
+        self.hovered = Some(hovered);
+        self
+    }
+
+    /// Return the StateStyle with an active override
+    pub fn with_active(mut self, active: T) -> Self {
+        self.active = Some(active);
+        self
+    }
+
+    /// Return the StateStyle with a focused override
+    pub fn with_focused(mut self, focused: T) -> Self {
+        self.focused = Some(focused);
+        self
+    }
+
+    /// Return the StateStyle with a disabled override
+    pub fn with_disabled(mut self, disabled: T) -> Self {
+        self.disabled = Some(disabled);
+        self
+    }
+
+    /// Return the StateStyle animating state changes with `transition`
+    pub fn with_transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// Value for the widget `state`
+    pub fn resolve(&self, state: WidgetState) -> &T {
+        if state.disabled {
+            return self.disabled.as_ref().unwrap_or(&self.normal);
+        }
+
+        [
+            (state.active, &self.active),
+            (state.hovered, &self.hovered),
+            (state.focused, &self.focused),
+        ]
+        .into_iter()
+        .find_map(|(set, value)| if set { value.as_ref() } else { None })
+        .unwrap_or(&self.normal)
+    }
+}
+
+impl<T: Clone> StateStyle<T> {
+    /// Return the StateStyle with a hovered override derived from the normal value, for
+    /// overriding only a part of it
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::style::{Border, BoxStyle, StateStyle, WidgetState};
+    ///
+    /// let button = StateStyle::new(
+    ///     BoxStyle::new()
+    ///         .with_border(Border::new(1.0, Color::BLACK))
+    ///         .with_background(Color::WHITE),
+    /// )
+    /// .with_hovered_from(|normal| normal.with_background(Color::from_hex(0xEE_EE_EE)));
+    ///
+    /// let hovered = button.resolve(WidgetState::normal().with_hovered(true));
+    ///
+    /// // Border is kept from the normal style
+    /// assert_eq!(hovered.borders, button.normal.borders);
+    /// ```
+    pub fn with_hovered_from(self, derive: impl FnOnce(T) -> T) -> Self {
+        let hovered = derive(self.normal.clone());
+        self.with_hovered(hovered)
+    }
+
+    /// Return the StateStyle with an active override derived from the normal value
+    pub fn with_active_from(self, derive: impl FnOnce(T) -> T) -> Self {
+        let active = derive(self.normal.clone());
+        self.with_active(active)
+    }
+
+    /// Return the StateStyle with a focused override derived from the normal value
+    pub fn with_focused_from(self, derive: impl FnOnce(T) -> T) -> Self {
+        let focused = derive(self.normal.clone());
+        self.with_focused(focused)
+    }
+
+    /// Return the StateStyle with a disabled override derived from the normal value
+    pub fn with_disabled_from(self, derive: impl FnOnce(T) -> T) -> Self {
+        let disabled = derive(self.normal.clone());
+        self.with_disabled(disabled)
+    }
+}
+
+impl<T: Interpolate + Clone + PartialEq> StateStyle<T> {
+    /// Value for the widget `state`, transitioning from the previous state over
+    /// [`StateStyle::transition`]
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - Current widget state
+    /// * `animation` - Animation state kept by the widget between frames, created with the
+    ///   normal value
+    /// * `delta` - Time since the last frame
+    ///
+    /// # Returns
+    ///
+    /// Value to draw this frame
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use ho_gui::animation::{Animated, Easing, Transition};
+    /// use ho_gui::color::Color;
+    /// use ho_gui::style::{StateStyle, WidgetState};
+    ///
+    /// let background = StateStyle::new(Color::BLACK)
+    ///     .with_hovered(Color::WHITE)
+    ///     .with_transition(Transition::new(Duration::from_millis(150), Easing::Linear));
+    /// let mut animation = Animated::new(background.normal);
+    ///
+    /// let hovered = WidgetState::normal().with_hovered(true);
+    /// let frame = Duration::from_millis(50);
+    ///
+    /// assert_eq!(background.animate(WidgetState::normal(), &mut animation, frame), Color::BLACK);
+    /// background.animate(hovered, &mut animation, frame);
+    /// background.animate(hovered, &mut animation, frame);
+    /// assert_eq!(background.animate(hovered, &mut animation, frame), Color::WHITE);
+    /// ```
+    pub fn animate(&self, state: WidgetState, animation: &mut Animated<T>, delta: Duration) -> T {
+        animation.update(self.resolve(state).clone(), self.transition, delta)
+    }
+}
+
+impl<T> From<T> for StateStyle<T> {
+    fn from(normal: T) -> Self {
+        Self::new(normal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod padding {
+        use super::*;
+
+        #[test]
+        fn test_new() {
+            let pad = Padding::new(f32::EPSILON, f32::INFINITY, -f32::EPSILON, f32::NAN);
+
+            assert_eq!(
+                pad.left,
+                f32::EPSILON,
+                "Valid value should remain unchanged. value: {}",
+                pad.left
+            );
+
+            assert_eq!(
+                pad.right,
+                f32::INFINITY,
+                "Positive infinity should remain unchanged. value: {}",
+                pad.right
+            );
+
+            assert_eq!(
+                pad.top, 0.0,
+                "Negative value should set to 0.0. value: {}",
+                pad.top
+            );
+
+            assert_eq!(
+                pad.bottom, 0.0,
+                "NaN should set to 0.0. value: {}",
+                pad.bottom
+            );
+        }
+
+        #[test]
+        fn test_all() {
+            let valid = Padding::all(f32::EPSILON);
+            let infinity = Padding::all(f32::INFINITY);
+            let negative = Padding::all(-f32::EPSILON);
+            let nan = Padding::all(f32::NAN);
+
+            assert_eq!(
+                (valid.left, valid.right, valid.top, valid.bottom),
+                (f32::EPSILON, f32::EPSILON, f32::EPSILON, f32::EPSILON),
+                "Valid value should remain unchanged. value: {:?}",
+                valid,
+            );
+
+            assert_eq!(
+                (infinity.left, infinity.right, infinity.top, infinity.bottom),
+                (f32::INFINITY, f32::INFINITY, f32::INFINITY, f32::INFINITY),
+                "Positive infinity should remain unchanged. value: {:?}",
+                infinity,
+            );
+
+            assert_eq!(
+                (negative.left, negative.right, negative.top, negative.bottom),
+                (0.0, 0.0, 0.0, 0.0),
+                "Negative value should set to 0.0. value: {:?}",
+                negative,
+            );
+
+            assert_eq!(
+                (nan.left, nan.right, nan.top, nan.bottom),
+                (0.0, 0.0, 0.0, 0.0),
+                "NaN should set to 0.0. value: {:?}",
+                nan,
+            );
+        }
+
+        #[test]
+        fn test_horizontal() {
+            let valid = Padding::horizontal(f32::EPSILON);
+            let infinity = Padding::horizontal(f32::INFINITY);
+            let negative = Padding::horizontal(-f32::EPSILON);
+            let nan = Padding::horizontal(f32::NAN);
+
+            // check if horizontal values are set as specified
             assert_eq!(
                 (valid.left, valid.right),
                 (f32::EPSILON, f32::EPSILON),
@@ -774,6 +1941,359 @@ mod tests {
             assert_eq!(CONST_SOLID.color, Color::BLUE);
         }
     }
+
+    mod borders {
+        use super::*;
+
+        #[test]
+        fn test_border_styles() {
+            assert_eq!(BorderStyle::default(), BorderStyle::Solid);
+            assert_eq!(Border::new(1.0, Color::BLACK).style, BorderStyle::Solid);
+            assert_eq!(Border::solid(1.0, Color::BLACK).style, BorderStyle::Solid);
+            assert_eq!(Border::dashed(1.0, Color::BLACK).style, BorderStyle::Dashed);
+            assert_eq!(Border::dotted(1.0, Color::BLACK).style, BorderStyle::Dotted);
+            assert_eq!(Border::double(1.0, Color::BLACK).style, BorderStyle::Double);
+
+            // Styled constructors validate width the same way
+            assert_eq!(Border::dashed(f32::NAN, Color::BLACK).width, 0.0);
+            assert_eq!(Border::double(-1.0, Color::BLACK).width, 0.0);
+        }
+
+        #[test]
+        fn test_sides() {
+            let thin = Border::new(1.0, Color::BLACK);
+            let thick = Border::dashed(3.0, Color::RED);
+
+            let borders = Borders::symmetric(thin, thick);
+            assert_eq!((borders.left, borders.right), (thin, thin));
+            assert_eq!((borders.top, borders.bottom), (thick, thick));
+
+            let borders = Borders::all(thin).with_top(thick);
+            assert_eq!(borders, Borders::new(thick, thin, thin, thin));
+            assert_eq!(Borders::none().with_right(thin).right, thin);
+            assert_eq!(Borders::none().with_bottom(thin).bottom, thin);
+            assert_eq!(Borders::none().with_left(thin).left, thin);
+        }
+
+        #[test]
+        fn test_uniform() {
+            let border = Border::new(1.0, Color::BLACK);
+
+            assert!(Borders::all(border).is_uniform());
+            assert!(Borders::from(border).is_uniform());
+            let dotted = border.with_style(BorderStyle::Dotted);
+            assert!(!Borders::all(border).with_left(dotted).is_uniform());
+        }
+
+        #[test]
+        fn test_widths() {
+            let borders = Borders::new(
+                Border::new(1.0, Color::BLACK),
+                Border::new(2.0, Color::BLACK),
+                Border::new(3.0, Color::BLACK),
+                Border::new(4.0, Color::BLACK),
+            );
+
+            assert_eq!(borders.widths(), Padding::new(4.0, 2.0, 1.0, 3.0));
+            assert_eq!(Borders::none().widths(), Padding::zero());
+        }
+
+        #[test]
+        fn test_const_functions() {
+            const CONST_BORDERS: Borders =
+                Borders::all(Border::dotted(2.0, Color::RED)).with_top(Border::none());
+
+            assert_eq!(CONST_BORDERS.top.width, 0.0);
+            assert_eq!(CONST_BORDERS.left.style, BorderStyle::Dotted);
+        }
+    }
+
+    mod margin {
+        use super::*;
+
+        #[test]
+        fn test_new_valid_and_invalid() {
+            let margin = Margin::new(f32::EPSILON, f32::INFINITY, -f32::EPSILON, f32::NAN);
+
+            assert_eq!(margin.left, f32::EPSILON);
+            assert_eq!(margin.right, f32::INFINITY);
+            assert_eq!(margin.top, 0.0, "Negative margin should be 0.0");
+            assert_eq!(margin.bottom, 0.0, "NaN margin should be 0.0");
+        }
+
+        #[test]
+        fn test_convenience_constructors() {
+            assert_eq!(Margin::all(2.0), Margin::new(2.0, 2.0, 2.0, 2.0));
+            assert_eq!(Margin::horizontal(2.0), Margin::new(2.0, 2.0, 0.0, 0.0));
+            assert_eq!(Margin::vertical(2.0), Margin::new(0.0, 0.0, 2.0, 2.0));
+            assert_eq!(Margin::symmetric(1.0, 2.0), Margin::new(1.0, 1.0, 2.0, 2.0));
+            assert_eq!(Margin::zero(), Margin::all(0.0));
+        }
+    }
+
+    mod box_style {
+        use super::*;
+
+        fn style() -> BoxStyle {
+            BoxStyle::new()
+                .with_margin(Margin::new(1.0, 2.0, 3.0, 4.0))
+                .with_borders(
+                    Borders::all(Border::new(1.0, Color::BLACK))
+                        .with_left(Border::new(5.0, Color::BLACK)),
+                )
+                .with_padding(Padding::new(2.0, 2.0, 1.0, 1.0))
+        }
+
+        #[test]
+        fn test_default() {
+            let style = BoxStyle::default();
+            let rect = Rect::new(1.0, 2.0, 30.0, 40.0);
+            let rects = style.layout(rect);
+
+            assert_eq!(style.background, Paint::Solid(Color::TRANSPARENT));
+            assert_eq!(rects.margin, rect);
+            assert_eq!(rects.border, rect);
+            assert_eq!(rects.padding, rect);
+            assert_eq!(rects.content, rect);
+        }
+
+        #[test]
+        fn test_layout() {
+            let rects = style().layout(Rect::new(10.0, 10.0, 100.0, 50.0));
+
+            assert_eq!(rects.margin, Rect::new(10.0, 10.0, 100.0, 50.0));
+            assert_eq!(rects.border, Rect::new(11.0, 13.0, 97.0, 43.0));
+            assert_eq!(rects.padding, Rect::new(16.0, 14.0, 91.0, 41.0));
+            assert_eq!(rects.content, Rect::new(18.0, 15.0, 87.0, 39.0));
+        }
+
+        #[test]
+        fn test_layout_too_small() {
+            let rects = style().layout(Rect::new(0.0, 0.0, 8.0, 8.0));
+
+            // Nothing gets a negative size
+            for rect in [rects.border, rects.padding, rects.content] {
+                assert!(rect.size.is_valid(), "Invalid size: {:?}", rect);
+            }
+            assert_eq!(rects.content.size, Size::zero());
+        }
+
+        #[test]
+        fn test_layout_size_constraints() {
+            let outer = Rect::new(0.0, 0.0, 100.0, 20.0);
+
+            let rects = BoxStyle::new()
+                .with_max_size(Size::new(50.0, f32::INFINITY))
+                .layout(outer);
+            assert_eq!(rects.border, Rect::new(0.0, 0.0, 50.0, 20.0));
+
+            let rects = BoxStyle::new()
+                .with_min_size(Size::new(0.0, 30.0))
+                .layout(outer);
+            assert_eq!(rects.border, Rect::new(0.0, 0.0, 100.0, 30.0));
+        }
+
+        #[test]
+        fn test_outer_size_inverts_layout() {
+            let style = style();
+            let content = Size::new(40.0, 20.0);
+            let outer = style.outer_size(content);
+            let rects = style.layout(Rect::new(0.0, 0.0, outer.width, outer.height));
+
+            assert_eq!(rects.content.size, content);
+        }
+    }
+
+    mod state_style {
+        use super::*;
+
+        fn style() -> StateStyle<u8> {
+            StateStyle::new(0)
+                .with_hovered(1)
+                .with_active(2)
+                .with_focused(3)
+                .with_disabled(4)
+        }
+
+        #[test]
+        fn test_resolve_single_state() {
+            let style = style();
+            let normal = WidgetState::normal();
+
+            assert_eq!(style.resolve(normal), &0);
+            assert_eq!(style.resolve(normal.with_hovered(true)), &1);
+            assert_eq!(style.resolve(normal.with_active(true)), &2);
+            assert_eq!(style.resolve(normal.with_focused(true)), &3);
+            assert_eq!(style.resolve(normal.with_disabled(true)), &4);
+        }
+
+        #[test]
+        fn test_resolve_precedence() {
+            let style = style();
+            let all = WidgetState::normal()
+                .with_hovered(true)
+                .with_active(true)
+                .with_focused(true);
+
+            assert_eq!(
+                style.resolve(all),
+                &2,
+                "Active wins over hovered and focused"
+            );
+            assert_eq!(style.resolve(all.with_active(false)), &1);
+            assert_eq!(style.resolve(all.with_disabled(true)), &4);
+        }
+
+        #[test]
+        fn test_partial_overrides_fall_back() {
+            let style = StateStyle::new(0).with_hovered(1);
+            let pressed = WidgetState::normal().with_hovered(true).with_active(true);
+
+            // No active override, so the hovered one is used
+            assert_eq!(style.resolve(pressed), &1);
+
+            // Disabled never falls back to interaction overrides
+            assert_eq!(style.resolve(pressed.with_disabled(true)), &0);
+            assert_eq!(StateStyle::from(7).resolve(pressed), &7);
+        }
+
+        #[test]
+        fn test_derived_overrides() {
+            let normal = BoxStyle::new()
+                .with_padding(Padding::all(4.0))
+                .with_background(Color::WHITE);
+            let style = StateStyle::new(normal.clone())
+                .with_active_from(|s| s.with_background(Color::BLACK))
+                .with_disabled_from(|s| s.with_background(Color::TRANSPARENT));
+
+            let active = style.resolve(WidgetState::normal().with_active(true));
+            assert_eq!(active.background, Paint::Solid(Color::BLACK));
+            assert_eq!(active.padding, normal.padding);
+
+            let disabled = style.resolve(WidgetState::normal().with_disabled(true));
+            assert_eq!(disabled.background, Paint::Solid(Color::TRANSPARENT));
+        }
+    }
+
+    mod shadow {
+        use super::*;
+
+        #[test]
+        fn test_new_valid_and_invalid() {
+            let offset = Vec2::new(1.0, 2.0);
+
+            let shadow = Shadow::new(offset, 4.0, 2.0, Color::BLACK);
+            assert_eq!(
+                (
+                    shadow.offset,
+                    shadow.blur,
+                    shadow.spread,
+                    shadow.inset,
+                    shadow.color
+                ),
+                (offset, 4.0, 2.0, false, Color::BLACK)
+            );
+
+            let invalid = Shadow::new(offset, f32::NAN, f32::NAN, Color::BLACK);
+            assert_eq!(
+                (invalid.blur, invalid.spread),
+                (0.0, 0.0),
+                "NaN should be 0.0"
+            );
+
+            let negative = Shadow::new(offset, -1.0, -1.0, Color::BLACK);
+            assert_eq!(negative.blur, 0.0, "Negative blur should be 0.0");
+            assert_eq!(negative.spread, -1.0, "Negative spread should be kept");
+        }
+
+        #[test]
+        fn test_glow_and_none() {
+            let glow = Shadow::glow(6.0, Color::BLUE);
+
+            assert_eq!(glow.offset, Vec2::zero());
+            assert_eq!(glow.blur, 6.0);
+            assert_eq!(glow.color, Color::BLUE);
+            assert_eq!(Shadow::none().color, Color::TRANSPARENT);
+        }
+
+        #[test]
+        fn test_const_functions() {
+            const CONST_SHADOW: Shadow = Shadow::glow(2.0, Color::RED).with_inset(true);
+
+            const { assert!(CONST_SHADOW.inset) };
+            assert_eq!(CONST_SHADOW.blur, 2.0);
+        }
+    }
+
+    mod corner_radius {
+        use super::*;
+
+        #[test]
+        fn test_new_valid_and_invalid() {
+            let radius = CornerRadius::new(1.0, -1.0, f32::NAN, f32::INFINITY);
+
+            assert_eq!(radius.top_left, 1.0);
+            assert_eq!(radius.top_right, 0.0, "Negative radius should be 0.0");
+            assert_eq!(radius.bottom_right, 0.0, "NaN radius should be 0.0");
+            assert_eq!(radius.bottom_left, f32::INFINITY);
+        }
+
+        #[test]
+        fn test_side_constructors() {
+            assert_eq!(
+                CornerRadius::top(2.0),
+                CornerRadius::new(2.0, 2.0, 0.0, 0.0)
+            );
+            assert_eq!(
+                CornerRadius::bottom(2.0),
+                CornerRadius::new(0.0, 0.0, 2.0, 2.0)
+            );
+            assert_eq!(
+                CornerRadius::left(2.0),
+                CornerRadius::new(2.0, 0.0, 0.0, 2.0)
+            );
+            assert_eq!(
+                CornerRadius::right(2.0),
+                CornerRadius::new(0.0, 2.0, 2.0, 0.0)
+            );
+            assert!(CornerRadius::zero().is_zero());
+            assert!(!CornerRadius::top(1.0).is_zero());
+        }
+
+        #[test]
+        fn test_fit() {
+            let size = Size::new(100.0, 40.0);
+
+            // Radii which fit are unchanged
+            assert_eq!(CornerRadius::all(10.0).fit(size), CornerRadius::all(10.0));
+
+            // Left side is 60 + 20 = 80 high, scaled by 40 / 80
+            assert_eq!(
+                CornerRadius::new(60.0, 0.0, 0.0, 20.0).fit(size),
+                CornerRadius::new(30.0, 0.0, 0.0, 10.0)
+            );
+
+            // Infinite radius produces a pill
+            assert_eq!(
+                CornerRadius::all(f32::INFINITY).fit(size),
+                CornerRadius::all(20.0)
+            );
+
+            assert_eq!(
+                CornerRadius::all(5.0).fit(Size::zero()),
+                CornerRadius::zero()
+            );
+        }
+
+        #[test]
+        fn test_const_functions() {
+            const CONST_RADIUS: CornerRadius = CornerRadius::all(4.0);
+            const CONST_INNER: CornerRadius = CONST_RADIUS.shrink(1.0);
+
+            assert_eq!(CONST_RADIUS.top_left, 4.0);
+            assert_eq!(CONST_INNER.bottom_right, 3.0);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -877,8 +2397,8 @@ mod bench_tests {
         assert_eq!(std::mem::size_of::<Padding>(), 16);
         assert_eq!(std::mem::align_of::<Padding>(), 4);
 
-        // Border has Color (16 bytes, 16-byte aligned) + f32 (4 bytes) + padding (12 bytes)
-        // Total: 32 bytes due to Color's 16-byte alignment requirement
+        // Border has f32 (4 bytes) + BorderStyle (1 byte) + padding (11 bytes) + Color (16 bytes,
+        // 16-byte aligned). Total: 32 bytes due to Color's 16-byte alignment requirement
         assert_eq!(std::mem::size_of::<Border>(), 32);
         assert_eq!(std::mem::align_of::<Border>(), 16);
 
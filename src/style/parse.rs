@@ -0,0 +1,63 @@
+//! Parsers for style values shared by the theme file and stylesheet formats
+
+use super::BorderStyle;
+
+/// CSS box shorthand: 1 to 4 lengths, expanded to `[top, right, bottom, left]`
+pub(crate) fn parse_box(values: &[&str]) -> Option<[f32; 4]> {
+    let lengths = values
+        .iter()
+        .map(|value| parse_length(value))
+        .collect::<Option<Vec<_>>>()?;
+
+    match lengths[..] {
+        [all] => Some([all; 4]),
+        [vertical, horizontal] => Some([vertical, horizontal, vertical, horizontal]),
+        [top, horizontal, bottom] => Some([top, horizontal, bottom, horizontal]),
+        [top, right, bottom, left] => Some([top, right, bottom, left]),
+        _ => None,
+    }
+}
+
+/// Non-negative length in pixels, with an optional `px` suffix
+pub(crate) fn parse_length(value: &str) -> Option<f32> {
+    let number = value.strip_suffix("px").unwrap_or(value);
+    let length = number.parse::<f32>().ok()?;
+
+    (length.is_finite() && length >= 0.0).then_some(length)
+}
+
+/// Border style by its CSS name
+pub(crate) fn border_style(name: &str) -> Option<BorderStyle> {
+    match name {
+        "solid" => Some(BorderStyle::Solid),
+        "dashed" => Some(BorderStyle::Dashed),
+        "dotted" => Some(BorderStyle::Dotted),
+        "double" => Some(BorderStyle::Double),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_box_expands_shorthands() {
+        assert_eq!(parse_box(&["4"]), Some([4.0; 4]));
+        assert_eq!(parse_box(&["1", "2px"]), Some([1.0, 2.0, 1.0, 2.0]));
+        assert_eq!(parse_box(&["1", "2", "3"]), Some([1.0, 2.0, 3.0, 2.0]));
+        assert_eq!(parse_box(&["1", "2", "3", "4"]), Some([1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(parse_box(&[]), None);
+        assert_eq!(parse_box(&["1", "2", "3", "4", "5"]), None);
+    }
+
+    #[test]
+    fn test_parse_length_rejects_invalid_lengths() {
+        assert_eq!(parse_length("12.5px"), Some(12.5));
+        assert_eq!(parse_length("-1"), None);
+        assert_eq!(parse_length("inf"), None);
+        assert_eq!(parse_length("1em"), None);
+        assert_eq!(border_style("dotted"), Some(BorderStyle::Dotted));
+        assert_eq!(border_style("groove"), None);
+    }
+}
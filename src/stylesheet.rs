@@ -0,0 +1,891 @@
+//! Stylesheets: CSS-like rules that skin widgets by type, class and state
+//!
+//! # Format
+//!
+//! A subset of CSS. Each rule has a comma separated list of compound selectors and a block of
+//! `property: value;` declarations:
+//!
+//! ```text
+//! /* Shared component library */
+//! Button {
+//!     padding: 6px 12px;
+//!     border: 1px solid #818B98;
+//!     corner-radius: 6px;
+//! }
+//!
+//! Button:hover, Button:focus { background: #EAEEF2; }
+//! .primary { background: #0969DA; border: #0969DA; }
+//! Button.primary:disabled { background: #8C959F; }
+//! ```
+//!
+//! - Selectors combine an optional widget type (or `*`), `.class`es and `:state`s, all of which
+//!   must match. States are `:hover`, `:active`, `:focus` and `:disabled`. There are no
+//!   combinators, since widgets are styled without looking at their parents.
+//! - Properties are `padding`, `border`, `background` and `corner-radius`
+//! - Lengths are non-negative pixels with an optional `px` suffix. Colors are `#RRGGBB` or
+//!   `#RRGGBBAA`, as parsed by [`Color::from_hex_str`].
+//! - `border` takes a width, a [`BorderStyle`] name and a color in any order, or `none`. Parts
+//!   left out keep the value of the style being skinned.
+//!
+//! # Cascade
+//!
+//! Rules are applied like CSS: in order of [`Specificity`], and in source order when specificity
+//! ties, so later rules win. A property set by no matching rule keeps its base value.
+
+use crate::color::{Color, ColorParseError};
+use crate::paint::Paint;
+use crate::style::parse::{border_style, parse_box, parse_length};
+use crate::style::{Border, BorderStyle, BoxStyle, CornerRadius, Padding, WidgetState};
+
+/// Widget state matched by a `:state` selector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PseudoClass {
+    /// `:hover`, matches `WidgetState::hovered`
+    Hover,
+
+    /// `:active`, matches `WidgetState::active`
+    Active,
+
+    /// `:focus`, matches `WidgetState::focused`
+    Focus,
+
+    /// `:disabled`, matches `WidgetState::disabled`
+    Disabled,
+}
+
+impl PseudoClass {
+    /// Pseudo-class with the given CSS name, without the leading `:`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "hover" => Some(PseudoClass::Hover),
+            "active" => Some(PseudoClass::Active),
+            "focus" => Some(PseudoClass::Focus),
+            "disabled" => Some(PseudoClass::Disabled),
+            _ => None,
+        }
+    }
+
+    /// Check if `state` has this pseudo-class
+    pub const fn matches(self, state: WidgetState) -> bool {
+        match self {
+            PseudoClass::Hover => state.hovered,
+            PseudoClass::Active => state.active,
+            PseudoClass::Focus => state.focused,
+            PseudoClass::Disabled => state.disabled,
+        }
+    }
+}
+
+/// Weight of a selector in the cascade
+///
+/// # Notes
+///
+/// - Compares like CSS: more classes and states win, ties are broken by more widget types
+/// - `*` adds nothing
+///
+/// # Examples
+/// ```
+/// use ho_gui::stylesheet::Selector;
+///
+/// let button_hover: Selector = "Button:hover".parse().unwrap();
+/// let primary: Selector = ".primary".parse().unwrap();
+/// let button: Selector = "Button".parse().unwrap();
+///
+/// assert!(button_hover.specificity() > primary.specificity());
+/// assert!(primary.specificity() > button.specificity());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Specificity {
+    /// Number of class and state selectors
+    pub classes: u32,
+
+    /// Number of widget type selectors, 0 or 1
+    pub types: u32,
+}
+
+/// Compound selector matching a widget by type, classes and states
+///
+/// # Examples
+/// ```
+/// use ho_gui::style::WidgetState;
+/// use ho_gui::stylesheet::{PseudoClass, Selector};
+///
+/// let selector = Selector::widget("Button")
+///     .with_class("primary")
+///     .with_state(PseudoClass::Hover);
+/// assert_eq!(selector, "Button.primary:hover".parse().unwrap());
+///
+/// let hovered = WidgetState::normal().with_hovered(true);
+/// assert!(selector.matches("Button", &["primary", "large"], hovered));
+/// assert!(!selector.matches("Button", &["primary"], WidgetState::normal()));
+/// assert!(!selector.matches("Slider", &["primary"], hovered));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Selector {
+    widget: Option<String>,
+    classes: Vec<String>,
+    states: Vec<PseudoClass>,
+}
+
+impl Selector {
+    /// Selector matching every widget, `*`
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Selector matching widgets of type `name`, e.g. `Button`
+    pub fn widget(name: &str) -> Self {
+        Self {
+            widget: Some(name.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Return the selector also requiring the class `name`
+    pub fn with_class(mut self, name: &str) -> Self {
+        self.classes.push(name.to_string());
+        self
+    }
+
+    /// Return the selector also requiring the widget `state`
+    pub fn with_state(mut self, state: PseudoClass) -> Self {
+        self.states.push(state);
+        self
+    }
+
+    /// Weight of the selector in the cascade
+    pub fn specificity(&self) -> Specificity {
+        Specificity {
+            classes: (self.classes.len() + self.states.len()) as u32,
+            types: self.widget.is_some() as u32,
+        }
+    }
+
+    /// Check if the selector matches a widget
+    ///
+    /// # Arguments
+    ///
+    /// * `widget` - Widget type name, e.g. `"Button"`
+    /// * `classes` - Classes assigned to the widget
+    /// * `state` - Current widget state
+    pub fn matches(&self, widget: &str, classes: &[&str], state: WidgetState) -> bool {
+        self.widget.as_deref().is_none_or(|name| name == widget)
+            && self
+                .classes
+                .iter()
+                .all(|class| classes.contains(&class.as_str()))
+            && self.states.iter().all(|pseudo| pseudo.matches(state))
+    }
+}
+
+impl std::str::FromStr for Selector {
+    type Err = StylesheetError;
+
+    /// Parse a single compound selector like `Button.primary:hover`
+    ///
+    /// # Errors
+    ///
+    /// * Returns `StylesheetError::InvalidSelector(1)` for empty selectors, combinators, unknown
+    ///   states and characters outside of names
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        parse_selector(source, 1)
+    }
+}
+
+/// Parsed stylesheet
+///
+/// # Examples
+/// ```
+/// use ho_gui::style::{BoxStyle, Padding, WidgetState};
+/// use ho_gui::stylesheet::Stylesheet;
+///
+/// let sheet: Stylesheet = "
+///     Button { padding: 4px; }
+///     .large { padding: 8px 16px; }
+///     Button:hover { padding: 6px; }
+/// "
+/// .parse()
+/// .unwrap();
+///
+/// let base = BoxStyle::default();
+/// let normal = WidgetState::normal();
+/// let hovered = normal.with_hovered(true);
+///
+/// assert_eq!(sheet.resolve("Button", &[], normal, &base).padding, Padding::all(4.0));
+/// assert_eq!(
+///     sheet.resolve("Button", &["large"], normal, &base).padding,
+///     Padding::symmetric(16.0, 8.0)
+/// );
+/// // `.large` and `Button:hover` tie on classes, the type breaks the tie
+/// assert_eq!(sheet.resolve("Button", &["large"], hovered, &base).padding, Padding::all(6.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Stylesheet {
+    rules: Vec<Rule>,
+}
+
+impl Stylesheet {
+    /// Empty stylesheet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of rules
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Check if the stylesheet has no rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Append the rules of `skin`, which win over the current rules at equal specificity
+    ///
+    /// # Notes
+    ///
+    /// - Use it to layer a product skin over a shared component library stylesheet
+    pub fn extend(&mut self, skin: Stylesheet) {
+        self.rules.extend(skin.rules);
+    }
+
+    /// Skin a widget style with the matching rules
+    ///
+    /// # Arguments
+    ///
+    /// * `widget` - Widget type name, e.g. `"Button"`
+    /// * `classes` - Classes assigned to the widget
+    /// * `state` - Current widget state
+    /// * `base` - Style of the widget before skinning
+    ///
+    /// # Returns
+    ///
+    /// `base` with the properties of every matching rule applied in cascade order
+    pub fn resolve(
+        &self,
+        widget: &str,
+        classes: &[&str],
+        state: WidgetState,
+        base: &BoxStyle,
+    ) -> BoxStyle {
+        let mut matched: Vec<(Specificity, usize)> = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter_map(|(index, rule)| {
+                rule.selectors
+                    .iter()
+                    .filter(|selector| selector.matches(widget, classes, state))
+                    .map(Selector::specificity)
+                    .max()
+                    .map(|specificity| (specificity, index))
+            })
+            .collect();
+        matched.sort_unstable();
+
+        let mut style = base.clone();
+        for (_, index) in matched {
+            self.rules[index].declarations.apply(&mut style);
+        }
+
+        style
+    }
+}
+
+impl std::str::FromStr for Stylesheet {
+    type Err = StylesheetError;
+
+    /// Parse a stylesheet, see the [module docs](self) for the format
+    ///
+    /// # Errors
+    ///
+    /// Every error holds the 1-based number of the offending line.
+    ///
+    /// * Returns `StylesheetError::InvalidRule` if a rule is not `selectors { declarations }`
+    /// * Returns `StylesheetError::UnclosedBlock` if a `{` or `/*` is never closed
+    /// * Returns `StylesheetError::InvalidSelector` if a selector doesn't parse
+    /// * Returns `StylesheetError::InvalidDeclaration` if a declaration is not `property: value`
+    /// * Returns `StylesheetError::UnknownProperty` for unsupported properties
+    /// * Returns `StylesheetError::InvalidValue` or `StylesheetError::InvalidColor` for values
+    ///   that don't parse
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let source = strip_comments(source)?;
+        let line_of = |offset: usize| source[..offset].matches('\n').count() + 1;
+
+        let mut rules = Vec::new();
+        let mut start = 0;
+
+        while let Some(skipped) = source[start..].find(|c: char| !c.is_whitespace()) {
+            start += skipped;
+
+            let open = source[start..]
+                .find('{')
+                .map(|offset| start + offset)
+                .ok_or(StylesheetError::InvalidRule(line_of(start)))?;
+            let close = source[open..]
+                .find('}')
+                .map(|offset| open + offset)
+                .ok_or(StylesheetError::UnclosedBlock(line_of(open)))?;
+
+            let prelude = &source[start..open];
+            let body = &source[open + 1..close];
+            if prelude.contains('}') || body.contains('{') {
+                return Err(StylesheetError::InvalidRule(line_of(start)));
+            }
+
+            let selectors = prelude
+                .split(',')
+                .map(|selector| parse_selector(selector, line_of(start)))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut declarations = Declarations::default();
+            let mut offset = open + 1;
+            for declaration in body.split(';') {
+                let line = line_of(offset + (declaration.len() - declaration.trim_start().len()));
+                offset += declaration.len() + 1;
+
+                if declaration.trim().is_empty() {
+                    continue;
+                }
+
+                let (property, value) = declaration
+                    .split_once(':')
+                    .ok_or(StylesheetError::InvalidDeclaration(line))?;
+                let values: Vec<&str> = value.split_whitespace().collect();
+
+                declarations.set(property.trim(), &values, line)?;
+            }
+
+            rules.push(Rule {
+                selectors,
+                declarations,
+            });
+            start = close + 1;
+        }
+
+        Ok(Self { rules })
+    }
+}
+
+/// Error type for stylesheet parsing
+#[derive(Debug, Clone, PartialEq)]
+pub enum StylesheetError {
+    /// The line has text that is not a `selectors { declarations }` rule
+    InvalidRule(usize),
+
+    /// The `{` block or `/*` comment opened on the line is never closed
+    UnclosedBlock(usize),
+
+    /// The rule starting on the line has a selector that doesn't parse
+    InvalidSelector(usize),
+
+    /// The line has a declaration that is not `property: value`
+    InvalidDeclaration(usize),
+
+    /// The line sets an unsupported property
+    UnknownProperty(usize),
+
+    /// The line has a value of the wrong shape for its property
+    InvalidValue(usize),
+
+    /// The line has a color that doesn't parse
+    InvalidColor(usize, ColorParseError),
+}
+
+impl std::fmt::Display for StylesheetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StylesheetError::InvalidRule(line) => {
+                write!(f, "Line {}: expected `selectors {{ declarations }}`", line)
+            }
+            StylesheetError::UnclosedBlock(line) => write!(f, "Line {}: unclosed block", line),
+            StylesheetError::InvalidSelector(line) => write!(f, "Line {}: invalid selector", line),
+            StylesheetError::InvalidDeclaration(line) => {
+                write!(f, "Line {}: expected `property: value`", line)
+            }
+            StylesheetError::UnknownProperty(line) => {
+                write!(f, "Line {}: unknown property", line)
+            }
+            StylesheetError::InvalidValue(line) => write!(f, "Line {}: invalid value", line),
+            StylesheetError::InvalidColor(line, error) => write!(f, "Line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for StylesheetError {}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    selectors: Vec<Selector>,
+    declarations: Declarations,
+}
+
+/// Properties set by a rule, `None` when left to the cascade
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Declarations {
+    padding: Option<Padding>,
+    border: Option<BorderParts>,
+    background: Option<Color>,
+    corner_radius: Option<CornerRadius>,
+}
+
+impl Declarations {
+    fn set(&mut self, property: &str, values: &[&str], line: usize) -> Result<(), StylesheetError> {
+        let invalid = StylesheetError::InvalidValue(line);
+
+        match property {
+            "padding" => {
+                let [top, right, bottom, left] = parse_box(values).ok_or(invalid)?;
+                self.padding = Some(Padding::new(left, right, top, bottom));
+            }
+            "border" => self.border = Some(BorderParts::parse(values, line)?),
+            "background" => {
+                self.background = Some(match values {
+                    ["transparent" | "none"] => Color::TRANSPARENT,
+                    [color] => parse_color(color, line)?,
+                    _ => return Err(invalid),
+                });
+            }
+            "corner-radius" => {
+                let [top_left, top_right, bottom_right, bottom_left] =
+                    parse_box(values).ok_or(invalid)?;
+                self.corner_radius = Some(CornerRadius::new(
+                    top_left,
+                    top_right,
+                    bottom_right,
+                    bottom_left,
+                ));
+            }
+            _ => return Err(StylesheetError::UnknownProperty(line)),
+        }
+
+        Ok(())
+    }
+
+    fn apply(&self, style: &mut BoxStyle) {
+        if let Some(padding) = self.padding {
+            style.padding = padding;
+        }
+        if let Some(parts) = self.border {
+            let borders = &mut style.borders;
+            for side in [
+                &mut borders.top,
+                &mut borders.right,
+                &mut borders.bottom,
+                &mut borders.left,
+            ] {
+                *side = parts.apply(*side);
+            }
+        }
+        if let Some(color) = self.background {
+            style.background = Paint::Solid(color);
+        }
+        if let Some(radius) = self.corner_radius {
+            style.corner_radius = radius;
+        }
+    }
+}
+
+/// Parts of a `border` shorthand, missing parts keep the skinned border's value
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BorderParts {
+    width: Option<f32>,
+    style: Option<BorderStyle>,
+    color: Option<Color>,
+}
+
+impl BorderParts {
+    fn parse(values: &[&str], line: usize) -> Result<Self, StylesheetError> {
+        let invalid = StylesheetError::InvalidValue(line);
+        let mut parts = Self {
+            width: None,
+            style: None,
+            color: None,
+        };
+
+        if values == ["none"] {
+            parts.width = Some(0.0);
+            return Ok(parts);
+        }
+        if values.is_empty() {
+            return Err(invalid);
+        }
+
+        for &value in values {
+            let taken = if value.starts_with('#') {
+                parts.color.replace(parse_color(value, line)?).is_some()
+            } else if let Some(style) = border_style(value) {
+                parts.style.replace(style).is_some()
+            } else {
+                let width = parse_length(value).ok_or(invalid.clone())?;
+                parts.width.replace(width).is_some()
+            };
+
+            if taken {
+                return Err(invalid);
+            }
+        }
+
+        Ok(parts)
+    }
+
+    fn apply(self, border: Border) -> Border {
+        Border {
+            width: self.width.unwrap_or(border.width),
+            style: self.style.unwrap_or(border.style),
+            color: self.color.unwrap_or(border.color),
+        }
+    }
+}
+
+fn parse_color(value: &str, line: usize) -> Result<Color, StylesheetError> {
+    Color::from_hex_str(value).map_err(|e| StylesheetError::InvalidColor(line, e))
+}
+
+fn parse_selector(source: &str, line: usize) -> Result<Selector, StylesheetError> {
+    let invalid = StylesheetError::InvalidSelector(line);
+    let source = source.trim();
+    let is_name = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+
+    let mut selector = Selector::any();
+    let mut rest = source;
+
+    if let Some(after_star) = source.strip_prefix('*') {
+        rest = after_star;
+    } else {
+        let end = rest.find(|c: char| !is_name(c)).unwrap_or(rest.len());
+        if end > 0 {
+            selector.widget = Some(rest[..end].to_string());
+        } else if !rest.starts_with(['.', ':']) {
+            return Err(invalid);
+        }
+        rest = &rest[end..];
+    }
+
+    while let Some(prefix) = rest.chars().next() {
+        // Anything but a class or state, such as a non-ASCII letter, is not a selector
+        if prefix != '.' && prefix != ':' {
+            return Err(invalid);
+        }
+
+        let tail = &rest[prefix.len_utf8()..];
+        let name_len = tail.find(|c: char| !is_name(c)).unwrap_or(tail.len());
+        let name = &tail[..name_len];
+        if name.is_empty() {
+            return Err(invalid);
+        }
+
+        if prefix == '.' {
+            selector.classes.push(name.to_string());
+        } else {
+            let state = PseudoClass::from_name(name).ok_or(invalid.clone())?;
+            selector.states.push(state);
+        }
+        rest = &tail[name_len..];
+    }
+
+    Ok(selector)
+}
+
+/// Blank out `/* */` comments, keeping line breaks so offsets and line numbers stay valid
+fn strip_comments(source: &str) -> Result<String, StylesheetError> {
+    let mut stripped = source.to_string();
+    let mut start = 0;
+
+    while let Some(open) = stripped[start..].find("/*").map(|offset| start + offset) {
+        let close = stripped[open + 2..]
+            .find("*/")
+            .map(|offset| open + 2 + offset + 2)
+            .ok_or_else(|| {
+                StylesheetError::UnclosedBlock(source[..open].matches('\n').count() + 1)
+            })?;
+
+        let blank: String = stripped[open..close]
+            .chars()
+            .map(|c| if c == '\n' { '\n' } else { ' ' })
+            .collect();
+        stripped.replace_range(open..close, &blank);
+        start = open + blank.len();
+    }
+
+    Ok(stripped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::Borders;
+
+    const LIBRARY: &str = "
+        /* Shared component library */
+        Button {
+            padding: 6px 12px;
+            border: 1px solid #818B98;
+            corner-radius: 6px;
+            background: #FFFFFF;
+        }
+
+        Button:hover, Button:focus { background: #EAEEF2; }
+        .primary { background: #0969DA; border: #0969DA; }
+        Button.primary:disabled { background: #8C959F; }
+    ";
+
+    fn hex(value: &str) -> Color {
+        Color::from_hex_str(value).unwrap()
+    }
+
+    mod selector {
+        use super::*;
+
+        #[test]
+        fn test_parse() {
+            let parse = |source: &str| source.parse::<Selector>();
+
+            assert_eq!(parse("*"), Ok(Selector::any()));
+            assert_eq!(parse(" Button "), Ok(Selector::widget("Button")));
+            assert_eq!(parse(".primary"), Ok(Selector::any().with_class("primary")));
+            assert_eq!(
+                parse("*.icon-only:focus:hover"),
+                Ok(Selector::any()
+                    .with_class("icon-only")
+                    .with_state(PseudoClass::Focus)
+                    .with_state(PseudoClass::Hover))
+            );
+
+            for invalid in [
+                "",
+                "Button .primary",
+                "Panel > Button",
+                "*Button",
+                "Button:",
+                ".",
+            ] {
+                assert_eq!(parse(invalid), Err(StylesheetError::InvalidSelector(1)));
+            }
+            assert_eq!(parse(":visited"), Err(StylesheetError::InvalidSelector(1)));
+        }
+
+        #[test]
+        fn test_matches() {
+            let normal = WidgetState::normal();
+            let selector: Selector = "Button.primary.large:active".parse().unwrap();
+
+            assert!(selector.matches("Button", &["large", "primary"], normal.with_active(true)));
+            assert!(!selector.matches("Button", &["primary"], normal.with_active(true)));
+            assert!(!selector.matches("Button", &["large", "primary"], normal));
+            assert!(Selector::any().matches("Slider", &[], normal));
+            assert!("Button:disabled".parse::<Selector>().unwrap().matches(
+                "Button",
+                &[],
+                normal.with_disabled(true)
+            ));
+        }
+
+        #[test]
+        fn test_specificity() {
+            let specificity = |source: &str| source.parse::<Selector>().unwrap().specificity();
+
+            assert_eq!(specificity("*"), Specificity::default());
+            assert_eq!(
+                specificity("Button"),
+                Specificity {
+                    classes: 0,
+                    types: 1
+                }
+            );
+            assert_eq!(
+                specificity("Button.a.b:hover"),
+                Specificity {
+                    classes: 3,
+                    types: 1
+                }
+            );
+            assert!(specificity(".a") > specificity("Button"));
+            assert!(specificity("Button.a") > specificity(".a"));
+            assert!(specificity(".a.b") > specificity("Button:hover"));
+        }
+    }
+
+    mod cascade {
+        use super::*;
+
+        #[test]
+        fn test_resolve_library() {
+            let sheet: Stylesheet = LIBRARY.parse().unwrap();
+            let base = BoxStyle::default();
+            let normal = WidgetState::normal();
+            assert_eq!(sheet.len(), 4);
+
+            let button = sheet.resolve("Button", &[], normal, &base);
+            assert_eq!(button.padding, Padding::symmetric(12.0, 6.0));
+            assert_eq!(
+                button.borders,
+                Borders::all(Border::new(1.0, hex("#818B98")))
+            );
+            assert_eq!(button.corner_radius, CornerRadius::all(6.0));
+            assert_eq!(button.background, Paint::Solid(Color::WHITE));
+
+            let hovered = sheet.resolve("Button", &[], normal.with_hovered(true), &base);
+            assert_eq!(hovered.background, Paint::Solid(hex("#EAEEF2")));
+
+            let primary = sheet.resolve("Button", &["primary"], normal, &base);
+            assert_eq!(primary.background, Paint::Solid(hex("#0969DA")));
+            // Only recolors the border
+            assert_eq!(primary.borders.top, Border::new(1.0, hex("#0969DA")));
+
+            // `Button:hover` is more specific than `.primary`
+            let primary_hovered =
+                sheet.resolve("Button", &["primary"], normal.with_hovered(true), &base);
+            assert_eq!(primary_hovered.background, Paint::Solid(hex("#EAEEF2")));
+
+            let disabled = sheet.resolve("Button", &["primary"], normal.with_disabled(true), &base);
+            assert_eq!(disabled.background, Paint::Solid(hex("#8C959F")));
+
+            // Unmatched widgets keep the base style
+            assert_eq!(sheet.resolve("Slider", &[], normal, &base), base);
+        }
+
+        #[test]
+        fn test_specificity_beats_source_order() {
+            let sheet: Stylesheet = "
+                Button.primary { padding: 1; }
+                .primary { padding: 2; }
+                Button { padding: 3; }
+            "
+            .parse()
+            .unwrap();
+
+            let padding = sheet
+                .resolve(
+                    "Button",
+                    &["primary"],
+                    WidgetState::normal(),
+                    &BoxStyle::default(),
+                )
+                .padding;
+            assert_eq!(padding, Padding::all(1.0));
+        }
+
+        #[test]
+        fn test_most_specific_selector_of_rule_counts() {
+            let sheet: Stylesheet = "
+                Button.primary, Slider { padding: 1; }
+                .primary { padding: 2; }
+            "
+            .parse()
+            .unwrap();
+
+            let padding = |widget: &str| {
+                sheet
+                    .resolve(
+                        widget,
+                        &["primary"],
+                        WidgetState::normal(),
+                        &BoxStyle::default(),
+                    )
+                    .padding
+            };
+            assert_eq!(padding("Button"), Padding::all(1.0));
+            assert_eq!(padding("Slider"), Padding::all(2.0));
+        }
+
+        #[test]
+        fn test_skin_overrides_library() {
+            let mut sheet: Stylesheet = LIBRARY.parse().unwrap();
+            let skin: Stylesheet = "
+                Button { corner-radius: 0; border: none; }
+                .primary { background: #1A7F37; }
+            "
+            .parse()
+            .unwrap();
+            sheet.extend(skin);
+
+            let base = BoxStyle::default();
+            let button = sheet.resolve("Button", &["primary"], WidgetState::normal(), &base);
+
+            assert_eq!(button.corner_radius, CornerRadius::zero());
+            assert_eq!(button.background, Paint::Solid(hex("#1A7F37")));
+            assert_eq!(button.borders.left.width, 0.0);
+            // Colored by the library `.primary`, which is more specific than the skin `Button`
+            assert_eq!(button.borders.left.color, hex("#0969DA"));
+            assert_eq!(button.padding, Padding::symmetric(12.0, 6.0));
+        }
+    }
+
+    mod parse {
+        use super::*;
+
+        #[test]
+        fn test_empty() {
+            assert_eq!("".parse(), Ok(Stylesheet::new()));
+            assert!(
+                " /* only a comment */ "
+                    .parse::<Stylesheet>()
+                    .unwrap()
+                    .is_empty()
+            );
+            assert_eq!(
+                "Button {}".parse::<Stylesheet>().map(|sheet| sheet.len()),
+                Ok(1)
+            );
+        }
+
+        #[test]
+        fn test_values() {
+            let declare = |declaration: &str| {
+                format!("* {{ {} }}", declaration)
+                    .parse::<Stylesheet>()
+                    .map(|sheet| {
+                        sheet.resolve("Any", &[], WidgetState::normal(), &BoxStyle::default())
+                    })
+            };
+
+            assert_eq!(
+                declare("corner-radius: 1 2 3 4").unwrap().corner_radius,
+                CornerRadius::new(1.0, 2.0, 3.0, 4.0)
+            );
+            assert_eq!(
+                declare("background: transparent").unwrap().background,
+                Paint::Solid(Color::TRANSPARENT)
+            );
+            assert_eq!(
+                declare("border: #FF0000 double 3px").unwrap().borders,
+                Borders::all(Border::double(3.0, Color::RED))
+            );
+            assert_eq!(declare("padding: 4;;").unwrap().padding, Padding::all(4.0));
+        }
+
+        #[test]
+        fn test_errors_report_line() {
+            let parse = |source: &str| source.parse::<Stylesheet>().unwrap_err();
+
+            assert_eq!(parse("Button"), StylesheetError::InvalidRule(1));
+            assert_eq!(parse("\nButton {\n"), StylesheetError::UnclosedBlock(2));
+            assert_eq!(parse("/* a\n\n"), StylesheetError::UnclosedBlock(1));
+            assert_eq!(parse("A { B { } }"), StylesheetError::InvalidRule(1));
+            assert_eq!(parse("A { } }"), StylesheetError::InvalidRule(1));
+            assert_eq!(parse("\nA, B C { }"), StylesheetError::InvalidSelector(2));
+            // Non-ASCII characters after a name are rejected, not sliced through
+            for selector in ["Buttonä", "Button.primaryä", "Button:hoverä", ".ä", "ä"] {
+                assert_eq!(
+                    parse(&format!("{selector} {{ color: #fff }}")),
+                    StylesheetError::InvalidSelector(1)
+                );
+            }
+            assert_eq!(
+                parse("A {\n  padding: 1;\n  margin: 2;\n}"),
+                StylesheetError::UnknownProperty(3)
+            );
+            assert_eq!(
+                parse("/* one\n two */ A {\n  padding 1;\n}"),
+                StylesheetError::InvalidDeclaration(3)
+            );
+            assert_eq!(parse("A { padding: -1 }"), StylesheetError::InvalidValue(1));
+            assert_eq!(parse("A { border: 1 2 }"), StylesheetError::InvalidValue(1));
+            assert_eq!(
+                parse("A { background: red }"),
+                StylesheetError::InvalidColor(1, ColorParseError::InvalidFormat)
+            );
+        }
+    }
+}
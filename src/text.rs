@@ -0,0 +1,375 @@
+//! Fonts, shaping and paragraph layout
+//!
+//! A [`Font`] shapes text into positioned glyphs with rustybuzz. Layout code only needs the
+//! [`TextShaper`] trait, so [`paragraph`] can wrap, align and truncate text without knowing
+//! where the glyphs come from. [`rich`] text mixes fonts from a [`library::FontLibrary`] in one
+//! paragraph.
+
+pub mod library;
+pub mod line_break;
+pub mod paragraph;
+pub mod rich;
+
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use rustybuzz::ttf_parser::LineMetrics;
+
+use crate::math::Vec2;
+
+/// Glyph produced by shaping, in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+    /// Glyph index in the font
+    pub id: u16,
+
+    /// Byte offset of the first character of the glyph's cluster in the shaped text
+    pub cluster: usize,
+
+    /// Distance the pen moves after drawing the glyph
+    pub advance: f32,
+
+    /// Offset of the glyph from the pen position, y pointing down
+    pub offset: Vec2,
+}
+
+/// Vertical metrics of a font at a size, in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FontMetrics {
+    /// Distance from the baseline up to the top of the tallest glyphs
+    pub ascent: f32,
+
+    /// Distance from the baseline down to the bottom of the lowest glyphs, positive
+    pub descent: f32,
+
+    /// Extra space the font recommends between lines
+    pub line_gap: f32,
+
+    /// Underline recommended by the font, `None` if the font doesn't have one
+    pub underline: Option<DecorationMetrics>,
+
+    /// Strikeout recommended by the font, `None` if the font doesn't have one
+    pub strikeout: Option<DecorationMetrics>,
+}
+
+impl FontMetrics {
+    /// Recommended distance between baselines
+    pub fn line_height(&self) -> f32 {
+        self.ascent + self.descent + self.line_gap
+    }
+}
+
+/// Position and thickness of an underline or strikeout, in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DecorationMetrics {
+    /// Distance from the baseline down to the top of the line, negative above the baseline
+    pub top: f32,
+
+    /// Line thickness
+    pub thickness: f32,
+}
+
+/// Source of glyphs and metrics for text layout
+pub trait TextShaper {
+    /// Shape `text` at `size` pixels per em into glyphs in logical, left-to-right order
+    fn shape(&self, text: &str, size: f32) -> Vec<Glyph>;
+
+    /// Vertical metrics at `size` pixels per em
+    fn metrics(&self, size: f32) -> FontMetrics;
+}
+
+/// OpenType or TrueType font face
+///
+/// # Notes
+///
+/// - Font data and the parsed face are shared, so cloning a Font is cheap
+/// - Text is shaped left to right; right-to-left scripts come out in logical order
+///
+/// # Examples
+/// ```no_run
+/// use ho_gui::text::{Font, TextShaper};
+///
+/// let font = Font::load("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf", 0).unwrap();
+/// let glyphs = font.shape("Hello", 16.0);
+///
+/// assert_eq!(glyphs.len(), 5);
+/// ```
+#[derive(Clone)]
+pub struct Font {
+    face: Arc<OwnedFace>,
+    index: u32,
+    units_per_em: f32,
+    ascender: f32,
+    descender: f32,
+    line_gap: f32,
+    underline: Option<LineMetrics>,
+    strikeout: Option<LineMetrics>,
+}
+
+impl Font {
+    /// Create Font from the face at `index` of font file `data`
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Contents of a font file
+    /// * `index` - Face in a font collection, 0 for single fonts
+    ///
+    /// # Errors
+    ///
+    /// `FontError::InvalidFont` if the data is not a font or has no face at `index`
+    pub fn from_data(data: impl Into<Arc<[u8]>>, index: u32) -> Result<Self, FontError> {
+        let face = OwnedFace::new(data.into(), index).ok_or(FontError::InvalidFont)?;
+        let units_per_em = face.face.units_per_em() as f32;
+        let ascender = face.face.ascender() as f32;
+        let descender = -(face.face.descender() as f32);
+        let line_gap = face.face.line_gap() as f32;
+        let underline = face.face.underline_metrics();
+        let strikeout = face.face.strikeout_metrics();
+
+        Ok(Self {
+            face: Arc::new(face),
+            index,
+            units_per_em,
+            ascender,
+            descender,
+            line_gap,
+            underline,
+            strikeout,
+        })
+    }
+
+    /// Read the face at `index` of the font file at `path`
+    ///
+    /// # Errors
+    ///
+    /// - `FontError::Io` if the file can't be read
+    /// - `FontError::InvalidFont` if the file is not a font or has no face at `index`
+    pub fn load(path: impl AsRef<Path>, index: u32) -> Result<Self, FontError> {
+        let data = std::fs::read(path).map_err(|error| FontError::Io(error.kind()))?;
+        Self::from_data(data, index)
+    }
+
+    /// Face index in the font data
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+/// rustybuzz face parsed once, borrowing the font data stored next to it
+///
+/// Parsing a face reads its layout tables, which costs more than shaping a short label, so it
+/// happens when the [`Font`] is created instead of on every `shape` call.
+struct OwnedFace {
+    // Declared first, so it is dropped before the data it borrows
+    face: rustybuzz::Face<'static>,
+    data: Arc<[u8]>,
+}
+
+impl OwnedFace {
+    fn new(data: Arc<[u8]>, index: u32) -> Option<Self> {
+        // SAFETY: the bytes live in the Arc's heap allocation, which doesn't move or change while
+        // `data` holds it. `data` is private and dropped after `face`, and `face()` only lends
+        // the face for as long as `self`, so the 'static borrow never outlives the bytes.
+        let bytes: &'static [u8] = unsafe { &*std::ptr::from_ref::<[u8]>(&data) };
+        let face = rustybuzz::Face::from_slice(bytes, index)?;
+
+        Some(Self { face, data })
+    }
+
+    fn face(&self) -> &rustybuzz::Face<'_> {
+        &self.face
+    }
+}
+
+impl TextShaper for Font {
+    fn shape(&self, text: &str, size: f32) -> Vec<Glyph> {
+        let scale = size / self.units_per_em;
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.set_direction(rustybuzz::Direction::LeftToRight);
+
+        let output = rustybuzz::shape(self.face.face(), &[], buffer);
+        output
+            .glyph_infos()
+            .iter()
+            .zip(output.glyph_positions())
+            .map(|(info, position)| Glyph {
+                id: info.glyph_id as u16,
+                cluster: info.cluster as usize,
+                advance: position.x_advance as f32 * scale,
+                offset: Vec2::new(
+                    position.x_offset as f32 * scale,
+                    -(position.y_offset as f32) * scale,
+                ),
+            })
+            .collect()
+    }
+
+    fn metrics(&self, size: f32) -> FontMetrics {
+        let scale = size / self.units_per_em;
+        // Font units point up, fonts without the table data have a zero thickness
+        let decoration = |line: Option<LineMetrics>| {
+            line.filter(|line| line.thickness > 0)
+                .map(|line| DecorationMetrics {
+                    top: -(line.position as f32) * scale,
+                    thickness: line.thickness as f32 * scale,
+                })
+        };
+
+        FontMetrics {
+            ascent: self.ascender * scale,
+            descent: self.descender * scale,
+            line_gap: self.line_gap * scale,
+            underline: decoration(self.underline),
+            strikeout: decoration(self.strikeout),
+        }
+    }
+}
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Font")
+            .field("bytes", &self.face.data.len())
+            .field("index", &self.index)
+            .field("units_per_em", &self.units_per_em)
+            .finish()
+    }
+}
+
+/// Error type for font loading
+#[derive(Debug, Clone, PartialEq)]
+pub enum FontError {
+    /// The font file couldn't be read
+    Io(std::io::ErrorKind),
+
+    /// The data is not a supported font, or has no face at the requested index
+    InvalidFont,
+
+    /// No font is loaded to select from
+    NoFont,
+}
+
+impl std::fmt::Display for FontError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FontError::Io(kind) => write!(f, "Can't read font file: {}", kind),
+            FontError::InvalidFont => write!(f, "Invalid font data"),
+            FontError::NoFont => write!(f, "No font loaded"),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+/// Shaper fixture shared by the tests of the text modules
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::{DecorationMetrics, FontMetrics, Glyph, TextShaper};
+    use crate::math::Vec2;
+
+    /// Every character is `width` em wide, ascent 0.8 em and descent 0.2 em
+    ///
+    /// With `decorations`, the font has a 0.1 em underline 0.1 em below the baseline and a
+    /// 0.1 em strikeout 0.4 em above it.
+    pub(crate) struct Mono {
+        pub(crate) width: f32,
+        pub(crate) decorations: bool,
+    }
+
+    /// Mono shaper with characters half an em wide, without decoration metrics
+    pub(crate) const MONO: Mono = Mono {
+        width: 0.5,
+        decorations: false,
+    };
+
+    impl TextShaper for Mono {
+        fn shape(&self, text: &str, size: f32) -> Vec<Glyph> {
+            text.char_indices()
+                .map(|(cluster, c)| Glyph {
+                    id: c as u16,
+                    cluster,
+                    advance: size * self.width,
+                    offset: Vec2::zero(),
+                })
+                .collect()
+        }
+
+        fn metrics(&self, size: f32) -> FontMetrics {
+            FontMetrics {
+                ascent: size * 0.8,
+                descent: size * 0.2,
+                line_gap: 0.0,
+                underline: self.decorations.then_some(DecorationMetrics {
+                    top: size * 0.1,
+                    thickness: size * 0.1,
+                }),
+                strikeout: self.decorations.then_some(DecorationMetrics {
+                    top: -size * 0.4,
+                    thickness: size * 0.1,
+                }),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_font_data() {
+        assert_eq!(
+            Font::from_data(vec![0u8; 16], 0).err(),
+            Some(FontError::InvalidFont)
+        );
+        assert_eq!(
+            Font::load("/nonexistent/font.ttf", 0).err(),
+            Some(FontError::Io(std::io::ErrorKind::NotFound))
+        );
+    }
+
+    #[test]
+    fn test_font_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Font>();
+    }
+
+    #[test]
+    fn test_metrics_line_height() {
+        let metrics = FontMetrics {
+            ascent: 12.0,
+            descent: 4.0,
+            line_gap: 2.0,
+            ..FontMetrics::default()
+        };
+
+        assert_eq!(metrics.line_height(), 18.0);
+    }
+
+    #[test]
+    fn test_shape_with_system_font() {
+        // Only runs where DejaVu is installed
+        let Ok(font) = Font::load("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf", 0) else {
+            return;
+        };
+
+        let glyphs = font.shape("Hi!", 20.0);
+        assert_eq!(glyphs.len(), 3);
+        assert_eq!(
+            glyphs.iter().map(|glyph| glyph.cluster).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        assert!(glyphs.iter().all(|glyph| glyph.advance > 0.0));
+
+        let metrics = font.metrics(20.0);
+        assert!(metrics.ascent > 0.0 && metrics.descent > 0.0);
+        assert!(metrics.line_height() > 20.0);
+
+        // DejaVu has an underline below the baseline and a strikeout above it
+        let underline = metrics.underline.unwrap();
+        let strikeout = metrics.strikeout.unwrap();
+        assert!(underline.top > 0.0 && underline.thickness > 0.0);
+        assert!(strikeout.top < 0.0 && strikeout.thickness > 0.0);
+    }
+}
@@ -0,0 +1,320 @@
+//! Font selection by family, weight and style
+//!
+//! [`FontLibrary`] indexes font files with fontdb and hands out [`Font`]s matching a
+//! [`FontDescriptor`], falling back to another face when nothing matches, so text always has
+//! something to shape with once any font is loaded.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use super::{Font, FontError, TextShaper};
+
+/// Family of a font, either named or generic
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum FontFamily {
+    /// Family with this name, such as "DejaVu Sans"
+    Named(String),
+
+    /// Default proportional font without serifs
+    #[default]
+    SansSerif,
+
+    /// Proportional font with serifs
+    Serif,
+
+    /// Font with glyphs of equal width, for code and logs
+    Monospace,
+}
+
+/// Thickness of glyph strokes, from 100 to 900 like CSS `font-weight`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    /// Thin weight (100)
+    pub const THIN: Self = Self(100);
+
+    /// Light weight (300)
+    pub const LIGHT: Self = Self(300);
+
+    /// Normal weight (400)
+    pub const NORMAL: Self = Self(400);
+
+    /// Medium weight (500)
+    pub const MEDIUM: Self = Self(500);
+
+    /// Semi-bold weight (600)
+    pub const SEMI_BOLD: Self = Self(600);
+
+    /// Bold weight (700)
+    pub const BOLD: Self = Self(700);
+
+    /// Black weight (900)
+    pub const BLACK: Self = Self(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// Slant of a font
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FontStyle {
+    /// Upright glyphs
+    #[default]
+    Normal,
+
+    /// Cursive glyphs designed as italics
+    Italic,
+
+    /// Slanted upright glyphs
+    Oblique,
+}
+
+/// Font requested by family, weight and style
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct FontDescriptor {
+    /// Font family
+    pub family: FontFamily,
+
+    /// Stroke thickness
+    pub weight: FontWeight,
+
+    /// Slant
+    pub style: FontStyle,
+}
+
+impl FontDescriptor {
+    /// Create FontDescriptor of a normal weight, upright font of `family`
+    pub fn new(family: FontFamily) -> Self {
+        Self {
+            family,
+            weight: FontWeight::NORMAL,
+            style: FontStyle::Normal,
+        }
+    }
+
+    /// Return the FontDescriptor with `weight`
+    pub fn with_weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Return the FontDescriptor with `style`
+    pub fn with_style(mut self, style: FontStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Source of shapers for font descriptors, such as a [`FontLibrary`]
+pub trait FontSource {
+    /// Shaper returned for descriptors
+    type Shaper: TextShaper;
+
+    /// Shaper for the font closest to `font`, `None` if there are no fonts at all
+    fn font(&mut self, font: &FontDescriptor) -> Option<Self::Shaper>;
+}
+
+/// Collection of fonts selected by [`FontDescriptor`]
+///
+/// # Notes
+///
+/// - Faces are parsed when first selected and cached afterwards
+/// - A descriptor without a matching family gets the closest sans-serif face, then any face
+///
+/// # Examples
+/// ```no_run
+/// use ho_gui::text::library::{FontDescriptor, FontFamily, FontLibrary, FontSource, FontWeight};
+///
+/// let mut fonts = FontLibrary::new();
+/// fonts.load_system_fonts();
+///
+/// let bold = FontDescriptor::new(FontFamily::Monospace).with_weight(FontWeight::BOLD);
+/// assert!(fonts.font(&bold).is_some());
+/// ```
+pub struct FontLibrary {
+    database: fontdb::Database,
+    fonts: HashMap<fontdb::ID, Font>,
+}
+
+impl FontLibrary {
+    /// Create FontLibrary without any fonts
+    pub fn new() -> Self {
+        Self {
+            database: fontdb::Database::new(),
+            fonts: HashMap::new(),
+        }
+    }
+
+    /// Add the fonts installed on the system
+    pub fn load_system_fonts(&mut self) {
+        self.database.load_system_fonts();
+    }
+
+    /// Add all faces of the font file at `path`
+    ///
+    /// # Errors
+    ///
+    /// `FontError::Io` if the file can't be read
+    pub fn load_font_file(&mut self, path: impl AsRef<Path>) -> Result<(), FontError> {
+        self.database
+            .load_font_file(path)
+            .map_err(|error| FontError::Io(error.kind()))
+    }
+
+    /// Add all faces of font file `data`, data that isn't a font is ignored
+    pub fn load_font_data(&mut self, data: Vec<u8>) {
+        self.database.load_font_data(data);
+    }
+
+    /// Use the family named `family` for [`FontFamily::SansSerif`]
+    pub fn set_sans_serif_family(&mut self, family: impl Into<String>) {
+        self.database.set_sans_serif_family(family);
+    }
+
+    /// Use the family named `family` for [`FontFamily::Serif`]
+    pub fn set_serif_family(&mut self, family: impl Into<String>) {
+        self.database.set_serif_family(family);
+    }
+
+    /// Use the family named `family` for [`FontFamily::Monospace`]
+    pub fn set_monospace_family(&mut self, family: impl Into<String>) {
+        self.database.set_monospace_family(family);
+    }
+
+    /// Number of loaded faces
+    pub fn len(&self) -> usize {
+        self.database.len()
+    }
+
+    /// Whether no faces are loaded
+    pub fn is_empty(&self) -> bool {
+        self.database.is_empty()
+    }
+
+    fn query(&self, font: &FontDescriptor) -> Option<fontdb::ID> {
+        let family = match &font.family {
+            FontFamily::Named(name) => fontdb::Family::Name(name),
+            FontFamily::SansSerif => fontdb::Family::SansSerif,
+            FontFamily::Serif => fontdb::Family::Serif,
+            FontFamily::Monospace => fontdb::Family::Monospace,
+        };
+        let style = match font.style {
+            FontStyle::Normal => fontdb::Style::Normal,
+            FontStyle::Italic => fontdb::Style::Italic,
+            FontStyle::Oblique => fontdb::Style::Oblique,
+        };
+
+        self.database
+            .query(&fontdb::Query {
+                families: &[family, fontdb::Family::SansSerif],
+                weight: fontdb::Weight(font.weight.0),
+                stretch: fontdb::Stretch::Normal,
+                style,
+            })
+            .or_else(|| self.database.faces().next().map(|face| face.id))
+    }
+}
+
+impl FontSource for FontLibrary {
+    type Shaper = Font;
+
+    fn font(&mut self, font: &FontDescriptor) -> Option<Font> {
+        let id = self.query(font)?;
+        if let Some(font) = self.fonts.get(&id) {
+            return Some(font.clone());
+        }
+
+        let font = self
+            .database
+            .with_face_data(id, |data, index| Font::from_data(data.to_vec(), index))?
+            .ok()?;
+        self.fonts.insert(id, font.clone());
+        Some(font)
+    }
+}
+
+impl Default for FontLibrary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for FontLibrary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FontLibrary")
+            .field("faces", &self.database.len())
+            .field("cached", &self.fonts.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEJAVU: &str = "/usr/share/fonts/truetype/dejavu";
+
+    #[test]
+    fn test_empty_library_has_no_fonts() {
+        let mut fonts = FontLibrary::new();
+
+        assert!(fonts.is_empty());
+        assert!(fonts.font(&FontDescriptor::default()).is_none());
+        assert_eq!(
+            fonts.load_font_file("/nonexistent/font.ttf"),
+            Err(FontError::Io(std::io::ErrorKind::NotFound))
+        );
+    }
+
+    #[test]
+    fn test_descriptor_builders() {
+        let font = FontDescriptor::new(FontFamily::Monospace)
+            .with_weight(FontWeight::BOLD)
+            .with_style(FontStyle::Italic);
+
+        assert_eq!(font.family, FontFamily::Monospace);
+        assert_eq!(font.weight, FontWeight(700));
+        assert_eq!(font.style, FontStyle::Italic);
+        assert_eq!(FontDescriptor::default().weight, FontWeight::NORMAL);
+    }
+
+    #[test]
+    fn test_select_by_family_and_weight() {
+        // Only runs where DejaVu is installed
+        let mut fonts = FontLibrary::new();
+        for file in [
+            "DejaVuSans.ttf",
+            "DejaVuSans-Bold.ttf",
+            "DejaVuSansMono.ttf",
+        ] {
+            if fonts.load_font_file(format!("{DEJAVU}/{file}")).is_err() {
+                return;
+            }
+        }
+        fonts.set_sans_serif_family("DejaVu Sans");
+        fonts.set_monospace_family("DejaVu Sans Mono");
+
+        let regular = fonts.font(&FontDescriptor::default()).unwrap();
+        let bold = fonts
+            .font(&FontDescriptor::default().with_weight(FontWeight::BOLD))
+            .unwrap();
+        let mono = fonts
+            .font(&FontDescriptor::new(FontFamily::Monospace))
+            .unwrap();
+        let advance = |font: &Font, text| font.shape(text, 10.0)[0].advance;
+
+        assert!(advance(&bold, "i") > advance(&regular, "i"));
+        assert_eq!(advance(&mono, "i"), advance(&mono, "W"));
+
+        // Unknown families fall back to sans-serif
+        let missing = fonts
+            .font(&FontDescriptor::new(FontFamily::Named("Missing".into())))
+            .unwrap();
+        assert_eq!(advance(&missing, "i"), advance(&regular, "i"));
+    }
+}
@@ -0,0 +1,546 @@
+//! Line break opportunities following the Unicode line breaking algorithm (UAX #14)
+//!
+//! The pair rules LB4 to LB31 are applied to line break classes looked up from a compact table
+//! covering Latin punctuation, numbers, spaces, combining marks, the CJK blocks, emoji and the
+//! Thai, Lao, Khmer and Myanmar scripts. Characters outside of the table are alphabetic (`AL`),
+//! which never break between each other, so unusual scripts wrap at spaces like Latin text.
+//!
+//! Thai, Lao, Khmer and Myanmar (`SA`) don't separate words with spaces, and finding the words
+//! needs a dictionary. Without one, these scripts may break before any consonant or independent
+//! vowel, but not before their dependent vowels and marks nor after a leading vowel or a virama,
+//! so long runs still wrap without splitting clusters.
+
+/// Kind of a line break opportunity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Break {
+    /// The line must end here, after a newline or at the end of the text
+    Mandatory,
+
+    /// The line may end here if the next word doesn't fit
+    Allowed,
+}
+
+/// Line break opportunities of `text`
+///
+/// # Returns
+///
+/// Iterator of byte offsets where a new line may start, with the kind of break. The end of the
+/// text is always a mandatory break, unless the text is empty.
+///
+/// # Examples
+/// ```
+/// use ho_gui::text::line_break::{Break, line_breaks};
+///
+/// let breaks: Vec<_> = line_breaks("Hello, world!\nBye").collect();
+///
+/// assert_eq!(
+///     breaks,
+///     [(7, Break::Allowed), (14, Break::Mandatory), (17, Break::Mandatory)]
+/// );
+/// ```
+pub fn line_breaks(text: &str) -> LineBreaks<'_> {
+    LineBreaks {
+        text,
+        chars: text.char_indices(),
+        state: None,
+        done: false,
+    }
+}
+
+/// Iterator returned by [`line_breaks`]
+#[derive(Debug, Clone)]
+pub struct LineBreaks<'a> {
+    text: &'a str,
+    chars: std::str::CharIndices<'a>,
+    state: Option<State>,
+    done: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    // Class of the previous character, with combining marks taking their base's class (LB9)
+    previous: Class,
+
+    // Class of the last character that was not a space, for the `X SP* ×` rules
+    before_spaces: Class,
+
+    // Class of the previous character as is, for LB8a
+    previous_raw: Class,
+
+    // Whether the previous character ends an odd run of regional indicators, for LB30a
+    odd_regional: bool,
+}
+
+impl Iterator for LineBreaks<'_> {
+    type Item = (usize, Break);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        for (index, c) in self.chars.by_ref() {
+            let class = Class::of(c);
+            let Some(state) = &mut self.state else {
+                // LB10: marks at the start are alphabetic
+                let class = class.resolve_mark(Class::AL);
+                self.state = Some(State {
+                    previous: class,
+                    before_spaces: class,
+                    previous_raw: class,
+                    odd_regional: class == Class::RI,
+                });
+                continue;
+            };
+
+            let result = state.step(class);
+            if let Some(kind) = result {
+                return Some((index, kind));
+            }
+        }
+
+        self.done = true;
+        (!self.text.is_empty()).then_some((self.text.len(), Break::Mandatory))
+    }
+}
+
+impl State {
+    /// Break before a character of `class`, and move past it
+    fn step(&mut self, class: Class) -> Option<Break> {
+        use Class::*;
+
+        let previous = self.previous;
+        let previous_raw = self.previous_raw;
+        self.previous_raw = class;
+
+        // LB4, LB5
+        if matches!(previous, BK | LF | NL) || (previous == CR && class != LF) {
+            self.advance(class.resolve_mark(AL));
+            return Some(Break::Mandatory);
+        }
+        // LB6, LB7
+        if matches!(class, BK | CR | LF | NL | SP | ZW) {
+            self.advance(class);
+            return None;
+        }
+        // LB8
+        if self.before_spaces == ZW {
+            self.advance(class.resolve_mark(AL));
+            return Some(Break::Allowed);
+        }
+        // LB8a, and the consonant stacked by a virama
+        if matches!(previous_raw, ZWJ | VI) {
+            self.advance(class.resolve_mark(AL));
+            return None;
+        }
+        // LB9: marks take the class of their base, LB10: or are alphabetic after spaces
+        let class = if matches!(class, CM | ZWJ | VI) {
+            if previous == SP {
+                AL
+            } else {
+                return None;
+            }
+        } else {
+            class
+        };
+
+        // LB30a: regional indicators pair up into flags
+        let allowed = !(class == RI && previous == RI && self.odd_regional)
+            && pair_allows_break(previous, self.before_spaces, class);
+        self.advance(class);
+        allowed.then_some(Break::Allowed)
+    }
+
+    fn advance(&mut self, class: Class) {
+        self.odd_regional = class == Class::RI && !self.odd_regional;
+        self.previous = class;
+        if class != Class::SP {
+            self.before_spaces = class;
+        }
+    }
+}
+
+/// Rules LB11 to LB31 for a break between `previous` and `class`
+fn pair_allows_break(previous: Class, before_spaces: Class, class: Class) -> bool {
+    use Class::*;
+
+    // LB11, LB12, LB12a
+    if class == WJ || previous == WJ || previous == GL {
+        return false;
+    }
+    if class == GL && !matches!(previous, SP | BA | HY) {
+        return false;
+    }
+    // LB13
+    if matches!(class, CL | CP | EX | IS | SY) {
+        return false;
+    }
+    // LB14, LB15, LB16, LB17: the character before any spaces
+    if before_spaces == OP
+        || (before_spaces == QU && class == OP)
+        || (matches!(before_spaces, CL | CP) && class == NS)
+        || (before_spaces == B2 && class == B2)
+    {
+        return false;
+    }
+    // LB18
+    if previous == SP {
+        return true;
+    }
+    // LB19, LB21, LB22
+    if class == QU || previous == QU || matches!(class, BA | HY | NS | IN) || previous == BB {
+        return false;
+    }
+    // Without a dictionary, complex context scripts break between clusters (see the module docs),
+    // and are alphabetic otherwise (LB1)
+    if previous == SA && class == SA {
+        return true;
+    }
+    let (previous, class) = (previous.resolve_complex(), class.resolve_complex());
+
+    let keep_together = match (previous, class) {
+        // LB23, LB23a, LB24
+        (AL, NU) | (NU, AL) | (PR, ID | EB | EM) | (ID | EB | EM, PO) => true,
+        (PR | PO, AL) | (AL, PR | PO) => true,
+        // LB25, numbers and their prefixes, suffixes and separators
+        (PR | PO, OP | NU) | (OP | HY, NU) | (SY | IS, NU) => true,
+        (NU, NU | SY | IS | CL | CP | PO | PR) => true,
+        // LB28, LB29, LB30
+        (AL, AL) | (IS, AL) | (AL | NU, OP) | (CP, AL | NU) => true,
+        // LB30b
+        (EB, EM) => true,
+        _ => false,
+    };
+
+    // LB31
+    !keep_together
+}
+
+/// Line break class of a character, see UAX #14 table 1
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    /// Mandatory break
+    BK,
+    /// Carriage return
+    CR,
+    /// Line feed
+    LF,
+    /// Next line
+    NL,
+    /// Space
+    SP,
+    /// Zero width space
+    ZW,
+    /// Zero width joiner
+    ZWJ,
+    /// Virama stacking the next consonant, in complex context scripts
+    VI,
+    /// Word joiner
+    WJ,
+    /// Non-breaking ("glue")
+    GL,
+    /// Combining mark
+    CM,
+    /// Break after
+    BA,
+    /// Break before
+    BB,
+    /// Break opportunity before and after
+    B2,
+    /// Hyphen
+    HY,
+    /// Close punctuation
+    CL,
+    /// Close parenthesis
+    CP,
+    /// Open punctuation
+    OP,
+    /// Quotation
+    QU,
+    /// Exclamation or interrogation
+    EX,
+    /// Infix numeric separator
+    IS,
+    /// Symbol allowing a break after
+    SY,
+    /// Non-starter
+    NS,
+    /// Inseparable
+    IN,
+    /// Numeric
+    NU,
+    /// Prefix numeric
+    PR,
+    /// Postfix numeric
+    PO,
+    /// Ideographic
+    ID,
+    /// Emoji base
+    EB,
+    /// Emoji modifier
+    EM,
+    /// Regional indicator
+    RI,
+    /// Complex context dependent (South East Asian)
+    SA,
+    /// Alphabetic
+    AL,
+}
+
+impl Class {
+    fn of(c: char) -> Self {
+        use Class::*;
+
+        match c {
+            '\u{B}' | '\u{C}' | '\u{2028}' | '\u{2029}' => BK,
+            '\r' => CR,
+            '\n' => LF,
+            '\u{85}' => NL,
+            ' ' => SP,
+            '\u{200B}' => ZW,
+            '\u{200D}' => ZWJ,
+            '\u{2060}' | '\u{FEFF}' => WJ,
+            '\u{A0}' | '\u{202F}' | '\u{2007}' | '\u{2011}' | '\u{34F}' => GL,
+            '\t' | '\u{AD}' | '\u{1680}' | '\u{2000}'..='\u{2006}' | '\u{2008}'..='\u{200A}' => BA,
+            '\u{2010}' | '\u{2012}' | '\u{2013}' | '\u{205F}' | '\u{3000}' => BA,
+            '\u{2014}' | '\u{2E3A}' | '\u{2E3B}' => B2,
+            '\u{B4}' | '\u{2C8}' | '\u{2CC}' | '\u{2DF}' => BB,
+            '-' => HY,
+            '}' | '\u{3001}' | '\u{3002}' | '\u{FF0C}' | '\u{FF0E}' | '\u{FF5D}' => CL,
+            '\u{3009}' | '\u{300B}' | '\u{300D}' | '\u{300F}' | '\u{3011}' | '\u{3015}' => CL,
+            '\u{3017}' | '\u{3019}' | '\u{301B}' => CL,
+            ')' | ']' | '\u{FF09}' | '\u{FF3D}' => CP,
+            '(' | '[' | '{' | '\u{A1}' | '\u{BF}' | '\u{FF08}' | '\u{FF3B}' | '\u{FF5B}' => OP,
+            '\u{3008}' | '\u{300A}' | '\u{300C}' | '\u{300E}' | '\u{3010}' | '\u{3014}' => OP,
+            '\u{3016}' | '\u{3018}' | '\u{301A}' => OP,
+            '"'
+            | '\''
+            | '\u{AB}'
+            | '\u{BB}'
+            | '\u{2018}'..='\u{201F}'
+            | '\u{2039}'
+            | '\u{203A}' => QU,
+            '!' | '?' | '\u{FF01}' | '\u{FF1F}' => EX,
+            ',' | '.' | ':' | ';' | '\u{37E}' | '\u{589}' => IS,
+            '/' => SY,
+            '\u{2024}'..='\u{2026}' | '\u{22EF}' => IN,
+            '$' | '+' | '\\' | '\u{A3}' | '\u{A5}' | '\u{B1}' | '\u{20A0}'..='\u{20CF}' => PR,
+            '%' | '\u{A2}' | '\u{B0}' | '\u{2030}' | '\u{2031}' | '\u{2032}'..='\u{2037}' => PO,
+            // Small kana, iteration marks and the prolonged sound mark don't start a line
+            '\u{3005}' | '\u{303B}' | '\u{309D}' | '\u{309E}' | '\u{30FB}'..='\u{30FE}' => NS,
+            '\u{3041}' | '\u{3043}' | '\u{3045}' | '\u{3047}' | '\u{3049}' | '\u{3063}' => NS,
+            '\u{3083}' | '\u{3085}' | '\u{3087}' | '\u{308E}' | '\u{3095}' | '\u{3096}' => NS,
+            '\u{30A1}' | '\u{30A3}' | '\u{30A5}' | '\u{30A7}' | '\u{30A9}' | '\u{30C3}' => NS,
+            '\u{30E3}' | '\u{30E5}' | '\u{30E7}' | '\u{30EE}' | '\u{30F5}' | '\u{30F6}' => NS,
+            '\u{300}'..='\u{36F}' | '\u{1AB0}'..='\u{1AFF}' | '\u{1DC0}'..='\u{1DFF}' => CM,
+            '\u{20D0}'..='\u{20FF}' | '\u{FE00}'..='\u{FE0F}' | '\u{FE20}'..='\u{FE2F}' => CM,
+            '\u{E0100}'..='\u{E01EF}' => CM,
+            '0'..='9' => NU,
+            // Thai, Lao, Khmer and Myanmar, keeping leading vowels with the next consonant
+            '\u{E50}'..='\u{E59}' | '\u{ED0}'..='\u{ED9}' | '\u{17E0}'..='\u{17E9}' => NU,
+            '\u{1040}'..='\u{1049}' | '\u{1090}'..='\u{1099}' => NU,
+            '\u{E3F}' | '\u{17DB}' => PR,
+            '\u{E5A}' | '\u{E5B}' | '\u{17D4}' | '\u{17D5}' | '\u{104A}' | '\u{104B}' => BA,
+            '\u{E40}'..='\u{E44}' | '\u{EC0}'..='\u{EC4}' => BB,
+            '\u{17D2}' | '\u{1039}' => VI,
+            '\u{E2F}'..='\u{E3A}' | '\u{E45}'..='\u{E4E}' | '\u{EAF}'..='\u{EBC}' => CM,
+            '\u{EC6}'..='\u{ECE}' | '\u{17B4}'..='\u{17D3}' | '\u{17D7}' | '\u{17DD}' => CM,
+            '\u{102B}'..='\u{103E}' | '\u{1056}'..='\u{1059}' | '\u{105E}'..='\u{1060}' => CM,
+            '\u{1062}'..='\u{1064}' | '\u{1067}'..='\u{106D}' | '\u{1071}'..='\u{1074}' => CM,
+            '\u{1082}'..='\u{108D}' | '\u{108F}' | '\u{109A}'..='\u{109D}' => CM,
+            '\u{E00}'..='\u{EFF}' | '\u{1000}'..='\u{109F}' | '\u{1780}'..='\u{17FF}' => SA,
+            // Emoji taking skin tone modifiers, and the flag letters
+            '\u{261D}' | '\u{26F9}' | '\u{270A}'..='\u{270D}' | '\u{1F385}' => EB,
+            '\u{1F3C2}'..='\u{1F3C4}' | '\u{1F3C7}' | '\u{1F3CA}'..='\u{1F3CC}' => EB,
+            '\u{1F442}' | '\u{1F443}' | '\u{1F446}'..='\u{1F450}' | '\u{1F466}'..='\u{1F478}' => EB,
+            '\u{1F47C}' | '\u{1F481}'..='\u{1F483}' | '\u{1F485}'..='\u{1F487}' | '\u{1F48F}' => EB,
+            '\u{1F491}' | '\u{1F4AA}' | '\u{1F574}' | '\u{1F575}' | '\u{1F57A}' | '\u{1F590}' => EB,
+            '\u{1F595}' | '\u{1F596}' | '\u{1F645}'..='\u{1F647}' | '\u{1F64B}'..='\u{1F64F}' => EB,
+            '\u{1F6A3}' | '\u{1F6B4}'..='\u{1F6B6}' | '\u{1F6C0}' | '\u{1F6CC}' | '\u{1F90C}' => EB,
+            '\u{1F90F}' | '\u{1F918}'..='\u{1F91F}' | '\u{1F926}' | '\u{1F930}'..='\u{1F939}' => EB,
+            '\u{1F93C}'..='\u{1F93E}' | '\u{1F977}' | '\u{1F9B5}' | '\u{1F9B6}' | '\u{1F9B8}' => EB,
+            '\u{1F9B9}' | '\u{1F9BB}' | '\u{1F9CD}'..='\u{1F9CF}' | '\u{1F9D1}'..='\u{1F9DD}' => EB,
+            '\u{1FAC3}'..='\u{1FAC5}' | '\u{1FAF0}'..='\u{1FAF8}' => EB,
+            '\u{1F3FB}'..='\u{1F3FF}' => EM,
+            '\u{1F1E6}'..='\u{1F1FF}' => RI,
+            '\u{2E80}'..='\u{2FFF}' | '\u{3040}'..='\u{30FF}' | '\u{3400}'..='\u{4DBF}' => ID,
+            '\u{4E00}'..='\u{9FFF}' | '\u{AC00}'..='\u{D7AF}' | '\u{F900}'..='\u{FAFF}' => ID,
+            '\u{FF00}'..='\u{FFEF}' | '\u{1F000}'..='\u{1FAFF}' | '\u{20000}'..='\u{3FFFD}' => ID,
+            c if c.is_numeric() => NU,
+            c if c.is_control() => CM,
+            _ => AL,
+        }
+    }
+
+    /// Class of a combining mark without a base
+    fn resolve_mark(self, base: Class) -> Class {
+        match self {
+            Class::CM | Class::ZWJ | Class::VI => base,
+            class => class,
+        }
+    }
+
+    /// Class of a complex context character outside of its script runs (LB1)
+    fn resolve_complex(self) -> Class {
+        match self {
+            Class::SA => Class::AL,
+            class => class,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed(text: &str) -> Vec<usize> {
+        line_breaks(text)
+            .filter(|(_, kind)| *kind == Break::Allowed)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn lines(text: &str) -> Vec<&str> {
+        let mut start = 0;
+        line_breaks(text)
+            .map(|(end, _)| {
+                let line = &text[start..end];
+                start = end;
+                line
+            })
+            .collect()
+    }
+
+    mod spaces {
+        use super::*;
+
+        #[test]
+        fn test_breaks_after_spaces() {
+            assert_eq!(lines("one two  three"), ["one ", "two  ", "three"]);
+        }
+
+        #[test]
+        fn test_empty_text_has_no_breaks() {
+            assert_eq!(line_breaks("").count(), 0);
+            assert_eq!(lines("word"), ["word"]);
+        }
+
+        #[test]
+        fn test_non_breaking_spaces_glue() {
+            assert_eq!(allowed("10\u{A0}km away"), [7]);
+            assert_eq!(allowed("a\u{2060}b c"), [6]);
+        }
+
+        #[test]
+        fn test_zero_width_space_breaks() {
+            assert_eq!(lines("long\u{200B}word"), ["long\u{200B}", "word"]);
+        }
+    }
+
+    mod mandatory {
+        use super::*;
+
+        #[test]
+        fn test_newlines_break() {
+            let breaks: Vec<_> = line_breaks("a\nb\r\nc\rd").collect();
+
+            assert_eq!(
+                breaks,
+                [
+                    (2, Break::Mandatory),
+                    (5, Break::Mandatory),
+                    (7, Break::Mandatory),
+                    (8, Break::Mandatory),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_trailing_newline_is_the_end() {
+            assert_eq!(
+                line_breaks("a\n").collect::<Vec<_>>(),
+                [(2, Break::Mandatory)]
+            );
+        }
+    }
+
+    mod punctuation {
+        use super::*;
+
+        #[test]
+        fn test_breaks_after_hyphens_but_not_before_numbers() {
+            assert_eq!(lines("well-known"), ["well-", "known"]);
+            assert_eq!(allowed("-5 degrees"), [3]);
+        }
+
+        #[test]
+        fn test_closing_punctuation_stays_on_the_line() {
+            assert_eq!(allowed("(see above) then!"), [5, 12]);
+            assert_eq!(allowed("wait ! really ?"), [7]);
+        }
+
+        #[test]
+        fn test_numbers_stay_together() {
+            assert_eq!(allowed("$1,234.50 and 15% off"), [10, 14, 18]);
+        }
+
+        #[test]
+        fn test_urls_break_after_slashes() {
+            assert_eq!(lines("a/b"), ["a/", "b"]);
+        }
+
+        #[test]
+        fn test_quotes_stay_with_their_words() {
+            assert_eq!(allowed("say \"hi there\" now"), [4, 8, 15]);
+        }
+    }
+
+    mod scripts {
+        use super::*;
+
+        #[test]
+        fn test_breaks_between_ideographs() {
+            assert_eq!(lines("漢字です"), ["漢", "字", "で", "す"]);
+        }
+
+        #[test]
+        fn test_cjk_punctuation_and_small_kana_dont_start_lines() {
+            assert_eq!(lines("です。ちょっと"), ["で", "す。", "ちょっ", "と"]);
+        }
+
+        #[test]
+        fn test_combining_marks_stay_with_their_base() {
+            assert_eq!(lines("e\u{301} a\u{301}"), ["e\u{301} ", "a\u{301}"]);
+            assert_eq!(allowed("漢\u{301}字"), [5]);
+        }
+
+        #[test]
+        fn test_emoji_modifiers_stay_with_their_base() {
+            assert_eq!(lines("👍🏽"), ["👍🏽"]);
+            assert_eq!(lines("👍🏽👍"), ["👍🏽", "👍"]);
+            assert_eq!(allowed("ok 👋🏻!"), [3]);
+        }
+
+        #[test]
+        fn test_regional_indicators_pair_into_flags() {
+            assert_eq!(lines("🇩🇪🇫🇷"), ["🇩🇪", "🇫🇷"]);
+            assert_eq!(lines("🇩🇪🇫"), ["🇩🇪", "🇫"]);
+            assert_eq!(allowed("a🇩🇪 🇫🇷"), [1, 10]);
+        }
+
+        #[test]
+        fn test_thai_breaks_between_clusters() {
+            // Dependent vowels stay with their consonant, leading vowels with the next one
+            assert_eq!(lines("ภาษาไทย"), ["ภา", "ษา", "ไท", "ย"]);
+            assert_eq!(allowed("ที่นี่"), [9]);
+            assert_eq!(allowed("Thai ไทย"), [5, 11]);
+        }
+
+        #[test]
+        fn test_viramas_stack_the_next_consonant() {
+            assert!(allowed("ក្ក").is_empty());
+            assert_eq!(allowed("ក្កក"), [9]);
+        }
+    }
+}
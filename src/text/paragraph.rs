@@ -0,0 +1,686 @@
+//! Paragraph layout: wrapping, alignment, line height and truncation of shaped text
+//!
+//! Text is shaped once, then broken into lines at the [`line_breaks`] opportunities that fit the
+//! maximum width. Words wider than a whole line are split between glyph clusters. Trailing spaces
+//! hang past the end of their line, so they don't count toward its width or alignment.
+
+use std::ops::Range;
+
+use super::line_break::{Break, line_breaks};
+use super::{FontMetrics, Glyph, TextShaper};
+use crate::layout::to_valid;
+use crate::math::{Point, Rect, Size};
+
+/// Horizontal alignment of lines in a paragraph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextAlign {
+    /// Lines start at the left edge
+    #[default]
+    Left,
+
+    /// Lines are centered
+    Center,
+
+    /// Lines end at the right edge
+    Right,
+}
+
+/// Distance between the tops of consecutive lines
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineHeight {
+    /// Ascent, descent and line gap recommended by the font
+    #[default]
+    Normal,
+
+    /// Multiple of the font size
+    Relative(f32),
+
+    /// Fixed height in pixels
+    Absolute(f32),
+}
+
+/// Paragraph layout settings
+///
+/// # Notes
+///
+/// - Lines are laid out left to right; right-to-left text is not reordered
+/// - Extra space of a line taller than its text is split evenly above and below it, like CSS
+///
+/// # Examples
+/// ```no_run
+/// use ho_gui::text::Font;
+/// use ho_gui::text::paragraph::{Paragraph, TextAlign};
+///
+/// let font = Font::load("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf", 0).unwrap();
+/// let paragraph = Paragraph::new()
+///     .with_max_width(120.0)
+///     .with_align(TextAlign::Center)
+///     .with_max_lines(2);
+///
+/// let layout = paragraph.layout(&font, "A label too long to fit on one line", 14.0);
+/// assert!(layout.size.width <= 120.0);
+/// assert_eq!(layout.lines.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paragraph {
+    /// Width lines wrap at, `None` to break only at newlines
+    pub max_width: Option<f32>,
+
+    /// Horizontal alignment of the lines
+    pub align: TextAlign,
+
+    /// Distance between the tops of consecutive lines
+    pub line_height: LineHeight,
+
+    /// Number of lines kept, `None` for all of them
+    pub max_lines: Option<usize>,
+
+    /// Text ending the last kept line when lines are cut off
+    pub ellipsis: String,
+}
+
+impl Paragraph {
+    /// Create Paragraph without wrapping, aligned left, ending truncated text with "…"
+    pub fn new() -> Self {
+        Self {
+            max_width: None,
+            align: TextAlign::Left,
+            line_height: LineHeight::Normal,
+            max_lines: None,
+            ellipsis: String::from("\u{2026}"),
+        }
+    }
+
+    /// Return the Paragraph wrapping lines at `max_width`, negative or NaN widths are 0.0
+    pub fn with_max_width(mut self, max_width: f32) -> Self {
+        self.max_width = Some(to_valid(max_width));
+        self
+    }
+
+    /// Return the Paragraph aligning lines by `align`
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Return the Paragraph with `line_height`
+    pub fn with_line_height(mut self, line_height: LineHeight) -> Self {
+        self.line_height = line_height;
+        self
+    }
+
+    /// Return the Paragraph keeping at most `max_lines` lines, at least one
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines.max(1));
+        self
+    }
+
+    /// Return the Paragraph ending truncated text with `ellipsis`, empty for none
+    pub fn with_ellipsis(mut self, ellipsis: impl Into<String>) -> Self {
+        self.ellipsis = ellipsis.into();
+        self
+    }
+
+    /// Lay out `text` shaped by `shaper` at `size` pixels per em
+    pub fn layout(&self, shaper: &impl TextShaper, text: &str, size: f32) -> TextLayout {
+        self.layout_runs(
+            text,
+            &[TextRun {
+                range: 0..text.len(),
+                shaper,
+                size,
+            }],
+        )
+    }
+
+    /// Size of `text` shaped by `shaper` at `size` pixels per em
+    ///
+    /// # Returns
+    ///
+    /// Width of the widest line and height of all lines, see [`TextLayout::size`]
+    pub fn measure(&self, shaper: &impl TextShaper, text: &str, size: f32) -> Size {
+        self.layout(shaper, text, size).size
+    }
+
+    /// Lay out `text` made of `runs` shaped separately, covering the text in order
+    pub(crate) fn layout_runs(&self, text: &str, runs: &[TextRun<'_>]) -> TextLayout {
+        let shaped = Shaped::new(text, runs);
+        let max_width = self.max_width.unwrap_or(f32::INFINITY);
+
+        let mut ranges = shaped.break_lines(max_width);
+        let truncated = self
+            .max_lines
+            .is_some_and(|max_lines| ranges.len() > max_lines);
+        let mut ellipsis = None;
+        if let Some(max_lines) = self.max_lines.filter(|_| truncated) {
+            ranges.truncate(max_lines);
+            if let Some(last) = ranges.last_mut() {
+                ellipsis = Some(shaped.add_ellipsis(last, &self.ellipsis, max_width));
+            }
+        }
+
+        let mut lines = Vec::with_capacity(ranges.len());
+        let mut top = 0.0;
+        for (index, range) in ranges.iter().enumerate() {
+            let ellipsis = ellipsis.as_ref().filter(|_| index + 1 == ranges.len());
+            let line = shaped.line(range.clone(), ellipsis, top, self.line_height);
+            top += line.rect.size.height;
+            lines.push(line);
+        }
+
+        let widest = lines
+            .iter()
+            .map(|line| line.rect.size.width)
+            .fold(0.0, f32::max);
+        let align_width = if max_width.is_finite() {
+            max_width
+        } else {
+            widest
+        };
+        for line in &mut lines {
+            let shift = match self.align {
+                TextAlign::Left => 0.0,
+                TextAlign::Center => (align_width - line.rect.size.width) / 2.0,
+                TextAlign::Right => align_width - line.rect.size.width,
+            };
+            line.rect.pos.x += shift;
+            for glyph in &mut line.glyphs {
+                glyph.position.x += shift;
+            }
+        }
+
+        TextLayout {
+            lines,
+            size: Size::new(widest, top),
+            truncated,
+        }
+    }
+}
+
+impl Default for Paragraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Laid out paragraph, with the top left corner of its box at (0.0, 0.0)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    /// Lines from top to bottom
+    pub lines: Vec<TextLine>,
+
+    /// Width of the widest line and height of all lines
+    pub size: Size,
+
+    /// Lines were cut off by `Paragraph::max_lines`
+    pub truncated: bool,
+}
+
+/// Line of a [`TextLayout`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+    /// Byte range of the text on the line, without the part replaced by an ellipsis
+    pub range: Range<usize>,
+
+    /// Box of the line: aligned visible width by line height
+    pub rect: Rect,
+
+    /// Vertical position of the baseline
+    pub baseline: f32,
+
+    /// Glyphs of the line, followed by the ellipsis glyphs of a truncated last line
+    pub glyphs: Vec<PositionedGlyph>,
+}
+
+/// Glyph placed in a [`TextLayout`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedGlyph {
+    /// Glyph index in the font
+    pub id: u16,
+
+    /// Byte offset of the glyph's cluster in the text
+    pub cluster: usize,
+
+    /// Pen position on the baseline, moved by the glyph's shaping offset
+    pub position: Point,
+
+    /// Distance the pen moves after drawing the glyph
+    pub advance: f32,
+
+    /// Index of the run the glyph was shaped by, 0 for plain paragraphs
+    pub run: usize,
+}
+
+/// Part of a paragraph shaped with one font and size
+#[derive(Clone)]
+pub(crate) struct TextRun<'a> {
+    pub range: Range<usize>,
+    pub shaper: &'a dyn TextShaper,
+    pub size: f32,
+}
+
+/// Glyphs of all runs, with cluster widths for line breaking
+struct Shaped<'a> {
+    text: &'a str,
+    runs: &'a [TextRun<'a>],
+    // (glyph, run) in logical order, with clusters relative to the whole text
+    glyphs: Vec<(Glyph, usize)>,
+    // offsets[i] is the width of the text before byte i
+    offsets: Vec<f32>,
+    cluster_starts: Vec<bool>,
+}
+
+impl<'a> Shaped<'a> {
+    fn new(text: &'a str, runs: &'a [TextRun<'a>]) -> Self {
+        let mut glyphs = Vec::new();
+        let mut advances = vec![0.0; text.len()];
+        let mut cluster_starts = vec![false; text.len() + 1];
+        cluster_starts[text.len()] = true;
+
+        for (index, run) in runs.iter().enumerate() {
+            for mut glyph in run.shaper.shape(&text[run.range.clone()], run.size) {
+                glyph.cluster += run.range.start;
+                // Newlines end lines, they have nothing to draw
+                if text[glyph.cluster..].starts_with(is_newline) {
+                    continue;
+                }
+                advances[glyph.cluster] += glyph.advance;
+                cluster_starts[glyph.cluster] = true;
+                glyphs.push((glyph, index));
+            }
+        }
+
+        let mut offsets = Vec::with_capacity(text.len() + 1);
+        offsets.push(0.0);
+        for advance in advances {
+            offsets.push(offsets[offsets.len() - 1] + advance);
+        }
+
+        Self {
+            text,
+            runs,
+            glyphs,
+            offsets,
+            cluster_starts,
+        }
+    }
+
+    fn width(&self, range: Range<usize>) -> f32 {
+        self.offsets[range.end] - self.offsets[range.start]
+    }
+
+    /// End of `range` without hanging whitespace
+    fn visible_end(&self, range: Range<usize>) -> usize {
+        range.start + self.text[range].trim_end_matches(is_hanging).len()
+    }
+
+    /// Byte ranges of the lines of the text wrapped at `max_width`
+    fn break_lines(&self, max_width: f32) -> Vec<Range<usize>> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        let mut end = 0;
+
+        for (position, kind) in line_breaks(self.text) {
+            let visible = self.visible_end(end..position);
+            if end > start && self.width(start..visible) > max_width {
+                lines.push(start..end);
+                start = end;
+            }
+
+            // Words wider than a line are split between clusters
+            loop {
+                let visible = self.visible_end(start..position);
+                if self.width(start..visible) <= max_width {
+                    break;
+                }
+                let split = self.fit(start..visible, max_width);
+                if split >= visible {
+                    break;
+                }
+                lines.push(start..split);
+                start = split;
+            }
+
+            end = position;
+            if kind == Break::Mandatory {
+                lines.push(start..end);
+                start = end;
+            }
+        }
+
+        if lines.is_empty() {
+            lines.push(0..0);
+        }
+        lines
+    }
+
+    /// Last cluster boundary in `range` fitting `max_width` from its start, at least one cluster
+    fn fit(&self, range: Range<usize>, max_width: f32) -> usize {
+        let mut boundaries = (range.start + 1..=range.end).filter(|&end| self.cluster_starts[end]);
+        let first = boundaries.next().unwrap_or(range.end);
+
+        boundaries
+            .take_while(|&end| self.width(range.start..end) <= max_width)
+            .last()
+            .unwrap_or(first)
+    }
+
+    /// Cut `line` so it fits `max_width` followed by `ellipsis`
+    ///
+    /// # Returns
+    ///
+    /// Ellipsis glyphs shaped by the run at the cut, with their run index and width
+    fn add_ellipsis(
+        &self,
+        line: &mut Range<usize>,
+        ellipsis: &str,
+        max_width: f32,
+    ) -> (Vec<(Glyph, usize)>, f32) {
+        let visible = self.visible_end(line.clone());
+        let run_index = self.run_at(visible.saturating_sub(1).max(line.start));
+        let Some(run) = self.runs.get(run_index) else {
+            return (Vec::new(), 0.0);
+        };
+
+        let glyphs: Vec<(Glyph, usize)> = run
+            .shaper
+            .shape(ellipsis, run.size)
+            .into_iter()
+            .map(|glyph| (glyph, run_index))
+            .collect();
+        let ellipsis_width: f32 = glyphs.iter().map(|(glyph, _)| glyph.advance).sum();
+
+        let mut cut = visible;
+        while cut > line.start && self.width(line.start..cut) + ellipsis_width > max_width {
+            cut = (line.start..cut)
+                .rev()
+                .find(|&end| self.cluster_starts[end])
+                .unwrap_or(line.start);
+        }
+        line.end = self.visible_end(line.start..cut);
+
+        let glyphs = glyphs
+            .into_iter()
+            .map(|(glyph, run)| {
+                (
+                    Glyph {
+                        cluster: line.end,
+                        ..glyph
+                    },
+                    run,
+                )
+            })
+            .collect();
+        (glyphs, ellipsis_width)
+    }
+
+    fn run_at(&self, position: usize) -> usize {
+        self.runs
+            .iter()
+            .position(|run| run.range.contains(&position))
+            .unwrap_or(self.runs.len().saturating_sub(1))
+    }
+
+    /// Line of text `range` at `top`, glyphs starting at x = 0.0
+    fn line(
+        &self,
+        range: Range<usize>,
+        ellipsis: Option<&(Vec<(Glyph, usize)>, f32)>,
+        top: f32,
+        line_height: LineHeight,
+    ) -> TextLine {
+        let mut metrics = FontMetrics::default();
+        let mut size: f32 = 0.0;
+        for (index, run) in self.runs.iter().enumerate() {
+            let overlaps = run.range.start < range.end && range.start < run.range.end;
+            if overlaps || index == self.run_at(range.start) {
+                let run_metrics = run.shaper.metrics(run.size);
+                metrics.ascent = metrics.ascent.max(run_metrics.ascent);
+                metrics.descent = metrics.descent.max(run_metrics.descent);
+                metrics.line_gap = metrics.line_gap.max(run_metrics.line_gap);
+                size = size.max(run.size);
+            }
+        }
+
+        let height = match line_height {
+            LineHeight::Normal => metrics.line_height(),
+            LineHeight::Relative(factor) => to_valid(factor) * size,
+            LineHeight::Absolute(height) => to_valid(height),
+        };
+        let baseline = top + (height - metrics.ascent - metrics.descent) / 2.0 + metrics.ascent;
+
+        let first = self
+            .glyphs
+            .partition_point(|(glyph, _)| glyph.cluster < range.start);
+        let last = self
+            .glyphs
+            .partition_point(|(glyph, _)| glyph.cluster < range.end);
+        let ellipsis_glyphs = ellipsis.map_or(&[][..], |(glyphs, _)| glyphs);
+
+        let mut pen = 0.0;
+        let glyphs = self.glyphs[first..last]
+            .iter()
+            .chain(ellipsis_glyphs)
+            .map(|&(glyph, run)| {
+                let position = Point::new(pen + glyph.offset.x, baseline + glyph.offset.y);
+                pen += glyph.advance;
+                PositionedGlyph {
+                    id: glyph.id,
+                    cluster: glyph.cluster,
+                    position,
+                    advance: glyph.advance,
+                    run,
+                }
+            })
+            .collect();
+
+        let width = self.width(range.start..self.visible_end(range.clone()))
+            + ellipsis.map_or(0.0, |(_, width)| *width);
+
+        TextLine {
+            range,
+            rect: Rect::new(0.0, top, width, height),
+            baseline,
+            glyphs,
+        }
+    }
+}
+
+fn is_newline(c: char) -> bool {
+    matches!(
+        c,
+        '\n' | '\r' | '\u{B}' | '\u{C}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+/// Whitespace hanging past the end of a line, everything but non-breaking spaces
+fn is_hanging(c: char) -> bool {
+    c.is_whitespace() && !matches!(c, '\u{A0}' | '\u{2007}' | '\u{202F}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::fixtures::MONO;
+
+    fn ranges(layout: &TextLayout) -> Vec<Range<usize>> {
+        layout.lines.iter().map(|line| line.range.clone()).collect()
+    }
+
+    mod wrapping {
+        use super::*;
+
+        #[test]
+        fn test_unbounded_text_is_one_line() {
+            let layout = Paragraph::new().layout(&MONO, "one two three", 10.0);
+
+            assert_eq!(layout.lines.len(), 1);
+            assert_eq!(layout.lines[0].range, 0..13);
+            assert_eq!(layout.size, Size::new(65.0, 10.0));
+            assert!(!layout.truncated);
+        }
+
+        #[test]
+        fn test_wraps_between_words() {
+            let paragraph = Paragraph::new().with_max_width(40.0);
+            let layout = paragraph.layout(&MONO, "one two three", 10.0);
+
+            assert_eq!(ranges(&layout), [0..8, 8..13]);
+            // The space after "two" hangs
+            assert_eq!(layout.lines[0].rect, Rect::new(0.0, 0.0, 35.0, 10.0));
+            assert_eq!(layout.lines[1].rect, Rect::new(0.0, 10.0, 25.0, 10.0));
+            assert_eq!(layout.size, Size::new(35.0, 20.0));
+        }
+
+        #[test]
+        fn test_newlines_always_break() {
+            let layout = Paragraph::new().layout(&MONO, "a\n\nb", 10.0);
+
+            assert_eq!(ranges(&layout), [0..2, 2..3, 3..4]);
+            assert_eq!(layout.size, Size::new(5.0, 30.0));
+            // Newlines have no glyphs
+            assert_eq!(layout.lines[1].glyphs, []);
+        }
+
+        #[test]
+        fn test_long_words_are_split() {
+            let paragraph = Paragraph::new().with_max_width(20.0);
+            let layout = paragraph.layout(&MONO, "abcdefghij", 10.0);
+
+            assert_eq!(ranges(&layout), [0..4, 4..8, 8..10]);
+        }
+
+        #[test]
+        fn test_zero_width_keeps_one_cluster_per_line() {
+            let paragraph = Paragraph::new().with_max_width(f32::NAN);
+            let layout = paragraph.layout(&MONO, "ab c", 10.0);
+
+            assert_eq!(ranges(&layout), [0..1, 1..3, 3..4]);
+        }
+
+        #[test]
+        fn test_empty_text_is_one_empty_line() {
+            let layout = Paragraph::new().layout(&MONO, "", 10.0);
+
+            assert_eq!(layout.lines.len(), 1);
+            assert_eq!(layout.lines[0].range, 0..0);
+            assert_eq!(layout.size, Size::new(0.0, 10.0));
+            assert_eq!(Paragraph::new().measure(&MONO, "", 10.0), layout.size);
+        }
+    }
+
+    mod placement {
+        use super::*;
+
+        #[test]
+        fn test_glyphs_sit_on_the_baseline() {
+            let layout = Paragraph::new().layout(&MONO, "ab", 10.0);
+            let line = &layout.lines[0];
+
+            assert_eq!(line.baseline, 8.0);
+            assert_eq!(line.glyphs[1].position, Point::new(5.0, 8.0));
+            assert_eq!(line.glyphs[1].cluster, 1);
+            assert_eq!(line.glyphs[1].run, 0);
+        }
+
+        #[test]
+        fn test_alignment_ignores_hanging_spaces() {
+            let paragraph = Paragraph::new().with_max_width(50.0);
+            let text = "abc   ";
+
+            let center = paragraph
+                .clone()
+                .with_align(TextAlign::Center)
+                .layout(&MONO, text, 10.0);
+            assert_eq!(center.lines[0].rect.pos.x, 17.5);
+            assert_eq!(center.lines[0].glyphs[0].position.x, 17.5);
+
+            let right = paragraph
+                .with_align(TextAlign::Right)
+                .layout(&MONO, text, 10.0);
+            assert_eq!(right.lines[0].rect.pos.x, 35.0);
+            assert_eq!(right.size.width, 15.0);
+        }
+
+        #[test]
+        fn test_unbounded_text_aligns_to_the_widest_line() {
+            let paragraph = Paragraph::new().with_align(TextAlign::Right);
+            let layout = paragraph.layout(&MONO, "abcd\nab", 10.0);
+
+            assert_eq!(layout.lines[0].rect.pos.x, 0.0);
+            assert_eq!(layout.lines[1].rect.pos.x, 10.0);
+        }
+
+        #[test]
+        fn test_line_height_centers_the_text() {
+            let relative = Paragraph::new().with_line_height(LineHeight::Relative(1.5));
+            let layout = relative.layout(&MONO, "a\nb", 10.0);
+
+            assert_eq!(layout.lines[1].rect, Rect::new(0.0, 15.0, 5.0, 15.0));
+            assert_eq!(layout.lines[1].baseline, 25.5);
+
+            let absolute = Paragraph::new().with_line_height(LineHeight::Absolute(12.0));
+            assert_eq!(
+                absolute.measure(&MONO, "a\nb\nc", 10.0),
+                Size::new(5.0, 36.0)
+            );
+        }
+    }
+
+    mod truncation {
+        use super::*;
+
+        #[test]
+        fn test_max_lines_end_with_an_ellipsis() {
+            let paragraph = Paragraph::new().with_max_width(40.0).with_max_lines(2);
+            let layout = paragraph.layout(&MONO, "one two three four", 10.0);
+
+            assert!(layout.truncated);
+            assert_eq!(ranges(&layout), [0..8, 8..13]);
+
+            let last = &layout.lines[1];
+            let ellipsis = last.glyphs.last().unwrap();
+            assert_eq!(last.glyphs.len(), 6);
+            assert_eq!(ellipsis.id, '\u{2026}' as u16);
+            assert_eq!(ellipsis.cluster, 13);
+            assert_eq!(ellipsis.position.x, 25.0);
+            assert_eq!(last.rect.size.width, 30.0);
+        }
+
+        #[test]
+        fn test_ellipsis_replaces_clusters_that_dont_fit() {
+            let paragraph = Paragraph::new().with_max_width(20.0).with_max_lines(1);
+            let layout = paragraph.layout(&MONO, "abcdefghij", 10.0);
+
+            assert_eq!(layout.lines.len(), 1);
+            assert_eq!(layout.lines[0].range, 0..3);
+            assert_eq!(layout.size, Size::new(20.0, 10.0));
+        }
+
+        #[test]
+        fn test_custom_ellipsis() {
+            let paragraph = Paragraph::new()
+                .with_max_width(30.0)
+                .with_max_lines(1)
+                .with_ellipsis("...");
+            let layout = paragraph.layout(&MONO, "ab cd ef", 10.0);
+
+            assert_eq!(layout.lines.len(), 1);
+            assert_eq!(layout.lines[0].range, 0..2);
+            assert_eq!(layout.lines[0].glyphs.len(), 5);
+
+            let no_ellipsis = paragraph.with_ellipsis("").layout(&MONO, "ab cd ef", 10.0);
+            assert_eq!(no_ellipsis.lines[0].rect.size.width, 25.0);
+        }
+
+        #[test]
+        fn test_fitting_text_is_not_truncated() {
+            let paragraph = Paragraph::new().with_max_width(40.0).with_max_lines(2);
+            let layout = paragraph.layout(&MONO, "one two three", 10.0);
+
+            assert!(!layout.truncated);
+            assert_eq!(layout.lines[1].glyphs.len(), 5);
+            assert_eq!(Paragraph::new().with_max_lines(0).max_lines, Some(1));
+        }
+    }
+}
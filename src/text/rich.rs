@@ -0,0 +1,587 @@
+//! Rich text: spans with their own font, size, color and decorations laid out as one paragraph
+//!
+//! Each [`Span`] is shaped separately with the font its [`SpanStyle`] selects from a
+//! [`FontSource`], then all spans wrap together, so a colored word breaks like any other word of
+//! the paragraph. Underlines, strikethroughs and highlights come out as rects to draw around the
+//! glyphs.
+
+use std::ops::Range;
+
+use super::library::{FontDescriptor, FontFamily, FontSource, FontStyle, FontWeight};
+use super::paragraph::{Paragraph, PositionedGlyph, TextLayout, TextRun};
+use super::{DecorationMetrics, FontError, FontMetrics, TextShaper};
+use crate::color::Color;
+use crate::layout::to_valid;
+use crate::math::Rect;
+
+/// Look of a span of rich text
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanStyle {
+    /// Font of the span
+    pub font: FontDescriptor,
+
+    /// Font size in pixels per em
+    pub size: f32,
+
+    /// Color of the glyphs and their underline and strikethrough
+    pub color: Color,
+
+    /// Whether a line is drawn below the baseline
+    pub underline: bool,
+
+    /// Whether a line is drawn through the glyphs
+    pub strikethrough: bool,
+
+    /// Highlight behind the span, `None` for none
+    pub background: Option<Color>,
+}
+
+impl SpanStyle {
+    /// Create SpanStyle of black, undecorated, normal sans-serif text of `size` pixels per em
+    ///
+    /// # Notes
+    ///
+    /// Negative or NaN sizes are 0.0
+    pub fn new(size: f32) -> Self {
+        Self {
+            font: FontDescriptor::default(),
+            size: to_valid(size),
+            color: Color::BLACK,
+            underline: false,
+            strikethrough: false,
+            background: None,
+        }
+    }
+
+    /// Return the SpanStyle with `font`
+    pub fn with_font(mut self, font: FontDescriptor) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Return the SpanStyle with font `family`
+    pub fn with_family(mut self, family: FontFamily) -> Self {
+        self.font.family = family;
+        self
+    }
+
+    /// Return the SpanStyle with font `weight`
+    pub fn with_weight(mut self, weight: FontWeight) -> Self {
+        self.font.weight = weight;
+        self
+    }
+
+    /// Return the SpanStyle with font `style`
+    pub fn with_font_style(mut self, style: FontStyle) -> Self {
+        self.font.style = style;
+        self
+    }
+
+    /// Return the SpanStyle with font `size`, negative or NaN sizes are 0.0
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = to_valid(size);
+        self
+    }
+
+    /// Return the SpanStyle with glyph `color`
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Return the SpanStyle with or without an underline
+    pub fn with_underline(mut self, underline: bool) -> Self {
+        self.underline = underline;
+        self
+    }
+
+    /// Return the SpanStyle with or without a strikethrough
+    pub fn with_strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = strikethrough;
+        self
+    }
+
+    /// Return the SpanStyle highlighted with `background`
+    pub fn with_background(mut self, background: Color) -> Self {
+        self.background = Some(background);
+        self
+    }
+}
+
+impl Default for SpanStyle {
+    /// Body text size of the default theme
+    fn default() -> Self {
+        Self::new(14.0)
+    }
+}
+
+/// Part of a [`RichText`] in one style
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    /// Byte range of the span in [`RichText::text`]
+    pub range: Range<usize>,
+
+    /// Look of the span
+    pub style: SpanStyle,
+}
+
+/// Text made of spans in different styles
+///
+/// # Notes
+///
+/// - Empty spans are dropped, so `PositionedGlyph::run` of a layout indexes [`RichText::spans`]
+/// - Spans are shaped separately, so there is no kerning or ligatures across span boundaries
+///
+/// # Examples
+/// ```no_run
+/// use ho_gui::color::Color;
+/// use ho_gui::text::library::{FontLibrary, FontWeight};
+/// use ho_gui::text::paragraph::Paragraph;
+/// use ho_gui::text::rich::{RichText, SpanStyle};
+///
+/// let mut fonts = FontLibrary::new();
+/// fonts.load_system_fonts();
+///
+/// let body = SpanStyle::new(13.0);
+/// let text = RichText::new()
+///     .with_span("ERROR", body.clone().with_color(Color::RED).with_weight(FontWeight::BOLD))
+///     .with_span(" connection refused", body);
+///
+/// let layout = text.layout(&Paragraph::new().with_max_width(200.0), &mut fonts).unwrap();
+/// for (glyph, color) in layout.glyphs() {
+///     // Draw glyph.id at glyph.position in color
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RichText {
+    text: String,
+    spans: Vec<Span>,
+}
+
+impl RichText {
+    /// Create RichText without spans
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the RichText with `text` appended in `style`
+    pub fn with_span(mut self, text: &str, style: SpanStyle) -> Self {
+        self.push(text, style);
+        self
+    }
+
+    /// Append `text` in `style`, empty text is ignored
+    pub fn push(&mut self, text: &str, style: SpanStyle) {
+        if text.is_empty() {
+            return;
+        }
+
+        let start = self.text.len();
+        self.text.push_str(text);
+        self.spans.push(Span {
+            range: start..self.text.len(),
+            style,
+        });
+    }
+
+    /// Text of all spans
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Spans in text order
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// Whether there is no text
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Remove all spans
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.spans.clear();
+    }
+
+    /// Lay out the spans as one `paragraph` with fonts from `fonts`
+    ///
+    /// # Errors
+    ///
+    /// `FontError::NoFont` if `fonts` has no font for a span
+    pub fn layout(
+        &self,
+        paragraph: &Paragraph,
+        fonts: &mut impl FontSource,
+    ) -> Result<RichLayout, FontError> {
+        let shapers = self
+            .spans
+            .iter()
+            .map(|span| fonts.font(&span.style.font).ok_or(FontError::NoFont))
+            .collect::<Result<Vec<_>, _>>()?;
+        let runs: Vec<TextRun<'_>> = self
+            .spans
+            .iter()
+            .zip(&shapers)
+            .map(|(span, shaper)| TextRun {
+                range: span.range.clone(),
+                shaper,
+                size: span.style.size,
+            })
+            .collect();
+
+        let layout = paragraph.layout_runs(&self.text, &runs);
+        let metrics: Vec<FontMetrics> = self
+            .spans
+            .iter()
+            .zip(&shapers)
+            .map(|(span, shaper)| shaper.metrics(span.style.size))
+            .collect();
+        let decorations = self.decorations(&layout, &metrics);
+
+        Ok(RichLayout {
+            layout,
+            decorations,
+            colors: self.spans.iter().map(|span| span.style.color).collect(),
+        })
+    }
+
+    /// Backgrounds of all lines, then underlines and strikethroughs
+    fn decorations(&self, layout: &TextLayout, metrics: &[FontMetrics]) -> Vec<Decoration> {
+        let mut backgrounds = Vec::new();
+        let mut lines = Vec::new();
+
+        for line in &layout.lines {
+            let mut glyphs = line.glyphs.as_slice();
+            while let Some(first) = glyphs.first() {
+                let count = glyphs
+                    .iter()
+                    .take_while(|glyph| glyph.run == first.run)
+                    .count();
+                let (pieces, rest) = glyphs.split_at(count);
+                glyphs = rest;
+
+                // Hanging spaces aren't decorated
+                let left = pieces
+                    .iter()
+                    .map(|glyph| glyph.position.x)
+                    .fold(f32::INFINITY, f32::min);
+                let right = pieces
+                    .iter()
+                    .map(|glyph| glyph.position.x + glyph.advance)
+                    .fold(f32::NEG_INFINITY, f32::max)
+                    .min(line.rect.right());
+                if right <= left {
+                    continue;
+                }
+
+                let span = first.run;
+                let style = &self.spans[span].style;
+                // Fonts without decoration metrics get lines centered at `center`, lines are at
+                // least a pixel thick
+                let bar = |font: Option<DecorationMetrics>, center: f32| {
+                    let (center, thickness) = match font {
+                        Some(font) => (font.top + font.thickness / 2.0, font.thickness),
+                        None => (center, style.size / 14.0),
+                    };
+                    let thickness = thickness.max(1.0);
+                    let top = line.baseline + center - thickness / 2.0;
+                    Rect::new(left, top, right - left, thickness)
+                };
+                let decoration = |kind, rect, color| Decoration {
+                    kind,
+                    rect,
+                    color,
+                    span,
+                };
+
+                if let Some(background) = style.background {
+                    let rect =
+                        Rect::new(left, line.rect.pos.y, right - left, line.rect.size.height);
+                    backgrounds.push(decoration(DecorationKind::Background, rect, background));
+                }
+                if style.underline {
+                    let rect = bar(metrics[span].underline, metrics[span].descent / 2.0);
+                    lines.push(decoration(DecorationKind::Underline, rect, style.color));
+                }
+                if style.strikethrough {
+                    let rect = bar(metrics[span].strikeout, -metrics[span].ascent * 0.3);
+                    lines.push(decoration(DecorationKind::Strikethrough, rect, style.color));
+                }
+            }
+        }
+
+        backgrounds.append(&mut lines);
+        backgrounds
+    }
+}
+
+/// Laid out [`RichText`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RichLayout {
+    /// Lines and glyphs, `PositionedGlyph::run` is the index of the glyph's span
+    pub layout: TextLayout,
+
+    /// Highlights first, to be drawn under the glyphs, then underlines and strikethroughs
+    pub decorations: Vec<Decoration>,
+
+    colors: Vec<Color>,
+}
+
+impl RichLayout {
+    /// Glyphs of all lines with the colors of their spans
+    pub fn glyphs(&self) -> impl Iterator<Item = (&PositionedGlyph, Color)> + '_ {
+        self.layout
+            .lines
+            .iter()
+            .flat_map(|line| &line.glyphs)
+            .map(|glyph| (glyph, self.colors[glyph.run]))
+    }
+}
+
+/// Kind of a [`Decoration`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecorationKind {
+    /// Highlight behind the glyphs, as tall as the line
+    Background,
+
+    /// Line below the baseline
+    Underline,
+
+    /// Line through the middle of lowercase letters
+    Strikethrough,
+}
+
+/// Rect drawn with a span on one line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decoration {
+    /// What the rect is
+    pub kind: DecorationKind,
+
+    /// Area to fill, in the layout's coordinates
+    pub rect: Rect,
+
+    /// Fill color
+    pub color: Color,
+
+    /// Index of the span in [`RichText::spans`]
+    pub span: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Point;
+    use crate::text::fixtures::Mono;
+
+    /// Mono shapers half an em wide, bold ones 0.6 em
+    struct Fonts {
+        loaded: bool,
+    }
+
+    impl FontSource for Fonts {
+        type Shaper = Mono;
+
+        fn font(&mut self, font: &FontDescriptor) -> Option<Mono> {
+            let width = if font.weight >= FontWeight::BOLD {
+                0.6
+            } else {
+                0.5
+            };
+            let decorations = font.family == FontFamily::Named("Decorated".into());
+            self.loaded.then_some(Mono { width, decorations })
+        }
+    }
+
+    fn layout(text: &RichText, paragraph: &Paragraph) -> RichLayout {
+        text.layout(paragraph, &mut Fonts { loaded: true }).unwrap()
+    }
+
+    mod spans {
+        use super::*;
+
+        #[test]
+        fn test_spans_cover_the_text_in_order() {
+            let style = SpanStyle::new(10.0);
+            let mut text = RichText::new()
+                .with_span("ab", style.clone())
+                .with_span("", style.clone().with_color(Color::RED))
+                .with_span("c", style.clone().with_color(Color::BLUE));
+
+            assert_eq!(text.text(), "abc");
+            assert_eq!(text.spans().len(), 2);
+            assert_eq!(text.spans()[1].range, 2..3);
+            assert_eq!(text.spans()[1].style.color, Color::BLUE);
+
+            text.clear();
+            assert!(text.is_empty());
+            assert_eq!(text.spans(), []);
+        }
+
+        #[test]
+        fn test_style_builders() {
+            let style = SpanStyle::new(f32::NAN)
+                .with_family(FontFamily::Monospace)
+                .with_weight(FontWeight::BOLD)
+                .with_font_style(FontStyle::Italic)
+                .with_underline(true)
+                .with_strikethrough(true)
+                .with_background(Color::YELLOW);
+
+            assert_eq!(style.size, 0.0);
+            assert_eq!(style.clone().with_size(-1.0).size, 0.0);
+            assert_eq!(
+                SpanStyle::default().with_font(style.font.clone()).font,
+                FontDescriptor::new(FontFamily::Monospace)
+                    .with_weight(FontWeight::BOLD)
+                    .with_style(FontStyle::Italic)
+            );
+            assert_eq!(SpanStyle::default().size, 14.0);
+        }
+
+        #[test]
+        fn test_missing_fonts_are_an_error() {
+            let text = RichText::new().with_span("a", SpanStyle::default());
+            let result = text.layout(&Paragraph::new(), &mut Fonts { loaded: false });
+
+            assert_eq!(result, Err(FontError::NoFont));
+        }
+    }
+
+    mod layout {
+        use super::*;
+
+        #[test]
+        fn test_spans_share_lines() {
+            let text = RichText::new()
+                .with_span("red ", SpanStyle::new(10.0).with_color(Color::RED))
+                .with_span("big", SpanStyle::new(20.0).with_weight(FontWeight::BOLD));
+            let layout = layout(&text, &Paragraph::new());
+
+            let line = &layout.layout.lines[0];
+            assert_eq!(layout.layout.lines.len(), 1);
+            // The tallest span sets the line height
+            assert_eq!(line.rect, Rect::new(0.0, 0.0, 56.0, 20.0));
+            assert_eq!(line.baseline, 16.0);
+            assert_eq!(line.glyphs[4].position, Point::new(20.0, 16.0));
+            assert_eq!(line.glyphs[5].position.x, 32.0);
+
+            let colors: Vec<Color> = layout.glyphs().map(|(_, color)| color).collect();
+            assert_eq!(colors[..4], [Color::RED; 4]);
+            assert_eq!(colors[4..], [Color::BLACK; 3]);
+        }
+
+        #[test]
+        fn test_spans_wrap_together() {
+            let style = SpanStyle::new(10.0);
+            let text = RichText::new()
+                .with_span("one t", style.clone())
+                .with_span("wo three", style.with_color(Color::GREEN));
+            let layout = layout(&text, &Paragraph::new().with_max_width(40.0));
+
+            let ranges: Vec<_> = layout
+                .layout
+                .lines
+                .iter()
+                .map(|line| line.range.clone())
+                .collect();
+            assert_eq!(ranges, [0..8, 8..13]);
+            // A word split between spans stays whole
+            let runs: Vec<usize> = layout.layout.lines[0]
+                .glyphs
+                .iter()
+                .map(|glyph| glyph.run)
+                .collect();
+            assert_eq!(runs, [0, 0, 0, 0, 0, 1, 1, 1]);
+        }
+    }
+
+    mod decorations {
+        use super::*;
+
+        #[test]
+        fn test_decorations_follow_their_spans() {
+            let text = RichText::new()
+                .with_span(
+                    "ab ",
+                    SpanStyle::new(10.0)
+                        .with_underline(true)
+                        .with_background(Color::YELLOW),
+                )
+                .with_span(
+                    "cd",
+                    SpanStyle::new(10.0)
+                        .with_color(Color::RED)
+                        .with_strikethrough(true),
+                );
+            let layout = layout(&text, &Paragraph::new());
+
+            assert_eq!(
+                layout.decorations,
+                [
+                    Decoration {
+                        kind: DecorationKind::Background,
+                        rect: Rect::new(0.0, 0.0, 15.0, 10.0),
+                        color: Color::YELLOW,
+                        span: 0,
+                    },
+                    Decoration {
+                        kind: DecorationKind::Underline,
+                        rect: Rect::new(0.0, 8.5, 15.0, 1.0),
+                        color: Color::BLACK,
+                        span: 0,
+                    },
+                    Decoration {
+                        kind: DecorationKind::Strikethrough,
+                        rect: Rect::new(15.0, 5.1, 10.0, 1.0),
+                        color: Color::RED,
+                        span: 1,
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn test_decorations_are_split_by_lines() {
+            let style = SpanStyle::new(10.0).with_underline(true);
+            let text = RichText::new().with_span("one two", style);
+            let layout = layout(&text, &Paragraph::new().with_max_width(20.0));
+
+            let rects: Vec<Rect> = layout
+                .decorations
+                .iter()
+                .map(|decoration| decoration.rect)
+                .collect();
+            // The hanging space after "one" isn't underlined
+            assert_eq!(
+                rects,
+                [
+                    Rect::new(0.0, 8.5, 15.0, 1.0),
+                    Rect::new(0.0, 18.5, 15.0, 1.0)
+                ]
+            );
+        }
+
+        #[test]
+        fn test_decorations_use_font_metrics() {
+            let style = SpanStyle::new(20.0)
+                .with_family(FontFamily::Named("Decorated".into()))
+                .with_underline(true)
+                .with_strikethrough(true);
+            let layout = layout(&RichText::new().with_span("a", style), &Paragraph::new());
+
+            // Baseline at 16, underline 2 below it and strikeout 8 above it, 2 thick
+            assert_eq!(layout.decorations[0].rect, Rect::new(0.0, 18.0, 10.0, 2.0));
+            assert_eq!(layout.decorations[1].rect, Rect::new(0.0, 8.0, 10.0, 2.0));
+        }
+
+        #[test]
+        fn test_thickness_scales_with_size() {
+            let style = SpanStyle::new(28.0).with_underline(true);
+            let layout = layout(&RichText::new().with_span("a", style), &Paragraph::new());
+
+            assert_eq!(layout.decorations[0].rect.size.height, 2.0);
+        }
+    }
+}
@@ -0,0 +1,514 @@
+//! Themes: semantic colors and default styles shared by every widget
+//!
+//! Themes are built in presets or loaded from [theme files](file), which a running [`App`]
+//! can reload on change.
+//!
+//! [`App`]: crate::app::App
+
+pub mod file;
+
+use crate::color::Color;
+use crate::style::{Border, CornerRadius, Padding};
+
+/// Semantic colors of a theme
+///
+/// Widgets pick colors by role instead of by value, so switching the theme restyles every widget.
+/// `on_*` colors are used for text and icons drawn on top of the matching fill color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThemeColors {
+    /// Window background
+    pub background: Color,
+
+    /// Background of raised components like panels, cards and inputs
+    pub surface: Color,
+
+    /// Primary text
+    pub text: Color,
+
+    /// Secondary text like hints and captions
+    pub text_muted: Color,
+
+    /// Accent for primary actions, selection and links
+    pub accent: Color,
+
+    /// Text and icons on top of `accent`
+    pub on_accent: Color,
+
+    /// Component borders and separators
+    pub border: Color,
+
+    /// Focus indicator
+    pub focus: Color,
+
+    /// Errors and destructive actions
+    pub error: Color,
+
+    /// Text and icons on top of `error`
+    pub on_error: Color,
+
+    /// Warnings
+    pub warning: Color,
+
+    /// Success messages
+    pub success: Color,
+}
+
+impl ThemeColors {
+    /// Color of `role`
+    pub fn get(&self, role: ColorRole) -> Color {
+        match role {
+            ColorRole::Background => self.background,
+            ColorRole::Surface => self.surface,
+            ColorRole::Text => self.text,
+            ColorRole::TextMuted => self.text_muted,
+            ColorRole::Accent => self.accent,
+            ColorRole::OnAccent => self.on_accent,
+            ColorRole::Border => self.border,
+            ColorRole::Focus => self.focus,
+            ColorRole::Error => self.error,
+            ColorRole::OnError => self.on_error,
+            ColorRole::Warning => self.warning,
+            ColorRole::Success => self.success,
+        }
+    }
+
+    /// Replace the color of `role`
+    pub fn set(&mut self, role: ColorRole, color: Color) {
+        let slot = match role {
+            ColorRole::Background => &mut self.background,
+            ColorRole::Surface => &mut self.surface,
+            ColorRole::Text => &mut self.text,
+            ColorRole::TextMuted => &mut self.text_muted,
+            ColorRole::Accent => &mut self.accent,
+            ColorRole::OnAccent => &mut self.on_accent,
+            ColorRole::Border => &mut self.border,
+            ColorRole::Focus => &mut self.focus,
+            ColorRole::Error => &mut self.error,
+            ColorRole::OnError => &mut self.on_error,
+            ColorRole::Warning => &mut self.warning,
+            ColorRole::Success => &mut self.success,
+        };
+
+        *slot = color;
+    }
+}
+
+/// Semantic role of a [`ThemeColors`] color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorRole {
+    /// `ThemeColors::background`
+    Background,
+    /// `ThemeColors::surface`
+    Surface,
+    /// `ThemeColors::text`
+    Text,
+    /// `ThemeColors::text_muted`
+    TextMuted,
+    /// `ThemeColors::accent`
+    Accent,
+    /// `ThemeColors::on_accent`
+    OnAccent,
+    /// `ThemeColors::border`
+    Border,
+    /// `ThemeColors::focus`
+    Focus,
+    /// `ThemeColors::error`
+    Error,
+    /// `ThemeColors::on_error`
+    OnError,
+    /// `ThemeColors::warning`
+    Warning,
+    /// `ThemeColors::success`
+    Success,
+}
+
+/// Font sizes of a theme, in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontSizes {
+    /// Captions and hints
+    pub small: f32,
+
+    /// Body text and widget labels
+    pub body: f32,
+
+    /// Section headings
+    pub heading: f32,
+
+    /// Window and page titles
+    pub title: f32,
+}
+
+/// Spacing scale of a theme, in pixels, used between widgets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spacing {
+    /// Extra small spacing
+    pub xs: f32,
+
+    /// Small spacing
+    pub sm: f32,
+
+    /// Medium spacing, the default between widgets
+    pub md: f32,
+
+    /// Large spacing
+    pub lg: f32,
+
+    /// Extra large spacing
+    pub xl: f32,
+}
+
+/// Look of an application: semantic colors, default box styles, font sizes and spacing
+///
+/// # Notes
+///
+/// - `light`, `dark` and `high_contrast` presets are built in. `Theme::default()` is `light`.
+/// - Switch themes at runtime with [`App::set_theme`](crate::app::App::set_theme)
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::ContrastLevel;
+/// use ho_gui::theme::Theme;
+///
+/// let theme = Theme::dark();
+///
+/// assert!(theme.is_dark());
+/// assert!(theme.colors.text.meets_contrast(theme.colors.background, ContrastLevel::Aa));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// Human readable theme name
+    pub name: String,
+
+    /// Semantic colors
+    pub colors: ThemeColors,
+
+    /// Default padding inside of widgets
+    pub padding: Padding,
+
+    /// Default widget border
+    pub border: Border,
+
+    /// Default widget corner radii
+    pub corner_radius: CornerRadius,
+
+    /// Font sizes
+    pub font_sizes: FontSizes,
+
+    /// Spacing scale
+    pub spacing: Spacing,
+}
+
+impl Theme {
+    /// Light theme with dark text on white surfaces
+    pub fn light() -> Self {
+        let colors = ThemeColors {
+            background: Color::from_hex(0xF6_F8_FA),
+            surface: Color::from_hex(0xFF_FF_FF),
+            text: Color::from_hex(0x1F_23_28),
+            text_muted: Color::from_hex(0x59_63_6E),
+            accent: Color::from_hex(0x09_69_DA),
+            on_accent: Color::from_hex(0xFF_FF_FF),
+            border: Color::from_hex(0x81_8B_98),
+            focus: Color::from_hex(0x09_69_DA),
+            error: Color::from_hex(0xCF_22_2E),
+            on_error: Color::from_hex(0xFF_FF_FF),
+            warning: Color::from_hex(0x9A_67_00),
+            success: Color::from_hex(0x1A_7F_37),
+        };
+
+        Self::with_defaults("Light", colors)
+    }
+
+    /// Dark theme with light text on dark surfaces
+    pub fn dark() -> Self {
+        let colors = ThemeColors {
+            background: Color::from_hex(0x0D_11_17),
+            surface: Color::from_hex(0x16_1B_22),
+            text: Color::from_hex(0xE6_ED_F3),
+            text_muted: Color::from_hex(0x91_98_A1),
+            accent: Color::from_hex(0x44_93_F8),
+            on_accent: Color::from_hex(0x0D_11_17),
+            border: Color::from_hex(0x65_6C_76),
+            focus: Color::from_hex(0x44_93_F8),
+            error: Color::from_hex(0xF8_51_49),
+            on_error: Color::from_hex(0x0D_11_17),
+            warning: Color::from_hex(0xD2_99_22),
+            success: Color::from_hex(0x3F_B9_50),
+        };
+
+        Self::with_defaults("Dark", colors)
+    }
+
+    /// High contrast theme for low vision users
+    ///
+    /// # Notes
+    ///
+    /// - Every text color meets WCAG AAA (7:1) against the background
+    /// - Borders are thicker, so component edges stay visible
+    pub fn high_contrast() -> Self {
+        let colors = ThemeColors {
+            background: Color::BLACK,
+            surface: Color::BLACK,
+            text: Color::WHITE,
+            text_muted: Color::from_hex(0xE0_E0_E0),
+            accent: Color::from_hex(0xFF_D6_00),
+            on_accent: Color::BLACK,
+            border: Color::WHITE,
+            focus: Color::from_hex(0xFF_D6_00),
+            error: Color::from_hex(0xFF_7B_72),
+            on_error: Color::BLACK,
+            warning: Color::from_hex(0xFF_D6_00),
+            success: Color::from_hex(0x7E_E7_87),
+        };
+
+        let mut theme = Self::with_defaults("High Contrast", colors);
+        theme.border = Border::new(2.0, colors.border);
+        theme
+    }
+
+    /// Check if the theme has a dark background, e.g. to pick icons
+    pub fn is_dark(&self) -> bool {
+        self.colors.background.relative_luminance() < 0.5
+    }
+
+    /// Theme with shared default metrics and the given colors
+    fn with_defaults(name: &str, colors: ThemeColors) -> Self {
+        Self {
+            name: name.to_string(),
+            colors,
+            padding: Padding::symmetric(12.0, 6.0),
+            border: Border::new(1.0, colors.border),
+            corner_radius: CornerRadius::all(6.0),
+            font_sizes: FontSizes {
+                small: 12.0,
+                body: 14.0,
+                heading: 20.0,
+                title: 28.0,
+            },
+            spacing: Spacing {
+                xs: 2.0,
+                sm: 4.0,
+                md: 8.0,
+                lg: 16.0,
+                xl: 24.0,
+            },
+        }
+    }
+}
+
+/// Single theme value override, see [`Ui::push_style`](crate::ui::Ui::push_style)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StyleOverride {
+    /// Override one semantic color
+    Color(ColorRole, Color),
+
+    /// Override `Theme::padding`
+    Padding(Padding),
+
+    /// Override `Theme::border`
+    Border(Border),
+
+    /// Override `Theme::corner_radius`
+    CornerRadius(CornerRadius),
+
+    /// Override `Theme::font_sizes`
+    FontSizes(FontSizes),
+
+    /// Override `Theme::spacing`
+    Spacing(Spacing),
+}
+
+impl Theme {
+    /// Apply `value` to the theme
+    ///
+    /// # Returns
+    ///
+    /// Override holding the previous value, which undoes the change when applied
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::style::Padding;
+    /// use ho_gui::theme::{StyleOverride, Theme};
+    ///
+    /// let mut theme = Theme::light();
+    /// let undo = theme.apply(StyleOverride::Padding(Padding::zero()));
+    /// assert_eq!(theme.padding, Padding::zero());
+    ///
+    /// theme.apply(undo);
+    /// assert_eq!(theme, Theme::light());
+    /// ```
+    pub fn apply(&mut self, value: StyleOverride) -> StyleOverride {
+        match value {
+            StyleOverride::Color(role, color) => {
+                let previous = self.colors.get(role);
+                self.colors.set(role, color);
+                StyleOverride::Color(role, previous)
+            }
+            StyleOverride::Padding(padding) => {
+                StyleOverride::Padding(std::mem::replace(&mut self.padding, padding))
+            }
+            StyleOverride::Border(border) => {
+                StyleOverride::Border(std::mem::replace(&mut self.border, border))
+            }
+            StyleOverride::CornerRadius(radius) => {
+                StyleOverride::CornerRadius(std::mem::replace(&mut self.corner_radius, radius))
+            }
+            StyleOverride::FontSizes(sizes) => {
+                StyleOverride::FontSizes(std::mem::replace(&mut self.font_sizes, sizes))
+            }
+            StyleOverride::Spacing(spacing) => {
+                StyleOverride::Spacing(std::mem::replace(&mut self.spacing, spacing))
+            }
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use crate::color::ContrastLevel;
+
+    fn presets() -> [Theme; 3] {
+        [Theme::light(), Theme::dark(), Theme::high_contrast()]
+    }
+
+    fn assert_readable(theme: &Theme, role: &str, text: Color, bg: Color, level: ContrastLevel) {
+        assert!(
+            text.meets_contrast(bg, level),
+            "{} theme: {} is not readable at {:?} (contrast {:.2})",
+            theme.name,
+            role,
+            level,
+            crate::color::contrast_ratio(text, bg)
+        );
+    }
+
+    #[test]
+    fn test_text_is_readable() {
+        for theme in presets() {
+            let c = theme.colors;
+
+            for bg in [c.background, c.surface] {
+                assert_readable(&theme, "text", c.text, bg, ContrastLevel::Aa);
+                assert_readable(&theme, "text_muted", c.text_muted, bg, ContrastLevel::Aa);
+                assert_readable(&theme, "accent", c.accent, bg, ContrastLevel::Aa);
+                assert_readable(&theme, "error", c.error, bg, ContrastLevel::Aa);
+            }
+
+            assert_readable(
+                &theme,
+                "on_accent",
+                c.on_accent,
+                c.accent,
+                ContrastLevel::Aa,
+            );
+            assert_readable(&theme, "on_error", c.on_error, c.error, ContrastLevel::Aa);
+            assert_readable(&theme, "warning", c.warning, c.surface, ContrastLevel::Aa);
+            assert_readable(&theme, "success", c.success, c.surface, ContrastLevel::Aa);
+        }
+    }
+
+    #[test]
+    fn test_components_are_visible() {
+        // WCAG 1.4.11 asks for 3:1 for component boundaries and focus indicators
+        for theme in presets() {
+            let c = theme.colors;
+
+            for bg in [c.background, c.surface] {
+                assert_readable(&theme, "border", c.border, bg, ContrastLevel::AaLarge);
+                assert_readable(&theme, "focus", c.focus, bg, ContrastLevel::AaLarge);
+            }
+        }
+    }
+
+    #[test]
+    fn test_high_contrast_is_aaa() {
+        let theme = Theme::high_contrast();
+        let c = theme.colors;
+
+        for text in [
+            c.text,
+            c.text_muted,
+            c.accent,
+            c.error,
+            c.warning,
+            c.success,
+        ] {
+            assert_readable(&theme, "text", text, c.background, ContrastLevel::Aaa);
+        }
+        assert_readable(
+            &theme,
+            "on_accent",
+            c.on_accent,
+            c.accent,
+            ContrastLevel::Aaa,
+        );
+        assert_readable(&theme, "on_error", c.on_error, c.error, ContrastLevel::Aaa);
+        assert!(theme.border.width > Theme::light().border.width);
+    }
+
+    #[test]
+    fn test_is_dark() {
+        assert!(!Theme::light().is_dark());
+        assert!(Theme::dark().is_dark());
+        assert!(Theme::high_contrast().is_dark());
+        assert_eq!(Theme::default(), Theme::light());
+    }
+
+    #[test]
+    fn test_color_roles() {
+        let mut colors = Theme::light().colors;
+        colors.set(ColorRole::Accent, Color::RED);
+        colors.set(ColorRole::OnError, Color::BLUE);
+
+        assert_eq!(colors.get(ColorRole::Accent), Color::RED);
+        assert_eq!(colors.accent, Color::RED);
+        assert_eq!(colors.on_error, Color::BLUE);
+        assert_eq!(colors.get(ColorRole::Text), Theme::light().colors.text);
+    }
+
+    #[test]
+    fn test_apply_returns_previous_value() {
+        let mut theme = Theme::dark();
+        let overrides = [
+            StyleOverride::Color(ColorRole::Accent, Color::RED),
+            StyleOverride::Border(Border::none()),
+            StyleOverride::CornerRadius(CornerRadius::zero()),
+            StyleOverride::Spacing(Spacing {
+                xs: 0.0,
+                sm: 0.0,
+                md: 0.0,
+                lg: 0.0,
+                xl: 0.0,
+            }),
+        ];
+
+        let undo: Vec<_> = overrides.iter().map(|&o| theme.apply(o)).collect();
+        assert_eq!(theme.colors.accent, Color::RED);
+        assert_eq!(theme.border, Border::none());
+
+        for previous in undo.into_iter().rev() {
+            theme.apply(previous);
+        }
+        assert_eq!(theme, Theme::dark());
+    }
+
+    #[test]
+    fn test_switch_theme_at_runtime() {
+        let mut app = App::new();
+        assert_eq!(app.theme(), &Theme::light());
+
+        app.set_theme(Theme::dark());
+        assert_eq!(app.theme().name, "Dark");
+
+        let app = App::new().with_theme(Theme::high_contrast());
+        assert_eq!(app.theme().name, "High Contrast");
+    }
+}
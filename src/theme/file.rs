@@ -0,0 +1,645 @@
+//! Theme files: load a [`Theme`] from a human-editable text file and reload it when it changes
+//!
+//! # Format
+//!
+//! Line based `key = value` pairs, grouped in `[section]`s like TOML:
+//!
+//! ```text
+//! # Ocean theme
+//! base = "dark"                 # light, dark or high-contrast
+//! name = "Ocean"
+//!
+//! padding = 12 6                # CSS shorthand with 1 to 4 values
+//! border = 1 dashed "#2AA1B3"   # width, style and color in any order
+//! corner_radius = 6 6 0 0       # CSS shorthand, clockwise from the top left
+//!
+//! [colors]
+//! accent = "#2AA1B3"
+//! focus = "#2AA1B3"
+//!
+//! [font_sizes]
+//! body = 15
+//!
+//! [spacing]
+//! md = 10px
+//! ```
+//!
+//! - Colors are quoted `"#RRGGBB"` or `"#RRGGBBAA"` strings, as parsed by
+//!   [`Color::from_hex_str`]. Everything after an unquoted `#` is a comment.
+//! - Keys are the field names of [`Theme`], [`ThemeColors`](super::ThemeColors),
+//!   [`FontSizes`](super::FontSizes) and [`Spacing`](super::Spacing)
+//! - Values missing from the file are taken from the `base` preset, `light` by default.
+//!   `base` replaces every value set before it, so put it first.
+//! - Lengths are non-negative pixels, with an optional `px` suffix
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use super::{ColorRole, Theme};
+use crate::color::{Color, ColorParseError};
+use crate::style::parse::{border_style, parse_box, parse_length};
+use crate::style::{CornerRadius, Padding};
+
+impl Theme {
+    /// Load a theme from the file at `path`, see the [module docs](self) for the format
+    ///
+    /// # Errors
+    ///
+    /// * Returns `ThemeFileError::Io` if the file can't be read
+    /// * Returns any parse error of [`Theme::from_str`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ThemeFileError> {
+        let source = fs::read_to_string(path).map_err(|e| ThemeFileError::Io(e.kind()))?;
+        source.parse()
+    }
+}
+
+impl FromStr for Theme {
+    type Err = ThemeFileError;
+
+    /// Parse a theme file, see the [module docs](self) for the format
+    ///
+    /// # Errors
+    ///
+    /// Every parse error holds the 1-based number of the offending line.
+    ///
+    /// * Returns `ThemeFileError::InvalidLine` if a line is neither `[section]` nor `key = value`
+    /// * Returns `ThemeFileError::UnknownSection` or `ThemeFileError::UnknownKey` for names that
+    ///   are not theme fields
+    /// * Returns `ThemeFileError::InvalidValue` if a value has the wrong shape, e.g. a negative
+    ///   length or an unknown base theme
+    /// * Returns `ThemeFileError::InvalidColor` if a color string doesn't parse
+    ///
+    /// # Examples
+    /// ```
+    /// use ho_gui::color::Color;
+    /// use ho_gui::theme::Theme;
+    ///
+    /// let theme: Theme = "
+    ///     base = \"dark\"
+    ///     padding = 8
+    ///
+    ///     [colors]
+    ///     accent = \"#FF0000\"  # red
+    /// "
+    /// .parse()
+    /// .unwrap();
+    ///
+    /// assert!(theme.is_dark());
+    /// assert_eq!(theme.padding.left, 8.0);
+    /// assert_eq!(theme.colors.accent, Color::RED);
+    /// ```
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut theme = Theme::default();
+        let mut section = Section::Root;
+
+        for (index, raw) in source.lines().enumerate() {
+            let line = index + 1;
+            let content = strip_comment(raw).trim();
+
+            if content.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = content.strip_prefix('[') {
+                let name = name
+                    .strip_suffix(']')
+                    .ok_or(ThemeFileError::InvalidLine(line))?;
+                section =
+                    Section::from_name(name.trim()).ok_or(ThemeFileError::UnknownSection(line))?;
+                continue;
+            }
+
+            let (key, value) = content
+                .split_once('=')
+                .ok_or(ThemeFileError::InvalidLine(line))?;
+            let values = tokenize(value).ok_or(ThemeFileError::InvalidLine(line))?;
+
+            apply_entry(&mut theme, section, key.trim(), &values, line)?;
+        }
+
+        Ok(theme)
+    }
+}
+
+/// Polls a theme file for changes, so edits show up in a running application
+///
+/// # Notes
+///
+/// - Changes are detected by modification time and size, without platform file watching.
+///   Polling once per frame costs one `stat` call.
+/// - Each change, including the file disappearing, is reported once
+///
+/// # Examples
+/// ```no_run
+/// use ho_gui::theme::file::ThemeWatcher;
+///
+/// let mut watcher = ThemeWatcher::new("theme.txt");
+///
+/// // Once per frame
+/// if let Some(result) = watcher.poll() {
+///     match result {
+///         Ok(theme) => println!("Reloaded {}", theme.name),
+///         Err(error) => eprintln!("Keeping the old theme: {}", error),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ThemeWatcher {
+    path: PathBuf,
+    // Modification time and size seen by the last poll
+    stamp: Option<Result<(SystemTime, u64), io::ErrorKind>>,
+}
+
+impl ThemeWatcher {
+    /// Watch the theme file at `path`. The first [`ThemeWatcher::poll`] loads it.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            stamp: None,
+        }
+    }
+
+    /// Path of the watched file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Check the file for changes
+    ///
+    /// # Returns
+    ///
+    /// * `None` if the file didn't change since the last poll
+    /// * `Some(Ok(theme))` with the reloaded theme
+    /// * `Some(Err(error))` if the changed file can't be read or parsed
+    pub fn poll(&mut self) -> Option<Result<Theme, ThemeFileError>> {
+        let stamp = fs::metadata(&self.path)
+            .and_then(|meta| Ok((meta.modified()?, meta.len())))
+            .map_err(|e| e.kind());
+
+        if self.stamp == Some(stamp) {
+            return None;
+        }
+        self.stamp = Some(stamp);
+
+        Some(match stamp {
+            Ok(_) => Theme::load(&self.path),
+            Err(kind) => Err(ThemeFileError::Io(kind)),
+        })
+    }
+}
+
+/// Error type for loading theme files
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThemeFileError {
+    /// The file can't be read
+    Io(io::ErrorKind),
+
+    /// The line is neither a `[section]` header nor a `key = value` pair
+    InvalidLine(usize),
+
+    /// The line opens a section that is not a theme field
+    UnknownSection(usize),
+
+    /// The line sets a key that doesn't exist in its section
+    UnknownKey(usize),
+
+    /// The line has a value of the wrong shape for its key
+    InvalidValue(usize),
+
+    /// The line has a color string that doesn't parse
+    InvalidColor(usize, ColorParseError),
+}
+
+impl std::fmt::Display for ThemeFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeFileError::Io(kind) => write!(f, "Can't read theme file: {}", kind),
+            ThemeFileError::InvalidLine(line) => {
+                write!(f, "Line {}: expected `[section]` or `key = value`", line)
+            }
+            ThemeFileError::UnknownSection(line) => write!(f, "Line {}: unknown section", line),
+            ThemeFileError::UnknownKey(line) => write!(f, "Line {}: unknown key", line),
+            ThemeFileError::InvalidValue(line) => write!(f, "Line {}: invalid value", line),
+            ThemeFileError::InvalidColor(line, error) => write!(f, "Line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for ThemeFileError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Root,
+    Colors,
+    FontSizes,
+    Spacing,
+}
+
+impl Section {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "colors" => Some(Section::Colors),
+            "font_sizes" => Some(Section::FontSizes),
+            "spacing" => Some(Section::Spacing),
+            _ => None,
+        }
+    }
+}
+
+fn apply_entry(
+    theme: &mut Theme,
+    section: Section,
+    key: &str,
+    values: &[&str],
+    line: usize,
+) -> Result<(), ThemeFileError> {
+    let invalid = ThemeFileError::InvalidValue(line);
+
+    match section {
+        Section::Root => match key {
+            "name" => theme.name = single(values).ok_or(invalid)?.to_string(),
+            "base" => {
+                *theme = match single(values).ok_or(invalid.clone())? {
+                    "light" => Theme::light(),
+                    "dark" => Theme::dark(),
+                    "high-contrast" => Theme::high_contrast(),
+                    _ => return Err(invalid),
+                };
+            }
+            "padding" => {
+                let [top, right, bottom, left] = parse_box(values).ok_or(invalid)?;
+                theme.padding = Padding::new(left, right, top, bottom);
+            }
+            "border" => parse_border(theme, values, line)?,
+            "corner_radius" => {
+                let [top_left, top_right, bottom_right, bottom_left] =
+                    parse_box(values).ok_or(invalid)?;
+                theme.corner_radius =
+                    CornerRadius::new(top_left, top_right, bottom_right, bottom_left);
+            }
+            _ => return Err(ThemeFileError::UnknownKey(line)),
+        },
+        Section::Colors => {
+            let role = color_role(key).ok_or(ThemeFileError::UnknownKey(line))?;
+            let color = parse_color(single(values).ok_or(invalid)?, line)?;
+            theme.colors.set(role, color);
+        }
+        Section::FontSizes => {
+            let sizes = &mut theme.font_sizes;
+            let slot = match key {
+                "small" => &mut sizes.small,
+                "body" => &mut sizes.body,
+                "heading" => &mut sizes.heading,
+                "title" => &mut sizes.title,
+                _ => return Err(ThemeFileError::UnknownKey(line)),
+            };
+            *slot = single(values).and_then(parse_length).ok_or(invalid)?;
+        }
+        Section::Spacing => {
+            let spacing = &mut theme.spacing;
+            let slot = match key {
+                "xs" => &mut spacing.xs,
+                "sm" => &mut spacing.sm,
+                "md" => &mut spacing.md,
+                "lg" => &mut spacing.lg,
+                "xl" => &mut spacing.xl,
+                _ => return Err(ThemeFileError::UnknownKey(line)),
+            };
+            *slot = single(values).and_then(parse_length).ok_or(invalid)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Set the parts of the theme border given in `values`, in any order like CSS
+fn parse_border(theme: &mut Theme, values: &[&str], line: usize) -> Result<(), ThemeFileError> {
+    let invalid = ThemeFileError::InvalidValue(line);
+    let (mut width, mut style, mut color) = (None, None, None);
+
+    if values.is_empty() {
+        return Err(invalid);
+    }
+
+    for &value in values {
+        let taken = if value.starts_with('#') {
+            color.replace(parse_color(value, line)?).is_some()
+        } else if let Some(parsed) = border_style(value) {
+            style.replace(parsed).is_some()
+        } else {
+            let parsed = parse_length(value).ok_or(invalid.clone())?;
+            width.replace(parsed).is_some()
+        };
+
+        if taken {
+            return Err(invalid);
+        }
+    }
+
+    let border = &mut theme.border;
+    border.width = width.unwrap_or(border.width);
+    border.style = style.unwrap_or(border.style);
+    border.color = color.unwrap_or(border.color);
+
+    Ok(())
+}
+
+fn parse_color(value: &str, line: usize) -> Result<Color, ThemeFileError> {
+    Color::from_hex_str(value).map_err(|e| ThemeFileError::InvalidColor(line, e))
+}
+
+fn color_role(name: &str) -> Option<ColorRole> {
+    let role = match name {
+        "background" => ColorRole::Background,
+        "surface" => ColorRole::Surface,
+        "text" => ColorRole::Text,
+        "text_muted" => ColorRole::TextMuted,
+        "accent" => ColorRole::Accent,
+        "on_accent" => ColorRole::OnAccent,
+        "border" => ColorRole::Border,
+        "focus" => ColorRole::Focus,
+        "error" => ColorRole::Error,
+        "on_error" => ColorRole::OnError,
+        "warning" => ColorRole::Warning,
+        "success" => ColorRole::Success,
+        _ => return None,
+    };
+
+    Some(role)
+}
+
+fn single<'a>(values: &[&'a str]) -> Option<&'a str> {
+    match values {
+        [value] => Some(value),
+        _ => None,
+    }
+}
+
+/// Cut the line at the first `#` outside of a quoted string
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..index],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+/// Split a value at whitespace, keeping quoted strings whole and without their quotes
+///
+/// Returns `None` for an unterminated quote.
+fn tokenize(value: &str) -> Option<Vec<&str>> {
+    let mut tokens = Vec::new();
+    let mut rest = value.trim_start();
+
+    while !rest.is_empty() {
+        let (token, remainder) = match rest.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"')?;
+                (&quoted[..end], &quoted[end + 1..])
+            }
+            None => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                rest.split_at(end)
+            }
+        };
+
+        tokens.push(token);
+        rest = remainder.trim_start();
+    }
+
+    Some(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use crate::style::{Border, BorderStyle};
+    use std::fs::File;
+    use std::time::Duration;
+
+    const OCEAN: &str = r##"
+# Ocean theme
+base = "dark"
+name = "Ocean"
+
+padding = 12 6
+border = 2 dashed "#2AA1B3"
+corner_radius = 6 6 0 0
+
+[colors]
+accent = "#2AA1B3"   # teal
+on_accent = "#000000"
+
+[font_sizes]
+body = 15
+
+[spacing]
+md = 10px
+"##;
+
+    /// Temporary theme file, removed on drop
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, contents: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("ho_gui_{}_{}.theme", name, std::process::id()));
+            fs::write(&path, contents).unwrap();
+            Self(path)
+        }
+
+        /// Rewrite the file with a new modification time, independent of timestamp resolution
+        fn rewrite(&self, contents: &str, age: u64) {
+            fs::write(&self.0, contents).unwrap();
+            let modified = SystemTime::now() - Duration::from_secs(age);
+            File::options()
+                .write(true)
+                .open(&self.0)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    mod parse {
+        use super::*;
+
+        #[test]
+        fn test_full_file() {
+            let theme: Theme = OCEAN.parse().unwrap();
+            let dark = Theme::dark();
+
+            assert_eq!(theme.name, "Ocean");
+            assert_eq!(theme.padding, Padding::symmetric(6.0, 12.0));
+            assert_eq!(
+                theme.border,
+                Border::dashed(2.0, Color::from_hex_str("#2AA1B3").unwrap())
+            );
+            assert_eq!(theme.corner_radius, CornerRadius::new(6.0, 6.0, 0.0, 0.0));
+            assert_eq!(theme.colors.accent, Color::from_hex_str("#2AA1B3").unwrap());
+            assert_eq!(theme.colors.on_accent, Color::BLACK);
+            assert_eq!(theme.font_sizes.body, 15.0);
+            assert_eq!(theme.spacing.md, 10.0);
+
+            // Everything else comes from the base
+            assert_eq!(theme.colors.background, dark.colors.background);
+            assert_eq!(theme.font_sizes.title, dark.font_sizes.title);
+            assert_eq!(theme.spacing.xs, dark.spacing.xs);
+        }
+
+        #[test]
+        fn test_empty_file_is_default() {
+            assert_eq!("".parse::<Theme>(), Ok(Theme::default()));
+            assert_eq!("# nothing\n\n".parse::<Theme>(), Ok(Theme::default()));
+            assert_eq!(
+                "base = \"high-contrast\"".parse(),
+                Ok(Theme::high_contrast())
+            );
+        }
+
+        #[test]
+        fn test_box_shorthand() {
+            let padding = |value: &str| {
+                format!("padding = {}", value)
+                    .parse::<Theme>()
+                    .map(|theme| theme.padding)
+            };
+
+            assert_eq!(padding("4"), Ok(Padding::all(4.0)));
+            assert_eq!(padding("1 2"), Ok(Padding::new(2.0, 2.0, 1.0, 1.0)));
+            assert_eq!(padding("1 2 3"), Ok(Padding::new(2.0, 2.0, 1.0, 3.0)));
+            assert_eq!(padding("1px 2 3 4px"), Ok(Padding::new(4.0, 2.0, 1.0, 3.0)));
+            assert_eq!(padding(""), Err(ThemeFileError::InvalidValue(1)));
+            assert_eq!(padding("1 2 3 4 5"), Err(ThemeFileError::InvalidValue(1)));
+            assert_eq!(padding("-1"), Err(ThemeFileError::InvalidValue(1)));
+            assert_eq!(padding("NaN"), Err(ThemeFileError::InvalidValue(1)));
+        }
+
+        #[test]
+        fn test_border_parts_in_any_order() {
+            let border = |value: &str| {
+                format!("border = {}", value)
+                    .parse::<Theme>()
+                    .map(|theme| theme.border)
+            };
+            let light = Theme::light().border;
+
+            assert_eq!(
+                border("\"#FF0000\" 3 double"),
+                Ok(Border::double(3.0, Color::RED))
+            );
+            assert_eq!(border("dotted"), Ok(light.with_style(BorderStyle::Dotted)));
+            assert_eq!(
+                border("4").map(|b| (b.width, b.color)),
+                Ok((4.0, light.color))
+            );
+            assert_eq!(border("1 2"), Err(ThemeFileError::InvalidValue(1)));
+            assert_eq!(border("1 wavy"), Err(ThemeFileError::InvalidValue(1)));
+        }
+
+        #[test]
+        fn test_errors_report_line() {
+            let parse = |source: &str| source.parse::<Theme>().unwrap_err();
+
+            assert_eq!(parse("\n\npadding 4"), ThemeFileError::InvalidLine(3));
+            assert_eq!(parse("[colors"), ThemeFileError::InvalidLine(1));
+            assert_eq!(parse("name = \"Ocean"), ThemeFileError::InvalidLine(1));
+            assert_eq!(parse("\n[widgets]"), ThemeFileError::UnknownSection(2));
+            assert_eq!(
+                parse("[colors]\nlink = \"#FFFFFF\""),
+                ThemeFileError::UnknownKey(2)
+            );
+            assert_eq!(
+                parse("[spacing]\nmd = 4 4"),
+                ThemeFileError::InvalidValue(2)
+            );
+            assert_eq!(parse("base = \"sepia\""), ThemeFileError::InvalidValue(1));
+            assert_eq!(
+                parse("[colors]\naccent = \"#12345\""),
+                ThemeFileError::InvalidColor(2, ColorParseError::InvalidLength)
+            );
+
+            // Unquoted colors are comments, so the value is missing
+            assert_eq!(
+                parse("[colors]\naccent = #FFFFFF"),
+                ThemeFileError::InvalidValue(2)
+            );
+        }
+
+        #[test]
+        fn test_base_resets_earlier_values() {
+            let theme: Theme = "padding = 1\nbase = \"dark\"".parse().unwrap();
+            assert_eq!(theme, Theme::dark());
+        }
+    }
+
+    mod watch {
+        use super::*;
+
+        #[test]
+        fn test_watcher_reports_each_change_once() {
+            let file = TempFile::new("watcher", "name = \"One\"");
+            let mut watcher = ThemeWatcher::new(&file.0);
+
+            assert_eq!(watcher.poll().unwrap().unwrap().name, "One");
+            assert!(watcher.poll().is_none());
+
+            file.rewrite("name = \"Two\"", 10);
+            assert_eq!(watcher.poll().unwrap().unwrap().name, "Two");
+            assert!(watcher.poll().is_none());
+
+            file.rewrite("name = ", 5);
+            assert_eq!(watcher.poll(), Some(Err(ThemeFileError::InvalidValue(1))));
+            assert!(watcher.poll().is_none());
+
+            fs::remove_file(&file.0).unwrap();
+            assert_eq!(
+                watcher.poll(),
+                Some(Err(ThemeFileError::Io(io::ErrorKind::NotFound)))
+            );
+            assert!(watcher.poll().is_none());
+        }
+
+        #[test]
+        fn test_app_hot_reload() {
+            let file = TempFile::new("app", OCEAN);
+            let mut app = App::new();
+
+            assert_eq!(app.reload_theme_file(), Ok(false));
+            assert_eq!(app.watch_theme_file(&file.0), Ok(()));
+            assert_eq!(app.theme().name, "Ocean");
+            assert_eq!(app.reload_theme_file(), Ok(false));
+
+            file.rewrite(&OCEAN.replace("body = 15", "body = 18"), 10);
+            assert_eq!(app.reload_theme_file(), Ok(true));
+            assert_eq!(app.theme().font_sizes.body, 18.0);
+
+            // A broken edit keeps the running theme
+            file.rewrite("[colors]\naccent = \"teal\"", 5);
+            assert_eq!(
+                app.reload_theme_file(),
+                Err(ThemeFileError::InvalidColor(
+                    2,
+                    ColorParseError::InvalidFormat
+                ))
+            );
+            assert_eq!(app.theme().font_sizes.body, 18.0);
+        }
+    }
+}
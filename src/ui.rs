@@ -0,0 +1,234 @@
+//! Immediate-mode UI context passed to widgets every frame
+
+use crate::theme::{StyleOverride, Theme};
+
+/// Per-frame UI context
+///
+/// # Notes
+///
+/// - Widgets read their look from [`Ui::style`], the app theme with every pushed override applied
+/// - Every [`Ui::push_style`] must be matched by a [`Ui::pop_style`] within the same frame,
+///   which [`Ui::end_frame`] checks
+///
+/// # Examples
+/// ```
+/// use ho_gui::color::Color;
+/// use ho_gui::style::Padding;
+/// use ho_gui::theme::{ColorRole, StyleOverride, Theme};
+/// use ho_gui::ui::Ui;
+///
+/// let mut ui = Ui::new(Theme::light());
+///
+/// ui.push_style(StyleOverride::Color(ColorRole::Accent, Color::RED));
+/// assert_eq!(ui.style().colors.accent, Color::RED);
+///
+/// let padding = ui.with_style(StyleOverride::Padding(Padding::zero()), |ui| ui.style().padding);
+/// assert_eq!(padding, Padding::zero());
+///
+/// ui.pop_style();
+/// assert_eq!(ui.end_frame(), Ok(()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ui {
+    style: Theme,
+    // Values replaced by each push, restored by the matching pop
+    style_stack: Vec<StyleOverride>,
+    style_underflow: bool,
+}
+
+impl Ui {
+    /// Create a UI context styled with `theme`
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            style: theme,
+            style_stack: Vec::new(),
+            style_underflow: false,
+        }
+    }
+
+    /// Start a new frame styled with `theme`, e.g. the current
+    /// [`App::theme`](crate::app::App::theme)
+    ///
+    /// # Notes
+    ///
+    /// - Overrides left over from a previous, unbalanced frame are dropped
+    pub fn begin_frame(&mut self, theme: &Theme) {
+        self.style.clone_from(theme);
+        self.style_stack.clear();
+        self.style_underflow = false;
+    }
+
+    /// Finish the frame and check that style pushes and pops matched
+    ///
+    /// # Errors
+    ///
+    /// * Returns `UiError::UnpoppedStyles` if some pushed styles were not popped. They are popped
+    ///   here, so the next frame starts with the plain theme.
+    /// * Returns `UiError::StyleStackUnderflow` if `pop_style` was called more often than
+    ///   `push_style`
+    pub fn end_frame(&mut self) -> Result<(), UiError> {
+        if std::mem::take(&mut self.style_underflow) {
+            self.pop_all_styles();
+            return Err(UiError::StyleStackUnderflow);
+        }
+
+        let unpopped = self.style_stack.len();
+        if unpopped > 0 {
+            self.pop_all_styles();
+            return Err(UiError::UnpoppedStyles(unpopped));
+        }
+
+        Ok(())
+    }
+
+    /// Current style: the theme with every pushed override applied
+    pub fn style(&self) -> &Theme {
+        &self.style
+    }
+
+    /// Override a theme value for the widgets added until the matching [`Ui::pop_style`]
+    pub fn push_style(&mut self, value: StyleOverride) {
+        let previous = self.style.apply(value);
+        self.style_stack.push(previous);
+    }
+
+    /// Undo the last [`Ui::push_style`]
+    ///
+    /// # Notes
+    ///
+    /// - Popping an empty stack does nothing here and is reported by [`Ui::end_frame`]
+    pub fn pop_style(&mut self) {
+        match self.style_stack.pop() {
+            Some(previous) => {
+                self.style.apply(previous);
+            }
+            None => self.style_underflow = true,
+        }
+    }
+
+    /// Override a theme value for the widgets added in `build`
+    ///
+    /// # Returns
+    ///
+    /// Value returned by `build`
+    pub fn with_style<R>(&mut self, value: StyleOverride, build: impl FnOnce(&mut Self) -> R) -> R {
+        self.push_style(value);
+        let result = build(self);
+        self.pop_style();
+
+        result
+    }
+
+    fn pop_all_styles(&mut self) {
+        while let Some(previous) = self.style_stack.pop() {
+            self.style.apply(previous);
+        }
+    }
+}
+
+/// Error type for UI frame checks
+#[derive(Debug, Clone, PartialEq)]
+pub enum UiError {
+    /// Frame ended with the given number of pushed styles not popped
+    UnpoppedStyles(usize),
+
+    /// `pop_style` was called without a matching `push_style`
+    StyleStackUnderflow,
+}
+
+impl std::fmt::Display for UiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UiError::UnpoppedStyles(count) => {
+                write!(f, "Frame ended with {} pushed style(s) not popped", count)
+            }
+            UiError::StyleStackUnderflow => {
+                write!(f, "pop_style called without a matching push_style")
+            }
+        }
+    }
+}
+
+impl std::error::Error for UiError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::style::Padding;
+    use crate::theme::ColorRole;
+
+    const RED_ACCENT: StyleOverride = StyleOverride::Color(ColorRole::Accent, Color::RED);
+    const NO_PADDING: StyleOverride = StyleOverride::Padding(Padding::zero());
+
+    #[test]
+    fn test_push_pop_restores_theme() {
+        let mut ui = Ui::new(Theme::dark());
+
+        ui.push_style(RED_ACCENT);
+        ui.push_style(NO_PADDING);
+        ui.push_style(StyleOverride::Color(ColorRole::Accent, Color::BLUE));
+        assert_eq!(ui.style().colors.accent, Color::BLUE);
+        assert_eq!(ui.style().padding, Padding::zero());
+
+        ui.pop_style();
+        assert_eq!(ui.style().colors.accent, Color::RED);
+
+        ui.pop_style();
+        ui.pop_style();
+        assert_eq!(ui.style(), &Theme::dark());
+        assert_eq!(ui.end_frame(), Ok(()));
+    }
+
+    #[test]
+    fn test_with_style_is_scoped() {
+        let mut ui = Ui::new(Theme::light());
+
+        let accent = ui.with_style(RED_ACCENT, |ui| {
+            ui.with_style(NO_PADDING, |ui| {
+                assert_eq!(ui.style().padding, Padding::zero());
+            });
+            assert_eq!(ui.style().padding, Theme::light().padding);
+
+            ui.style().colors.accent
+        });
+
+        assert_eq!(accent, Color::RED);
+        assert_eq!(ui.style(), &Theme::light());
+        assert_eq!(ui.end_frame(), Ok(()));
+    }
+
+    #[test]
+    fn test_unpopped_styles() {
+        let mut ui = Ui::new(Theme::light());
+        ui.push_style(RED_ACCENT);
+        ui.push_style(NO_PADDING);
+
+        assert_eq!(ui.end_frame(), Err(UiError::UnpoppedStyles(2)));
+
+        // Leftovers are dropped, so the next frame is balanced again
+        assert_eq!(ui.style(), &Theme::light());
+        assert_eq!(ui.end_frame(), Ok(()));
+    }
+
+    #[test]
+    fn test_style_stack_underflow() {
+        let mut ui = Ui::new(Theme::light());
+        ui.pop_style();
+        ui.push_style(RED_ACCENT);
+        ui.pop_style();
+
+        assert_eq!(ui.end_frame(), Err(UiError::StyleStackUnderflow));
+        assert_eq!(ui.end_frame(), Ok(()));
+    }
+
+    #[test]
+    fn test_begin_frame_picks_up_theme() {
+        let mut ui = Ui::new(Theme::light());
+        ui.push_style(RED_ACCENT);
+
+        ui.begin_frame(&Theme::dark());
+        assert_eq!(ui.style(), &Theme::dark());
+        assert_eq!(ui.end_frame(), Ok(()));
+    }
+}